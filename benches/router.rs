@@ -0,0 +1,164 @@
+use std::sync::Arc;
+
+use axum::{
+    body::Body,
+    http::{Request, StatusCode},
+    Router,
+};
+use criterion::{criterion_group, criterion_main, Criterion, Throughput};
+use http_body_util::BodyExt;
+use i_tantana::business::auth::Role;
+use i_tantana::business::credential::service::credential_service::CredentialService;
+use i_tantana::business::group::service::group_service::GroupService;
+use i_tantana::business::user::service::user_service::UserService;
+use i_tantana::business::user::{EmailAddress, Name, User};
+use i_tantana::business::webhook::service::webhook_service::WebhookService;
+use i_tantana::inbound::axum_adapter::idempotency::IdempotencyCache;
+use i_tantana::inbound::axum_adapter::rate_limit::RateLimiter;
+use i_tantana::inbound::axum_adapter::setup::{
+    setup, AppState, CredentialState, CrudState, TrafficState, WebhookState,
+};
+use i_tantana::inbound::axum_adapter::tenant::TenantResolver;
+use i_tantana::outbound::api_key_authenticator_adapter::static_api_key_authenticator::StaticApiKeyAuthenticator;
+use i_tantana::outbound::argon2_password_hasher_adapter::argon2_password_hasher::Argon2PasswordHasher;
+use i_tantana::outbound::event_publisher_adapter::broadcast_event_publisher::BroadcastEventPublisher;
+use i_tantana::outbound::id_generator_adapter::uuid_v7_generator::UuidV7Generator;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_audit_trail::InMemoryAuditTrail;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_credential_repository::InMemoryCredentialRepository;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_group_repository::InMemoryGroupRepository;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_idempotency_store::InMemoryIdempotencyStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_rate_limit_store::InMemoryRateLimitStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_user_repository::InMemoryUserRepository;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_verification_token_store::InMemoryVerificationTokenStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_webhook_delivery_store::InMemoryWebhookDeliveryStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_webhook_subscription_store::InMemoryWebhookSubscriptionStore;
+use i_tantana::outbound::jwt_authenticator_adapter::jwt_token_issuer::JwtTokenIssuer;
+use i_tantana::outbound::mailer_adapter::file_mailer::FileMailer;
+use i_tantana::outbound::repository_trait::RepositoryTrait;
+use i_tantana::outbound::webhook_sender_adapter::http_webhook_sender::HttpWebhookSender;
+use tokio::runtime::Runtime;
+use tower::ServiceExt;
+use uuid::Uuid;
+
+const API_KEY: &str = "bench-key";
+const USERS: usize = 10_000;
+
+async fn router() -> (Router, User) {
+    let user_repository = InMemoryUserRepository::new();
+    let mut last = None;
+    for index in 0..USERS {
+        last = Some(
+            user_repository
+                .save(&User::new(
+                    &Uuid::nil(),
+                    &Name::new(&format!("First{}", index % 1000)).unwrap(),
+                    &Name::new(&format!("Last{}", index % 100)).unwrap(),
+                    &EmailAddress::new(&format!("user{index}@example.com")).unwrap(),
+                ))
+                .await
+                .unwrap(),
+        );
+    }
+    let from = EmailAddress::new("no-reply@example.com").unwrap();
+    let group_repository = InMemoryGroupRepository::new();
+    let group_service = GroupService::new(group_repository.clone(), user_repository.clone());
+    let webhook_service = WebhookService::new(
+        InMemoryWebhookSubscriptionStore::new(),
+        InMemoryWebhookDeliveryStore::new(),
+        HttpWebhookSender::default(),
+    );
+    let user_service = UserService::new(
+        user_repository.clone(),
+        FileMailer::file(&from, std::path::Path::new("/dev/null")),
+        InMemoryVerificationTokenStore::new(),
+        BroadcastEventPublisher::default(),
+        InMemoryAuditTrail::new(),
+        group_repository.clone(),
+        UuidV7Generator,
+    );
+    let credential_service = CredentialService::new(
+        user_repository,
+        InMemoryCredentialRepository::new(),
+        Argon2PasswordHasher::new(),
+        JwtTokenIssuer::hs256(Uuid::new_v4().as_bytes()),
+    );
+    let authenticator =
+        StaticApiKeyAuthenticator::new().with_key(API_KEY, "bench", &[Role::new("admin")]);
+    let router = setup(
+        AppState {
+            user_service: Arc::new(user_service),
+        },
+        CredentialState {
+            credential_service: Arc::new(credential_service),
+        },
+        WebhookState {
+            webhook_service: Arc::new(webhook_service),
+        },
+        CrudState {
+            service: Arc::new(group_service),
+        },
+        TenantResolver::new(),
+        authenticator,
+        TrafficState {
+            rate_limiter: RateLimiter::new(InMemoryRateLimitStore::new()),
+            idempotency_cache: IdempotencyCache::new(InMemoryIdempotencyStore::new()),
+        },
+    )
+    .await;
+    (router, last.unwrap())
+}
+
+async fn call(router: &Router, request: Request<Body>, expected: StatusCode) {
+    let response = router.clone().oneshot(request).await.unwrap();
+    assert_eq!(response.status(), expected);
+    response.into_body().collect().await.unwrap();
+}
+
+fn bench_router(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let (router, user) = runtime.block_on(router());
+    let mut group = c.benchmark_group("router");
+    group.throughput(Throughput::Elements(1));
+    group.bench_function("find_one_user", |b| {
+        b.iter(|| {
+            let request = Request::get(format!("/user/{}", user.get_id()))
+                .header("x-api-key", API_KEY)
+                .body(Body::empty())
+                .unwrap();
+            runtime.block_on(call(&router, request, StatusCode::OK))
+        })
+    });
+    group.bench_function("find_users", |b| {
+        b.iter(|| {
+            let request = Request::get("/user?lastname=Last42&order_by=firstname")
+                .header("x-api-key", API_KEY)
+                .body(Body::empty())
+                .unwrap();
+            runtime.block_on(call(&router, request, StatusCode::OK))
+        })
+    });
+    let mut index = USERS;
+    group.bench_function("create_user", |b| {
+        b.iter(|| {
+            index += 1;
+            let request = Request::post("/user")
+                .header("x-api-key", API_KEY)
+                .header("content-type", "application/json")
+                .body(Body::from(format!(
+                    r#"{{"firstname":"John","lastname":"Doe","email":"bench{index}@example.com"}}"#
+                )))
+                .unwrap();
+            runtime.block_on(call(&router, request, StatusCode::CREATED))
+        })
+    });
+    group.bench_function("unauthenticated", |b| {
+        b.iter(|| {
+            let request = Request::get("/user").body(Body::empty()).unwrap();
+            runtime.block_on(call(&router, request, StatusCode::UNAUTHORIZED))
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_router);
+criterion_main!(benches);
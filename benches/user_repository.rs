@@ -0,0 +1,132 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use i_tantana::business::user::dtos::{UserFindRequest, UserFindRequestFilter};
+use i_tantana::business::user::{EmailAddress, Name, User};
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_user_repository::InMemoryUserRepository;
+use i_tantana::outbound::repository_trait::RepositoryTrait;
+use tokio::runtime::Runtime;
+use uuid::Uuid;
+
+const SIZES: [usize; 3] = [10_000, 100_000, 1_000_000];
+
+fn user(index: usize) -> User {
+    let mut user = User::new(
+        &Uuid::nil(),
+        &Name::new(&format!("First{}", index % 1000)).unwrap(),
+        &Name::new(&format!("Last{}", index % 100)).unwrap(),
+        &EmailAddress::new(&format!("user{index}@example.com")).unwrap(),
+    );
+    user.set_verified(&(index % 2 == 0));
+    user
+}
+
+fn repository(runtime: &Runtime, size: usize) -> (InMemoryUserRepository, User) {
+    runtime.block_on(async {
+        let repository = InMemoryUserRepository::new();
+        let mut last = None;
+        for index in 0..size {
+            last = Some(repository.save(&user(index)).await.unwrap());
+        }
+        (repository, last.unwrap())
+    })
+}
+
+fn request(filters: UserFindRequestFilter, order_by: &str) -> UserFindRequest {
+    UserFindRequest::new(&filters, order_by, &25, &1).unwrap()
+}
+
+fn bench_find_all(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("find_all");
+    group.sample_size(10);
+    for size in SIZES {
+        let (repository, _) = repository(&runtime, size);
+        // From one user to every user: exact email, 1% by last name, about
+        // 11% by email prefix, half by the unindexed verified flag, and all.
+        let cases = [
+            (
+                "email",
+                request(
+                    UserFindRequestFilter {
+                        email: Some(String::from("user42@example.com")),
+                        ..Default::default()
+                    },
+                    "",
+                ),
+            ),
+            (
+                "lastname",
+                request(
+                    UserFindRequestFilter {
+                        lastname: Some(String::from("Last42")),
+                        ..Default::default()
+                    },
+                    "firstname",
+                ),
+            ),
+            (
+                "email_prefix",
+                request(
+                    UserFindRequestFilter {
+                        email: Some(String::from("user1*")),
+                        ..Default::default()
+                    },
+                    "email",
+                ),
+            ),
+            (
+                "verified",
+                request(
+                    UserFindRequestFilter {
+                        verified: Some(true),
+                        ..Default::default()
+                    },
+                    "lastname",
+                ),
+            ),
+            ("order_by_lastname", request(Default::default(), "lastname")),
+        ];
+        for (name, options) in cases {
+            group.bench_with_input(BenchmarkId::new(name, size), &options, |b, options| {
+                b.iter(|| runtime.block_on(repository.find_all(options)).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+fn bench_update(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("update");
+    group.sample_size(10);
+    for size in SIZES {
+        let (repository, last) = repository(&runtime, size);
+        group.bench_with_input(BenchmarkId::from_parameter(size), &last, |b, last| {
+            b.iter(|| {
+                runtime
+                    .block_on(repository.update(last.get_id(), last))
+                    .unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_save(c: &mut Criterion) {
+    let runtime = Runtime::new().unwrap();
+    let mut group = c.benchmark_group("save");
+    group.sample_size(10);
+    for size in SIZES {
+        let (repository, _) = repository(&runtime, size);
+        let mut index = size;
+        group.bench_function(BenchmarkId::from_parameter(size), |b| {
+            b.iter(|| {
+                index += 1;
+                runtime.block_on(repository.save(&user(index))).unwrap()
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_save, bench_find_all, bench_update);
+criterion_main!(benches);
@@ -0,0 +1,25 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use i_tantana::business::user::{EmailAddress, Name};
+
+fn bench_name(c: &mut Criterion) {
+    let mut group = c.benchmark_group("name_new");
+    group.bench_function("valid", |b| {
+        b.iter(|| Name::new(black_box("  Johnathan  ")))
+    });
+    group.bench_function("invalid", |b| b.iter(|| Name::new(black_box("johnathan"))));
+    group.finish();
+}
+
+fn bench_email(c: &mut Criterion) {
+    let mut group = c.benchmark_group("email_address_new");
+    group.bench_function("valid", |b| {
+        b.iter(|| EmailAddress::new(black_box("john.doe+news@mail.example.com")))
+    });
+    group.bench_function("invalid", |b| {
+        b.iter(|| EmailAddress::new(black_box("john.doe@@example")))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_name, bench_email);
+criterion_main!(benches);
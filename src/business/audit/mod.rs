@@ -0,0 +1,6 @@
+pub mod model;
+pub mod ports;
+
+pub use model::{AuditContext, AuditEntry, AuditError, AuditRecord, FieldChange, GENESIS_HASH};
+
+pub use ports::AuditTrailTrait;
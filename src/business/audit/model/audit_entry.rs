@@ -0,0 +1,465 @@
+use std::{
+    collections::BTreeSet,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::{RequestContext, TenantId},
+    user::{User, UserOperation},
+};
+
+pub const GENESIS_HASH: &str = "0000000000000000000000000000000000000000000000000000000000000000";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct FieldChange {
+    field: String,
+    before: Option<Value>,
+    after: Option<Value>,
+}
+
+impl FieldChange {
+    pub fn get_field(&self) -> &str {
+        &self.field
+    }
+
+    pub fn get_before(&self) -> Option<&Value> {
+        self.before.as_ref()
+    }
+
+    pub fn get_after(&self) -> Option<&Value> {
+        self.after.as_ref()
+    }
+
+    pub fn diff(before: Option<&User>, after: Option<&User>) -> Vec<FieldChange> {
+        let before = Self::fields(before);
+        let after = Self::fields(after);
+        let keys: BTreeSet<&String> = before.keys().chain(after.keys()).collect();
+        keys.into_iter()
+            .filter(|key| key.as_str() != "id" && before.get(*key) != after.get(*key))
+            .map(|key| FieldChange {
+                field: key.to_string(),
+                before: before.get(key).cloned(),
+                after: after.get(key).cloned(),
+            })
+            .collect()
+    }
+
+    fn fields(user: Option<&User>) -> serde_json::Map<String, Value> {
+        match user.map(serde_json::to_value) {
+            Some(Ok(Value::Object(fields))) => fields,
+            _ => serde_json::Map::new(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    recorded_at: u64,
+    actor: String,
+    operation: UserOperation,
+    user_id: Uuid,
+    changes: Vec<FieldChange>,
+    request_id: String,
+    tenant: TenantId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_id: Option<Uuid>,
+}
+
+impl AuditRecord {
+    pub fn new(
+        context: &RequestContext,
+        operation: UserOperation,
+        before: Option<&User>,
+        after: Option<&User>,
+    ) -> Self {
+        let user_id = after.or(before).map_or(Uuid::nil(), |user| *user.get_id());
+        Self {
+            recorded_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            actor: context.get_principal().get_subject().to_string(),
+            operation,
+            user_id,
+            changes: FieldChange::diff(before, after),
+            request_id: context.get_request_id().to_string(),
+            tenant: context.get_tenant().clone(),
+            event_id: None,
+        }
+    }
+
+    /// Ties the record to the event of the change, so that appending it
+    /// again once the event is retried is ignored.
+    pub fn with_event_id(mut self, event_id: &Uuid) -> Self {
+        self.event_id = Some(*event_id);
+        self
+    }
+
+    pub fn get_user_id(&self) -> &Uuid {
+        &self.user_id
+    }
+
+    pub fn get_event_id(&self) -> Option<&Uuid> {
+        self.event_id.as_ref()
+    }
+
+    /// Keeps which fields changed but not what they held, so that erased data
+    /// is not copied into the trail.
+    pub fn without_previous_values(mut self) -> Self {
+        self.changes
+            .iter_mut()
+            .for_each(|change| change.before = None);
+        self
+    }
+
+    /// Drops the values of the changes, keeping which fields changed.
+    pub fn redact(&mut self) {
+        self.changes.iter_mut().for_each(|change| {
+            change.before = None;
+            change.after = None;
+        });
+    }
+}
+
+/// The request and the operation under which a transaction changes users,
+/// so that every change it records is audited with it.
+#[derive(Clone, Debug)]
+pub struct AuditContext {
+    context: RequestContext,
+    operation: UserOperation,
+}
+
+impl AuditContext {
+    pub fn new(context: &RequestContext, operation: UserOperation) -> Self {
+        Self {
+            context: context.clone(),
+            operation,
+        }
+    }
+
+    /// An erasure keeps which fields changed but not what they held.
+    pub fn record(
+        &self,
+        event_id: &Uuid,
+        before: Option<&User>,
+        after: Option<&User>,
+    ) -> AuditRecord {
+        let record =
+            AuditRecord::new(&self.context, self.operation, before, after).with_event_id(event_id);
+        match self.operation {
+            UserOperation::Erase => record.without_previous_values(),
+            _ => record,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    sequence: u64,
+    recorded_at: u64,
+    actor: String,
+    operation: UserOperation,
+    user_id: Uuid,
+    changes: Vec<FieldChange>,
+    request_id: String,
+    /// Omitted for the default tenant so entries sealed before tenants existed keep their hash.
+    #[serde(default, skip_serializing_if = "TenantId::is_default")]
+    tenant: TenantId,
+    /// Salted digest of the changes, hashed in their place so that their
+    /// values can be redacted without breaking the chain. Entries sealed
+    /// before it existed hash their changes and cannot be redacted.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    changes_digest: Option<String>,
+    /// Dropped on redaction, so the digest cannot confirm a guessed value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    changes_salt: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    event_id: Option<Uuid>,
+    previous_hash: String,
+    hash: String,
+}
+
+impl AuditEntry {
+    pub fn seal(record: &AuditRecord, sequence: &u64, previous_hash: &str) -> Self {
+        let mut salt = [0u8; 16];
+        rand::rngs::OsRng.fill_bytes(&mut salt);
+        let salt = hex::encode(salt);
+        let mut entry = Self {
+            sequence: *sequence,
+            recorded_at: record.recorded_at,
+            actor: record.actor.clone(),
+            operation: record.operation,
+            user_id: record.user_id,
+            changes: record.changes.clone(),
+            request_id: record.request_id.clone(),
+            tenant: record.tenant.clone(),
+            changes_digest: Some(Self::digest_changes(&record.changes, &salt)),
+            changes_salt: Some(salt),
+            event_id: record.event_id,
+            previous_hash: previous_hash.to_string(),
+            hash: String::new(),
+        };
+        entry.hash = entry.compute_hash();
+        entry
+    }
+
+    fn digest_changes(changes: &[FieldChange], salt: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(salt.as_bytes());
+        hasher.update(serde_json::to_vec(changes).unwrap_or_default());
+        hex::encode(hasher.finalize())
+    }
+
+    fn compute_hash(&self) -> String {
+        let mut unsealed = Self {
+            hash: String::new(),
+            ..self.clone()
+        };
+        if unsealed.changes_digest.is_some() {
+            unsealed.changes = Vec::new();
+            unsealed.changes_salt = None;
+        }
+        let canonical = serde_json::to_vec(&unsealed).unwrap_or_default();
+        hex::encode(Sha256::digest(canonical))
+    }
+
+    /// Whether the changes still match their digest; once redacted they only
+    /// name the fields and hold no value.
+    fn has_intact_changes(&self) -> bool {
+        match (&self.changes_digest, &self.changes_salt) {
+            (Some(digest), Some(salt)) => digest.eq(&Self::digest_changes(&self.changes, salt)),
+            (Some(_), None) => self
+                .changes
+                .iter()
+                .all(|change| change.before.is_none() && change.after.is_none()),
+            (None, _) => true,
+        }
+    }
+
+    /// Drops the values of the changes, keeping which fields changed. Returns
+    /// false for entries sealed without a digest, which cannot be redacted.
+    pub fn redact(&mut self) -> bool {
+        if self.changes_digest.is_none() {
+            return false;
+        }
+        self.changes.iter_mut().for_each(|change| {
+            change.before = None;
+            change.after = None;
+        });
+        self.changes_salt = None;
+        true
+    }
+
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn get_recorded_at(&self) -> u64 {
+        self.recorded_at
+    }
+
+    pub fn get_actor(&self) -> &str {
+        &self.actor
+    }
+
+    pub fn get_operation(&self) -> UserOperation {
+        self.operation
+    }
+
+    pub fn get_user_id(&self) -> &Uuid {
+        &self.user_id
+    }
+
+    pub fn get_changes(&self) -> &[FieldChange] {
+        &self.changes
+    }
+
+    pub fn get_request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    pub fn get_event_id(&self) -> Option<&Uuid> {
+        self.event_id.as_ref()
+    }
+
+    pub fn get_previous_hash(&self) -> &str {
+        &self.previous_hash
+    }
+
+    pub fn get_hash(&self) -> &str {
+        &self.hash
+    }
+
+    /// The entry already appended for the event of `record`, if any. Retried
+    /// events are the latest ones, so the search starts from the end.
+    pub fn find_event<'a>(entries: &'a [AuditEntry], record: &AuditRecord) -> Option<&'a Self> {
+        let event_id = record.event_id?;
+        entries
+            .iter()
+            .rev()
+            .find(|entry| entry.event_id == Some(event_id))
+    }
+
+    /// Checks that the entries form an unbroken chain starting at the genesis hash.
+    pub fn verify_chain<'a>(
+        entries: impl IntoIterator<Item = &'a AuditEntry>,
+    ) -> Result<u64, AuditError> {
+        let mut previous_hash = GENESIS_HASH.to_string();
+        let mut expected_sequence = 1;
+        for entry in entries {
+            if entry.sequence != expected_sequence
+                || entry.previous_hash != previous_hash
+                || entry.hash != entry.compute_hash()
+                || !entry.has_intact_changes()
+            {
+                return Err(AuditError::TamperedEntry {
+                    sequence: entry.sequence,
+                });
+            }
+            previous_hash = entry.hash.clone();
+            expected_sequence += 1;
+        }
+        Ok(expected_sequence - 1)
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum AuditError {
+    #[error("Audit entry {sequence} does not match the hash chain")]
+    TamperedEntry { sequence: u64 },
+    #[error("Audit trail could not be written: {reason}")]
+    Storage { reason: String },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::{
+        auth::{AuthenticationMethod, Principal, RequestContext, Role},
+        user::{EmailAddress, Name, User, UserOperation},
+    };
+
+    use super::{AuditEntry, AuditError, AuditRecord, GENESIS_HASH};
+
+    fn context() -> RequestContext {
+        RequestContext::new(
+            &Principal::new(
+                "admin",
+                None,
+                &[Role::new("admin")],
+                AuthenticationMethod::ApiKey,
+            ),
+            "req-1",
+        )
+    }
+
+    #[test]
+    fn test_field_level_diff() {
+        let before = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let after = User::new(
+            before.get_id(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john.doe@example.com").unwrap(),
+        );
+        let record = AuditRecord::new(
+            &context(),
+            UserOperation::Update,
+            Some(&before),
+            Some(&after),
+        );
+        let entry = AuditEntry::seal(&record, &1, GENESIS_HASH);
+        assert_eq!(entry.get_changes().len(), 1);
+        assert_eq!(entry.get_changes()[0].get_field(), "email");
+        assert_eq!(
+            entry.get_changes()[0].get_before().unwrap(),
+            "john@example.com"
+        );
+        assert_eq!(
+            entry.get_changes()[0].get_after().unwrap(),
+            "john.doe@example.com"
+        );
+        assert_eq!(entry.get_request_id(), "req-1");
+        assert_eq!(entry.get_actor(), "admin");
+    }
+
+    #[test]
+    fn test_chain_detects_tampering() {
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let first = AuditEntry::seal(
+            &AuditRecord::new(&context(), UserOperation::Create, None, Some(&user)),
+            &1,
+            GENESIS_HASH,
+        );
+        let second = AuditEntry::seal(
+            &AuditRecord::new(&context(), UserOperation::Delete, Some(&user), None),
+            &2,
+            first.get_hash(),
+        );
+        assert_eq!(AuditEntry::verify_chain([&first, &second]).unwrap(), 2);
+
+        let mut forged = first.clone();
+        forged.actor = "someone-else".to_string();
+        assert!(matches!(
+            AuditEntry::verify_chain([&forged, &second]),
+            Err(AuditError::TamperedEntry { sequence: 1 })
+        ));
+        assert!(AuditEntry::verify_chain([&second]).is_err());
+    }
+
+    #[test]
+    fn test_redaction_keeps_chain() {
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let first = AuditEntry::seal(
+            &AuditRecord::new(&context(), UserOperation::Create, None, Some(&user)),
+            &1,
+            GENESIS_HASH,
+        );
+        let mut redacted = first.clone();
+        assert!(redacted.redact());
+        assert_eq!(redacted.get_hash(), first.get_hash());
+        assert!(redacted
+            .get_changes()
+            .iter()
+            .all(|change| change.get_after().is_none()));
+        assert!(!serde_json::to_string(&redacted)
+            .unwrap()
+            .contains("john@example.com"));
+        assert_eq!(AuditEntry::verify_chain([&redacted]).unwrap(), 1);
+
+        let mut forged = first.clone();
+        forged.changes[0].after = Some(serde_json::json!("forged"));
+        assert!(AuditEntry::verify_chain([&forged]).is_err());
+        forged.changes_salt = None;
+        assert!(AuditEntry::verify_chain([&forged]).is_err());
+    }
+}
@@ -0,0 +1,5 @@
+pub mod audit_entry;
+
+pub use audit_entry::{
+    AuditContext, AuditEntry, AuditError, AuditRecord, FieldChange, GENESIS_HASH,
+};
@@ -0,0 +1,27 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::audit::{AuditEntry, AuditError, AuditRecord};
+
+pub trait AuditTrailTrait: Sync + Send + Clone + 'static {
+    /// Appending a record tied to an event already in the trail returns the
+    /// entry of that event instead of a new one.
+    fn append(
+        &self,
+        record: &AuditRecord,
+    ) -> impl Future<Output = Result<AuditEntry, AuditError>> + Send;
+
+    fn find_by_user(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<AuditEntry>, AuditError>> + Send;
+
+    /// Drops the values recorded in the entries of the user, keeping the
+    /// chain verifiable. Returns the number of redacted entries.
+    fn redact_user(&self, user_id: &Uuid)
+        -> impl Future<Output = Result<usize, AuditError>> + Send;
+
+    /// Returns the number of entries of the verified chain.
+    fn verify_chain(&self) -> impl Future<Output = Result<u64, AuditError>> + Send;
+}
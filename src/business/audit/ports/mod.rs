@@ -0,0 +1,3 @@
+pub mod audit_trail_trait;
+
+pub use audit_trail_trait::AuditTrailTrait;
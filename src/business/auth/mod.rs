@@ -0,0 +1,10 @@
+pub mod model;
+pub mod ports;
+pub mod service;
+
+pub use model::{
+    AuthError, AuthToken, AuthenticationMethod, Principal, RequestContext, Role, TenantId,
+    TenantIdError, CROSS_TENANT_ROLE,
+};
+
+pub use ports::AuthenticatorTrait;
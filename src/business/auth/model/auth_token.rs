@@ -0,0 +1,27 @@
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AuthToken {
+    Bearer(String),
+    ApiKey(String),
+}
+
+#[derive(Debug, Error)]
+pub enum AuthError {
+    #[error("Authentication required")]
+    MissingCredentials,
+    #[error("Credentials type is not supported")]
+    UnsupportedCredentials,
+    #[error("Invalid token: {reason}")]
+    InvalidToken { reason: String },
+    #[error("Token has expired")]
+    ExpiredToken,
+    #[error("Unknown api key")]
+    UnknownApiKey,
+    #[error("Credentials are not valid for tenant {tenant}")]
+    TenantMismatch { tenant: String },
+    #[error("Cannot load key {source_name}: {reason}")]
+    InvalidKey { source_name: String, reason: String },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
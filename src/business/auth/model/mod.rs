@@ -0,0 +1,9 @@
+pub mod auth_token;
+pub mod principal;
+pub mod request_context;
+pub mod tenant_id;
+
+pub use auth_token::{AuthError, AuthToken};
+pub use principal::{AuthenticationMethod, Principal, Role, CROSS_TENANT_ROLE};
+pub use request_context::RequestContext;
+pub use tenant_id::{TenantId, TenantIdError};
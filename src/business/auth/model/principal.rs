@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use std::{fmt::Display, ops::Deref};
+use uuid::Uuid;
+
+use super::TenantId;
+
+/// Lets a principal without a tenant of its own act for any tenant.
+pub const CROSS_TENANT_ROLE: &str = "cross-tenant";
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Principal {
+    subject: String,
+    user_id: Option<Uuid>,
+    roles: Vec<Role>,
+    method: AuthenticationMethod,
+    tenant: Option<TenantId>,
+}
+
+impl Principal {
+    pub fn new(
+        subject: &str,
+        user_id: Option<&Uuid>,
+        roles: &[Role],
+        method: AuthenticationMethod,
+    ) -> Self {
+        Self {
+            subject: subject.to_string(),
+            user_id: user_id.copied(),
+            roles: roles.to_vec(),
+            method,
+            tenant: None,
+        }
+    }
+
+    /// Binds the principal to a tenant, such as the one claimed by its token.
+    pub fn with_tenant(mut self, tenant: &TenantId) -> Self {
+        self.tenant = Some(tenant.clone());
+        self
+    }
+
+    pub fn get_subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn get_user_id(&self) -> Option<&Uuid> {
+        self.user_id.as_ref()
+    }
+
+    pub fn get_roles(&self) -> &[Role] {
+        &self.roles
+    }
+
+    pub fn get_method(&self) -> &AuthenticationMethod {
+        &self.method
+    }
+
+    pub fn get_tenant(&self) -> Option<&TenantId> {
+        self.tenant.as_ref()
+    }
+
+    pub fn has_role(&self, role: &Role) -> bool {
+        self.roles.contains(role)
+    }
+
+    /// A principal bound to a tenant may only act for it; an unbound one for
+    /// the default tenant, or for any with the cross-tenant role.
+    pub fn can_act_for(&self, tenant: &TenantId) -> bool {
+        match &self.tenant {
+            Some(own) => own.eq(tenant),
+            None => tenant.is_default() || self.has_role(&Role::new(CROSS_TENANT_ROLE)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+pub enum AuthenticationMethod {
+    Jwt,
+    ApiKey,
+    /// Proof of the mailbox of the user, by the token mailed to it.
+    VerificationToken,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(from = "String")]
+pub struct Role(String);
+
+impl Role {
+    pub fn new(raw: &str) -> Self {
+        Self(raw.trim().to_lowercase())
+    }
+}
+
+impl From<String> for Role {
+    fn from(value: String) -> Self {
+        Self::new(&value)
+    }
+}
+
+impl From<&str> for Role {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl Deref for Role {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Role {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
@@ -0,0 +1,35 @@
+use super::{Principal, TenantId};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RequestContext {
+    principal: Principal,
+    request_id: String,
+    tenant: TenantId,
+}
+
+impl RequestContext {
+    pub fn new(principal: &Principal, request_id: &str) -> Self {
+        Self {
+            principal: principal.clone(),
+            request_id: request_id.to_string(),
+            tenant: TenantId::default(),
+        }
+    }
+
+    pub fn with_tenant(mut self, tenant: &TenantId) -> Self {
+        self.tenant = tenant.clone();
+        self
+    }
+
+    pub fn get_principal(&self) -> &Principal {
+        &self.principal
+    }
+
+    pub fn get_request_id(&self) -> &str {
+        &self.request_id
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+}
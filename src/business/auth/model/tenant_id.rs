@@ -0,0 +1,91 @@
+use std::{fmt::Display, ops::Deref};
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+lazy_static! {
+    static ref TENANT_REGEX: regex::Regex =
+        regex::Regex::new(r"^[a-z0-9]([a-z0-9-]{0,61}[a-z0-9])?$").unwrap();
+}
+
+const DEFAULT_TENANT: &str = "default";
+
+/// Tenant owning a user; usable as a DNS label so it can be resolved from a
+/// subdomain.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "&str")]
+pub struct TenantId(String);
+
+impl TenantId {
+    pub fn new(raw: &str) -> Result<Self, TenantIdError> {
+        let normalized = raw.trim().to_lowercase();
+        if TENANT_REGEX.is_match(&normalized) {
+            return Ok(Self(normalized));
+        }
+        Err(TenantIdError {
+            invalid_tenant: raw.to_string(),
+        })
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_TENANT
+    }
+}
+
+impl Default for TenantId {
+    fn default() -> Self {
+        Self(DEFAULT_TENANT.to_string())
+    }
+}
+
+impl TryFrom<&str> for TenantId {
+    type Error = TenantIdError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Deref for TenantId {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for TenantId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("{invalid_tenant} is not a valid tenant. Tenant must be 1 to 63 lowercase letters, digits or inner hyphens.")]
+pub struct TenantIdError {
+    pub invalid_tenant: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TenantId;
+
+    #[test]
+    fn test_tenant_id() {
+        assert_eq!(&*TenantId::new(" Acme-Corp ").unwrap(), "acme-corp");
+        assert_eq!(&*TenantId::default(), "default");
+        assert!(TenantId::default().is_default());
+        for invalid in [
+            "",
+            "-acme",
+            "acme-",
+            "acme.corp",
+            "acme_corp",
+            &"a".repeat(64),
+        ] {
+            assert!(TenantId::new(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -0,0 +1,10 @@
+use std::future::Future;
+
+use crate::business::auth::{AuthError, AuthToken, Principal};
+
+pub trait AuthenticatorTrait: Sync + Send + Clone + 'static {
+    fn authenticate(
+        &self,
+        token: &AuthToken,
+    ) -> impl Future<Output = Result<Principal, AuthError>> + Send;
+}
@@ -0,0 +1,3 @@
+pub mod authenticator_trait;
+
+pub use authenticator_trait::AuthenticatorTrait;
@@ -0,0 +1,41 @@
+use std::future::Future;
+
+use crate::business::auth::{AuthError, AuthToken, AuthenticatorTrait, Principal};
+
+#[derive(Debug, Clone)]
+pub struct AuthenticatorChain<A, B>
+where
+    A: AuthenticatorTrait,
+    B: AuthenticatorTrait,
+{
+    first: A,
+    second: B,
+}
+
+impl<A, B> AuthenticatorChain<A, B>
+where
+    A: AuthenticatorTrait,
+    B: AuthenticatorTrait,
+{
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A, B> AuthenticatorTrait for AuthenticatorChain<A, B>
+where
+    A: AuthenticatorTrait,
+    B: AuthenticatorTrait,
+{
+    fn authenticate(
+        &self,
+        token: &AuthToken,
+    ) -> impl Future<Output = Result<Principal, AuthError>> + Send {
+        Box::pin(async move {
+            match self.first.authenticate(token).await {
+                Err(AuthError::UnsupportedCredentials) => self.second.authenticate(token).await,
+                result => result,
+            }
+        })
+    }
+}
@@ -0,0 +1 @@
+pub mod authenticator_chain;
@@ -0,0 +1,25 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Clone, Deserialize, ToSchema)]
+pub struct ChangePasswordRequest {
+    current_password: String,
+    new_password: String,
+}
+
+impl ChangePasswordRequest {
+    pub fn new(current_password: &str, new_password: &str) -> Self {
+        Self {
+            current_password: current_password.to_string(),
+            new_password: new_password.to_string(),
+        }
+    }
+
+    pub fn get_current_password(&self) -> &str {
+        &self.current_password
+    }
+
+    pub fn get_new_password(&self) -> &str {
+        &self.new_password
+    }
+}
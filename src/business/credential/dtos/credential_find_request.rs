@@ -0,0 +1,80 @@
+use uuid::Uuid;
+
+use crate::{
+    business::credential::PasswordCredential,
+    outbound::repository_trait::{FindOptionTrait, FindResultTrait},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialFindRequest {
+    filters: CredentialFindRequestFilter,
+    per_page: u16,
+    offset: u64,
+}
+
+impl CredentialFindRequest {
+    pub fn new(filters: &CredentialFindRequestFilter, per_page: &u16, offset: &u64) -> Self {
+        Self {
+            filters: filters.clone(),
+            per_page: (*per_page).max(1),
+            offset: (*offset).max(1),
+        }
+    }
+}
+
+impl Default for CredentialFindRequest {
+    fn default() -> Self {
+        Self {
+            filters: CredentialFindRequestFilter::default(),
+            per_page: 25,
+            offset: 1,
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct CredentialFindRequestFilter {
+    pub user_id: Option<Uuid>,
+    pub locked: Option<bool>,
+}
+
+impl FindOptionTrait for CredentialFindRequest {
+    type QueryFilter = CredentialFindRequestFilter;
+    fn get_query(&self) -> Self::QueryFilter {
+        self.filters.clone()
+    }
+    fn get_order_by(&self) -> String {
+        String::from("user_id")
+    }
+    fn get_offset(&self) -> u64 {
+        self.offset
+    }
+    fn get_limit(&self) -> u16 {
+        self.per_page
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CredentialFindResponse {
+    credentials: Vec<PasswordCredential>,
+    num_pages: u64,
+}
+
+impl CredentialFindResponse {
+    pub fn new(credentials: Vec<PasswordCredential>, num_pages: u64) -> Self {
+        Self {
+            credentials,
+            num_pages,
+        }
+    }
+}
+
+impl FindResultTrait for CredentialFindResponse {
+    type Entity = PasswordCredential;
+    fn get_result(&self) -> impl Iterator<Item = Self::Entity> {
+        self.credentials.clone().into_iter()
+    }
+    fn get_page_count(&self) -> u64 {
+        self.num_pages
+    }
+}
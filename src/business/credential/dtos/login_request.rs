@@ -0,0 +1,27 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::business::user::EmailAddress;
+
+#[derive(Clone, Deserialize, ToSchema)]
+pub struct LoginRequest {
+    email: EmailAddress,
+    password: String,
+}
+
+impl LoginRequest {
+    pub fn new(email: &EmailAddress, password: &str) -> Self {
+        Self {
+            email: email.clone(),
+            password: password.to_string(),
+        }
+    }
+
+    pub fn get_email(&self) -> &EmailAddress {
+        &self.email
+    }
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+}
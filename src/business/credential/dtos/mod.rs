@@ -0,0 +1,15 @@
+pub mod change_password_request;
+pub mod credential_find_request;
+pub mod login_request;
+pub mod refresh_request;
+pub mod set_password_request;
+pub mod set_roles_request;
+
+pub use change_password_request::ChangePasswordRequest;
+pub use credential_find_request::{
+    CredentialFindRequest, CredentialFindRequestFilter, CredentialFindResponse,
+};
+pub use login_request::LoginRequest;
+pub use refresh_request::RefreshRequest;
+pub use set_password_request::SetPasswordRequest;
+pub use set_roles_request::SetRolesRequest;
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Clone, Deserialize, ToSchema)]
+pub struct RefreshRequest {
+    refresh_token: String,
+}
+
+impl RefreshRequest {
+    pub fn new(refresh_token: &str) -> Self {
+        Self {
+            refresh_token: refresh_token.to_string(),
+        }
+    }
+
+    pub fn get_refresh_token(&self) -> &str {
+        &self.refresh_token
+    }
+}
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Clone, Deserialize, ToSchema)]
+pub struct SetPasswordRequest {
+    password: String,
+}
+
+impl SetPasswordRequest {
+    pub fn new(password: &str) -> Self {
+        Self {
+            password: password.to_string(),
+        }
+    }
+
+    pub fn get_password(&self) -> &str {
+        &self.password
+    }
+}
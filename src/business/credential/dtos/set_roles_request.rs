@@ -0,0 +1,22 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::business::auth::Role;
+
+#[derive(Clone, Deserialize, ToSchema)]
+pub struct SetRolesRequest {
+    #[schema(value_type = Vec<String>)]
+    roles: Vec<Role>,
+}
+
+impl SetRolesRequest {
+    pub fn new(roles: &[Role]) -> Self {
+        Self {
+            roles: roles.to_vec(),
+        }
+    }
+
+    pub fn get_roles(&self) -> &[Role] {
+        &self.roles
+    }
+}
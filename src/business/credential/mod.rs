@@ -0,0 +1,16 @@
+pub mod dtos;
+pub mod model;
+pub mod ports;
+pub mod service;
+
+pub use dtos::{
+    ChangePasswordRequest, CredentialFindRequest, CredentialFindResponse, LoginRequest,
+    RefreshRequest, SetPasswordRequest, SetRolesRequest,
+};
+pub use model::{
+    AuthTokens, CredentialError, LockoutPolicy, PasswordCredential, PasswordError, PasswordPolicy,
+};
+
+pub use ports::{
+    CredentialRepositoryTrait, CredentialServiceTrait, PasswordHasherTrait, TokenIssuerTrait,
+};
@@ -0,0 +1,33 @@
+use serde::Serialize;
+use utoipa::ToSchema;
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, ToSchema)]
+pub struct AuthTokens {
+    access_token: String,
+    refresh_token: String,
+    token_type: String,
+    expires_in: u64,
+}
+
+impl AuthTokens {
+    pub fn new(access_token: &str, refresh_token: &str, expires_in: u64) -> Self {
+        Self {
+            access_token: access_token.to_string(),
+            refresh_token: refresh_token.to_string(),
+            token_type: String::from("Bearer"),
+            expires_in,
+        }
+    }
+
+    pub fn get_access_token(&self) -> &str {
+        &self.access_token
+    }
+
+    pub fn get_refresh_token(&self) -> &str {
+        &self.refresh_token
+    }
+
+    pub fn get_expires_in(&self) -> u64 {
+        self.expires_in
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::{Duration, SystemTime};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::Role,
+    credential::PasswordError,
+    user::{model::user::UserError, UserOperation, UserStatus},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PasswordCredential {
+    user_id: Uuid,
+    password_hash: String,
+    roles: Vec<Role>,
+    failed_attempts: u32,
+    locked_until: Option<SystemTime>,
+    token_version: u32,
+}
+
+impl PasswordCredential {
+    pub fn new(user_id: &Uuid, password_hash: &str, roles: &[Role]) -> Self {
+        Self {
+            user_id: *user_id,
+            password_hash: password_hash.to_string(),
+            roles: roles.to_vec(),
+            failed_attempts: 0,
+            locked_until: None,
+            token_version: 0,
+        }
+    }
+
+    pub fn get_user_id(&self) -> &Uuid {
+        &self.user_id
+    }
+
+    pub fn get_password_hash(&self) -> &str {
+        &self.password_hash
+    }
+
+    pub fn get_roles(&self) -> &[Role] {
+        &self.roles
+    }
+
+    pub fn get_failed_attempts(&self) -> u32 {
+        self.failed_attempts
+    }
+
+    pub fn get_locked_until(&self) -> Option<&SystemTime> {
+        self.locked_until.as_ref()
+    }
+
+    /// Carried by the refresh tokens, which are revoked once it changes.
+    pub fn get_token_version(&self) -> u32 {
+        self.token_version
+    }
+
+    pub fn set_password_hash(&mut self, password_hash: &str) {
+        self.password_hash = password_hash.to_string();
+        self.failed_attempts = 0;
+        self.locked_until = None;
+        self.token_version = self.token_version.wrapping_add(1);
+    }
+
+    pub fn set_roles(&mut self, roles: &[Role]) {
+        self.roles = roles.to_vec();
+    }
+
+    pub fn is_locked(&self, now: &SystemTime) -> bool {
+        self.locked_until.is_some_and(|until| &until > now)
+    }
+
+    pub fn remaining_lock(&self, now: &SystemTime) -> Option<Duration> {
+        self.locked_until
+            .and_then(|until| until.duration_since(*now).ok())
+    }
+
+    pub fn record_failure(&mut self, policy: &LockoutPolicy, now: &SystemTime) {
+        self.failed_attempts += 1;
+        if self.failed_attempts >= policy.max_failed_attempts {
+            self.failed_attempts = 0;
+            self.locked_until = Some(*now + policy.lock_duration);
+        }
+    }
+
+    pub fn record_success(&mut self) {
+        self.failed_attempts = 0;
+        self.locked_until = None;
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LockoutPolicy {
+    max_failed_attempts: u32,
+    lock_duration: Duration,
+}
+
+impl Default for LockoutPolicy {
+    fn default() -> Self {
+        Self {
+            max_failed_attempts: 5,
+            lock_duration: Duration::from_secs(15 * 60),
+        }
+    }
+}
+
+impl LockoutPolicy {
+    pub fn new(max_failed_attempts: u32, lock_duration: Duration) -> Self {
+        Self {
+            max_failed_attempts: max_failed_attempts.max(1),
+            lock_duration,
+        }
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum CredentialError {
+    #[error("Invalid email or password")]
+    InvalidCredentials,
+    #[error("Account locked, retry in {retry_after} seconds")]
+    AccountLocked { retry_after: u64 },
+    #[error("Account is {status}")]
+    AccountDisabled { status: UserStatus },
+    #[error("Current password does not match")]
+    CurrentPasswordMismatch,
+    #[error("Invalid refresh token")]
+    InvalidRefreshToken,
+    #[error(transparent)]
+    WeakPassword(#[from] PasswordError),
+    #[error("User with id {id} does not exists")]
+    UserNotExists { id: Uuid },
+    #[error("Credential for user {id} does not exists")]
+    CredentialNotExists { id: Uuid },
+    #[error("Credential for user {id} already exists")]
+    CredentialAlreadyExists { id: Uuid },
+    #[error("Operation {operation} forbidden")]
+    Forbidden { operation: UserOperation },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl From<UserError> for CredentialError {
+    fn from(val: UserError) -> Self {
+        match val {
+            UserError::UserNotExists { id } => CredentialError::UserNotExists { id },
+            UserError::Forbidden { operation } => CredentialError::Forbidden { operation },
+            UserError::Unknown(e) => CredentialError::Unknown(e),
+            e => CredentialError::Unknown(anyhow::anyhow!(e.to_string())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use uuid::Uuid;
+
+    use super::{LockoutPolicy, PasswordCredential};
+
+    #[test]
+    fn test_lockout_after_max_failures() {
+        let policy = LockoutPolicy::new(3, Duration::from_secs(60));
+        let now = SystemTime::now();
+        let mut credential = PasswordCredential::new(&Uuid::new_v4(), "hash", &[]);
+        credential.record_failure(&policy, &now);
+        credential.record_failure(&policy, &now);
+        assert!(!credential.is_locked(&now));
+        credential.record_failure(&policy, &now);
+        assert!(credential.is_locked(&now));
+        assert!(!credential.is_locked(&(now + Duration::from_secs(61))));
+    }
+
+    #[test]
+    fn test_success_resets_failures() {
+        let policy = LockoutPolicy::new(3, Duration::from_secs(60));
+        let now = SystemTime::now();
+        let mut credential = PasswordCredential::new(&Uuid::new_v4(), "hash", &[]);
+        credential.record_failure(&policy, &now);
+        credential.record_success();
+        assert_eq!(credential.get_failed_attempts(), 0);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod auth_tokens;
+pub mod credential;
+pub mod password;
+
+pub use auth_tokens::AuthTokens;
+pub use credential::{CredentialError, LockoutPolicy, PasswordCredential};
+pub use password::{PasswordError, PasswordPolicy};
@@ -0,0 +1,122 @@
+use thiserror::Error;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PasswordPolicy {
+    min_length: usize,
+    require_uppercase: bool,
+    require_lowercase: bool,
+    require_digit: bool,
+    require_symbol: bool,
+}
+
+impl Default for PasswordPolicy {
+    fn default() -> Self {
+        Self {
+            min_length: 12,
+            require_uppercase: true,
+            require_lowercase: true,
+            require_digit: true,
+            require_symbol: false,
+        }
+    }
+}
+
+impl PasswordPolicy {
+    pub fn new(
+        min_length: usize,
+        require_uppercase: bool,
+        require_lowercase: bool,
+        require_digit: bool,
+        require_symbol: bool,
+    ) -> Self {
+        Self {
+            min_length,
+            require_uppercase,
+            require_lowercase,
+            require_digit,
+            require_symbol,
+        }
+    }
+
+    pub fn validate(&self, password: &str) -> Result<(), PasswordError> {
+        if password.chars().count() < self.min_length {
+            return Err(PasswordError::TooShort {
+                min_length: self.min_length,
+            });
+        }
+        if self.require_uppercase && !password.chars().any(char::is_uppercase) {
+            return Err(PasswordError::MissingUppercase);
+        }
+        if self.require_lowercase && !password.chars().any(char::is_lowercase) {
+            return Err(PasswordError::MissingLowercase);
+        }
+        if self.require_digit && !password.chars().any(|c| c.is_ascii_digit()) {
+            return Err(PasswordError::MissingDigit);
+        }
+        if self.require_symbol && password.chars().all(char::is_alphanumeric) {
+            return Err(PasswordError::MissingSymbol);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Error)]
+pub enum PasswordError {
+    #[error("Password must contain at least {min_length} characters")]
+    TooShort { min_length: usize },
+    #[error("Password must contain an uppercase letter")]
+    MissingUppercase,
+    #[error("Password must contain a lowercase letter")]
+    MissingLowercase,
+    #[error("Password must contain a digit")]
+    MissingDigit,
+    #[error("Password must contain a symbol")]
+    MissingSymbol,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PasswordError, PasswordPolicy};
+
+    #[test]
+    fn test_password_ok() {
+        assert!(PasswordPolicy::default()
+            .validate("Correct-Horse-42")
+            .is_ok());
+    }
+
+    #[test]
+    fn test_password_too_short() {
+        assert_eq!(
+            PasswordPolicy::default().validate("Short1"),
+            Err(PasswordError::TooShort { min_length: 12 })
+        );
+    }
+
+    #[test]
+    fn test_password_missing_classes() {
+        let policy = PasswordPolicy::default();
+        assert_eq!(
+            policy.validate("correct-horse-42"),
+            Err(PasswordError::MissingUppercase)
+        );
+        assert_eq!(
+            policy.validate("CORRECT-HORSE-42"),
+            Err(PasswordError::MissingLowercase)
+        );
+        assert_eq!(
+            policy.validate("Correct-Horse-xx"),
+            Err(PasswordError::MissingDigit)
+        );
+    }
+
+    #[test]
+    fn test_password_missing_symbol() {
+        let policy = PasswordPolicy::new(8, false, false, false, true);
+        assert_eq!(
+            policy.validate("abcdefgh"),
+            Err(PasswordError::MissingSymbol)
+        );
+        assert!(policy.validate("abcd-efgh").is_ok());
+    }
+}
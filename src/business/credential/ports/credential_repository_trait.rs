@@ -0,0 +1,27 @@
+use std::{future::Future, time::SystemTime};
+
+use uuid::Uuid;
+
+use crate::{
+    business::credential::{CredentialError, LockoutPolicy, PasswordCredential},
+    outbound::repository_trait::RepositoryTrait,
+};
+
+/// Login attempts are counted by the repository itself, so concurrent
+/// attempts cannot overwrite each other's count.
+pub trait CredentialRepositoryTrait: RepositoryTrait + Sync + Send + 'static {
+    /// Counts a failed attempt, locking the credential as `policy` says.
+    fn record_failure(
+        &self,
+        user_id: &Uuid,
+        policy: &LockoutPolicy,
+        now: &SystemTime,
+    ) -> impl Future<Output = Result<PasswordCredential, CredentialError>> + Send;
+
+    /// Resets the failed attempts, unless the credential got locked meanwhile.
+    fn record_success(
+        &self,
+        user_id: &Uuid,
+        now: &SystemTime,
+    ) -> impl Future<Output = Result<PasswordCredential, CredentialError>> + Send;
+}
@@ -0,0 +1,46 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::{RequestContext, TenantId},
+    credential::{
+        AuthTokens, ChangePasswordRequest, CredentialError, LoginRequest, RefreshRequest,
+        SetPasswordRequest, SetRolesRequest,
+    },
+};
+
+pub trait CredentialServiceTrait: Sync + Send + Clone + 'static {
+    fn set_password(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &SetPasswordRequest,
+    ) -> impl Future<Output = Result<(), CredentialError>> + Send;
+
+    /// Replaces the roles carried by the tokens issued at login.
+    fn set_roles(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &SetRolesRequest,
+    ) -> impl Future<Output = Result<(), CredentialError>> + Send;
+
+    fn change_password(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &ChangePasswordRequest,
+    ) -> impl Future<Output = Result<(), CredentialError>> + Send;
+
+    fn login(
+        &self,
+        tenant: &TenantId,
+        req: &LoginRequest,
+    ) -> impl Future<Output = Result<AuthTokens, CredentialError>> + Send;
+
+    fn refresh(
+        &self,
+        req: &RefreshRequest,
+    ) -> impl Future<Output = Result<AuthTokens, CredentialError>> + Send;
+}
@@ -0,0 +1,9 @@
+pub mod credential_repository_trait;
+pub mod credential_service_trait;
+pub mod password_hasher_trait;
+pub mod token_issuer_trait;
+
+pub use credential_repository_trait::CredentialRepositoryTrait;
+pub use credential_service_trait::CredentialServiceTrait;
+pub use password_hasher_trait::PasswordHasherTrait;
+pub use token_issuer_trait::TokenIssuerTrait;
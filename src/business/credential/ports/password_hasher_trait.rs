@@ -0,0 +1,7 @@
+use crate::business::credential::CredentialError;
+
+pub trait PasswordHasherTrait: Sync + Send + Clone + 'static {
+    fn hash(&self, password: &str) -> Result<String, CredentialError>;
+
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, CredentialError>;
+}
@@ -0,0 +1,23 @@
+use uuid::Uuid;
+
+use crate::business::{
+    auth::{Role, TenantId},
+    credential::{AuthTokens, CredentialError},
+};
+
+pub trait TokenIssuerTrait: Sync + Send + Clone + 'static {
+    fn issue(
+        &self,
+        user_id: &Uuid,
+        tenant: &TenantId,
+        roles: &[Role],
+        token_version: &u32,
+    ) -> Result<AuthTokens, CredentialError>;
+
+    /// Returns the user, tenant and credential token version the refresh
+    /// token was issued for.
+    fn verify_refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<(Uuid, TenantId, u32), CredentialError>;
+}
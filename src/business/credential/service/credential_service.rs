@@ -0,0 +1,708 @@
+use std::{future::Future, sync::Arc, time::SystemTime};
+
+use tokio::sync::OnceCell;
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::{RequestContext, TenantId},
+        credential::{
+            AuthTokens, ChangePasswordRequest, CredentialError, CredentialFindRequest,
+            CredentialFindResponse, CredentialRepositoryTrait, CredentialServiceTrait,
+            LockoutPolicy, LoginRequest, PasswordCredential, PasswordHasherTrait, PasswordPolicy,
+            RefreshRequest, SetPasswordRequest, SetRolesRequest, TokenIssuerTrait,
+        },
+        user::{
+            dtos::{user_find_request::UserFindRequestFilter, UserFindRequest, UserFindResponse},
+            model::user::UserError,
+            User, UserOperation, UserPolicy, UserRepositoryTrait,
+        },
+    },
+    outbound::repository_trait::FindResultTrait,
+};
+
+#[derive(Debug, Clone)]
+pub struct CredentialService<U, C, H, T>
+where
+    U: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+    >,
+    C: CredentialRepositoryTrait<
+        Id = Uuid,
+        Entity = PasswordCredential,
+        Error = CredentialError,
+        FindOptions = CredentialFindRequest,
+        FindResult = CredentialFindResponse,
+    >,
+    H: PasswordHasherTrait,
+    T: TokenIssuerTrait,
+{
+    user_repository: U,
+    credential_repository: C,
+    password_hasher: H,
+    token_issuer: T,
+    policy: UserPolicy,
+    password_policy: PasswordPolicy,
+    lockout_policy: LockoutPolicy,
+    /// Verified against when there is no credential, so a login takes as
+    /// long for an unknown email as for a wrong password.
+    dummy_hash: Arc<OnceCell<String>>,
+}
+
+impl<U, C, H, T> CredentialService<U, C, H, T>
+where
+    U: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+    >,
+    C: CredentialRepositoryTrait<
+        Id = Uuid,
+        Entity = PasswordCredential,
+        Error = CredentialError,
+        FindOptions = CredentialFindRequest,
+        FindResult = CredentialFindResponse,
+    >,
+    H: PasswordHasherTrait,
+    T: TokenIssuerTrait,
+{
+    pub fn new(
+        user_repository: U,
+        credential_repository: C,
+        password_hasher: H,
+        token_issuer: T,
+    ) -> Self {
+        Self {
+            user_repository,
+            credential_repository,
+            password_hasher,
+            token_issuer,
+            policy: UserPolicy::default_rules(),
+            password_policy: PasswordPolicy::default(),
+            lockout_policy: LockoutPolicy::default(),
+            dummy_hash: Arc::new(OnceCell::new()),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UserPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_password_policy(mut self, password_policy: PasswordPolicy) -> Self {
+        self.password_policy = password_policy;
+        self
+    }
+
+    pub fn with_lockout_policy(mut self, lockout_policy: LockoutPolicy) -> Self {
+        self.lockout_policy = lockout_policy;
+        self
+    }
+
+    async fn hash_password(&self, password: &str) -> Result<String, CredentialError> {
+        let hasher = self.password_hasher.clone();
+        let password = password.to_string();
+        tokio::task::spawn_blocking(move || hasher.hash(&password))
+            .await
+            .map_err(|e| CredentialError::Unknown(e.into()))?
+    }
+
+    async fn verify_password(
+        &self,
+        password: &str,
+        credential: &PasswordCredential,
+    ) -> Result<bool, CredentialError> {
+        self.verify_hash(password, credential.get_password_hash())
+            .await
+    }
+
+    async fn verify_hash(
+        &self,
+        password: &str,
+        password_hash: &str,
+    ) -> Result<bool, CredentialError> {
+        let hasher = self.password_hasher.clone();
+        let password = password.to_string();
+        let password_hash = password_hash.to_string();
+        tokio::task::spawn_blocking(move || hasher.verify(&password, &password_hash))
+            .await
+            .map_err(|e| CredentialError::Unknown(e.into()))?
+    }
+
+    /// Spends the time of a password check, then fails the login.
+    async fn reject_login(&self, password: &str) -> Result<AuthTokens, CredentialError> {
+        let dummy_hash = self
+            .dummy_hash
+            .get_or_try_init(|| self.hash_password("dummy-password"))
+            .await?;
+        self.verify_hash(password, dummy_hash).await?;
+        Err(CredentialError::InvalidCredentials)
+    }
+
+    async fn store_password(&self, user_id: &Uuid, password: &str) -> Result<(), CredentialError> {
+        self.password_policy.validate(password)?;
+        let password_hash = self.hash_password(password).await?;
+        match self.credential_repository.find_by_id(user_id).await {
+            Ok(mut credential) => {
+                credential.set_password_hash(&password_hash);
+                self.credential_repository
+                    .update(user_id, &credential)
+                    .await?;
+            }
+            Err(CredentialError::CredentialNotExists { id: _ }) => {
+                self.credential_repository
+                    .save(&PasswordCredential::new(user_id, &password_hash, &[]))
+                    .await?;
+            }
+            Err(e) => return Err(e),
+        }
+        Ok(())
+    }
+
+    async fn find_user_by_email(
+        &self,
+        tenant: &TenantId,
+        req: &LoginRequest,
+    ) -> Result<Option<User>, CredentialError> {
+        let filters = UserFindRequestFilter {
+            email: Some(req.get_email().to_string()),
+            ..Default::default()
+        };
+        let find_request = UserFindRequest::new(&filters, "", &1, &1).map_err(UserError::from)?;
+        Ok(self
+            .user_repository
+            .for_tenant(tenant)
+            .find_all(&find_request)
+            .await?
+            .get_result()
+            .next())
+    }
+
+    /// Only checked once the password is verified, so the status of an
+    /// account is not disclosed to whoever only knows its email.
+    fn ensure_enabled(user: &User) -> Result<(), CredentialError> {
+        match user.get_status().can_sign_in() {
+            true => Ok(()),
+            false => Err(CredentialError::AccountDisabled {
+                status: *user.get_status(),
+            }),
+        }
+    }
+
+    fn ensure_unlocked(
+        credential: &PasswordCredential,
+        now: &SystemTime,
+    ) -> Result<(), CredentialError> {
+        match credential.remaining_lock(now) {
+            Some(remaining) if credential.is_locked(now) => Err(CredentialError::AccountLocked {
+                retry_after: remaining.as_secs().max(1),
+            }),
+            _ => Ok(()),
+        }
+    }
+}
+
+impl<U, C, H, T> CredentialServiceTrait for CredentialService<U, C, H, T>
+where
+    U: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+    >,
+    C: CredentialRepositoryTrait<
+        Id = Uuid,
+        Entity = PasswordCredential,
+        Error = CredentialError,
+        FindOptions = CredentialFindRequest,
+        FindResult = CredentialFindResponse,
+    >,
+    H: PasswordHasherTrait,
+    T: TokenIssuerTrait,
+{
+    fn set_password(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &SetPasswordRequest,
+    ) -> impl Future<Output = Result<(), CredentialError>> + Send {
+        Box::pin(async move {
+            self.policy
+                .authorize(context.get_principal(), UserOperation::Update, None)?;
+            self.user_repository
+                .for_tenant(context.get_tenant())
+                .find_by_id(user_id)
+                .await?;
+            self.store_password(user_id, req.get_password()).await
+        })
+    }
+
+    fn set_roles(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &SetRolesRequest,
+    ) -> impl Future<Output = Result<(), CredentialError>> + Send {
+        Box::pin(async move {
+            self.policy.authorize(
+                context.get_principal(),
+                UserOperation::AssignRoles,
+                Some(user_id),
+            )?;
+            self.user_repository
+                .for_tenant(context.get_tenant())
+                .find_by_id(user_id)
+                .await?;
+            let mut credential = self.credential_repository.find_by_id(user_id).await?;
+            credential.set_roles(req.get_roles());
+            self.credential_repository
+                .update(user_id, &credential)
+                .await?;
+            Ok(())
+        })
+    }
+
+    fn change_password(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &ChangePasswordRequest,
+    ) -> impl Future<Output = Result<(), CredentialError>> + Send {
+        Box::pin(async move {
+            self.policy.authorize(
+                context.get_principal(),
+                UserOperation::Update,
+                Some(user_id),
+            )?;
+            self.user_repository
+                .for_tenant(context.get_tenant())
+                .find_by_id(user_id)
+                .await?;
+            let credential = match self.credential_repository.find_by_id(user_id).await {
+                Err(CredentialError::CredentialNotExists { id: _ }) => {
+                    return Err(CredentialError::CurrentPasswordMismatch)
+                }
+                result => result?,
+            };
+            if !self
+                .verify_password(req.get_current_password(), &credential)
+                .await?
+            {
+                return Err(CredentialError::CurrentPasswordMismatch);
+            }
+            self.store_password(user_id, req.get_new_password()).await
+        })
+    }
+
+    fn login(
+        &self,
+        tenant: &TenantId,
+        req: &LoginRequest,
+    ) -> impl Future<Output = Result<AuthTokens, CredentialError>> + Send {
+        Box::pin(async move {
+            let Some(user) = self.find_user_by_email(tenant, req).await? else {
+                return self.reject_login(req.get_password()).await;
+            };
+            let credential = match self.credential_repository.find_by_id(user.get_id()).await {
+                Err(CredentialError::CredentialNotExists { id: _ }) => {
+                    return self.reject_login(req.get_password()).await
+                }
+                result => result?,
+            };
+            // The password is checked even while locked, and a locked account
+            // fails like a wrong password, so the lock is not disclosed.
+            let verified = self
+                .verify_password(req.get_password(), &credential)
+                .await?;
+            let now = SystemTime::now();
+            if credential.is_locked(&now) {
+                return Err(CredentialError::InvalidCredentials);
+            }
+            if !verified {
+                self.credential_repository
+                    .record_failure(user.get_id(), &self.lockout_policy, &now)
+                    .await?;
+                return Err(CredentialError::InvalidCredentials);
+            }
+            // Attempts running alongside this one may have locked it meanwhile.
+            let credential = self
+                .credential_repository
+                .record_success(user.get_id(), &now)
+                .await?;
+            if credential.is_locked(&now) {
+                return Err(CredentialError::InvalidCredentials);
+            }
+            Self::ensure_enabled(&user)?;
+            self.token_issuer.issue(
+                user.get_id(),
+                tenant,
+                credential.get_roles(),
+                &credential.get_token_version(),
+            )
+        })
+    }
+
+    fn refresh(
+        &self,
+        req: &RefreshRequest,
+    ) -> impl Future<Output = Result<AuthTokens, CredentialError>> + Send {
+        Box::pin(async move {
+            let (user_id, tenant, token_version) = self
+                .token_issuer
+                .verify_refresh_token(req.get_refresh_token())?;
+            let credential = match self.credential_repository.find_by_id(&user_id).await {
+                Err(CredentialError::CredentialNotExists { id: _ }) => {
+                    return Err(CredentialError::InvalidRefreshToken)
+                }
+                result => result?,
+            };
+            // Issued before the password last changed.
+            if credential.get_token_version() != token_version {
+                return Err(CredentialError::InvalidRefreshToken);
+            }
+            Self::ensure_unlocked(&credential, &SystemTime::now())?;
+            let user = match self
+                .user_repository
+                .for_tenant(&tenant)
+                .find_by_id(&user_id)
+                .await
+            {
+                Err(UserError::UserNotExists { id: _ }) => {
+                    return Err(CredentialError::InvalidRefreshToken)
+                }
+                result => result?,
+            };
+            Self::ensure_enabled(&user)?;
+            self.token_issuer.issue(
+                &user_id,
+                &tenant,
+                credential.get_roles(),
+                &credential.get_token_version(),
+            )
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use crate::{
+        business::{
+            auth::{
+                AuthToken, AuthenticationMethod, AuthenticatorTrait, Principal, RequestContext,
+                Role, TenantId,
+            },
+            credential::{
+                ChangePasswordRequest, CredentialError, CredentialServiceTrait, LockoutPolicy,
+                LoginRequest, RefreshRequest, SetPasswordRequest, SetRolesRequest,
+            },
+            user::{EmailAddress, Name, User, UserOperation, UserPolicy, UserStatus},
+        },
+        outbound::{
+            argon2_password_hasher_adapter::argon2_password_hasher::Argon2PasswordHasher,
+            in_memory_repository_adapter::{
+                in_memory_credential_repository::InMemoryCredentialRepository,
+                in_memory_user_repository::InMemoryUserRepository,
+            },
+            jwt_authenticator_adapter::{
+                jwt_authenticator::JwtAuthenticator, jwt_token_issuer::JwtTokenIssuer,
+            },
+            repository_trait::RepositoryTrait,
+        },
+    };
+
+    use super::CredentialService;
+
+    type TestCredentialService = CredentialService<
+        InMemoryUserRepository,
+        InMemoryCredentialRepository,
+        Argon2PasswordHasher,
+        JwtTokenIssuer,
+    >;
+
+    const PASSWORD: &str = "Correct-Horse-42";
+
+    fn admin() -> RequestContext {
+        RequestContext::new(
+            &Principal::new(
+                "admin",
+                None,
+                &[Role::new("admin")],
+                AuthenticationMethod::ApiKey,
+            ),
+            "test-request",
+        )
+    }
+
+    async fn setup() -> (TestCredentialService, User) {
+        let user_repository = InMemoryUserRepository::new();
+        let user = user_repository
+            .save(&User::new(
+                &uuid::Uuid::new_v4(),
+                &Name::new("John").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new("john@example.com").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let service = CredentialService::new(
+            user_repository,
+            InMemoryCredentialRepository::new(),
+            Argon2PasswordHasher::with_params(1024, 1, 1).unwrap(),
+            JwtTokenIssuer::hs256(b"secret"),
+        )
+        .with_lockout_policy(LockoutPolicy::new(2, Duration::from_secs(60)));
+        service
+            .set_password(&admin(), user.get_id(), &SetPasswordRequest::new(PASSWORD))
+            .await
+            .unwrap();
+        (service, user)
+    }
+
+    fn login_request(password: &str) -> LoginRequest {
+        LoginRequest::new(&EmailAddress::new("john@example.com").unwrap(), password)
+    }
+
+    #[tokio::test]
+    async fn test_login_and_refresh_ok() {
+        let (service, _user) = setup().await;
+        let tokens = service
+            .login(&TenantId::default(), &login_request(PASSWORD))
+            .await
+            .unwrap();
+        let refreshed = service
+            .refresh(&RefreshRequest::new(tokens.get_refresh_token()))
+            .await;
+        assert!(refreshed.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_login_unknown_email_ko() {
+        let (service, _user) = setup().await;
+        let request = LoginRequest::new(&EmailAddress::new("jane@example.com").unwrap(), PASSWORD);
+        let result = service.login(&TenantId::default(), &request).await;
+        assert!(matches!(result, Err(CredentialError::InvalidCredentials)));
+        assert!(service.dummy_hash.get().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_login_other_tenant_ko() {
+        let (service, _user) = setup().await;
+        let result = service
+            .login(&TenantId::new("acme").unwrap(), &login_request(PASSWORD))
+            .await;
+        assert!(matches!(result, Err(CredentialError::InvalidCredentials)));
+    }
+
+    async fn is_locked(service: &TestCredentialService, user: &User) -> bool {
+        service
+            .credential_repository
+            .find_by_id(user.get_id())
+            .await
+            .unwrap()
+            .is_locked(&SystemTime::now())
+    }
+
+    #[tokio::test]
+    async fn test_login_lockout() {
+        let (service, user) = setup().await;
+        for _ in 0..2 {
+            let result = service
+                .login(&TenantId::default(), &login_request("Wrong-Horse-42"))
+                .await;
+            assert!(matches!(result, Err(CredentialError::InvalidCredentials)));
+        }
+        assert!(is_locked(&service, &user).await);
+        let result = service
+            .login(&TenantId::default(), &login_request(PASSWORD))
+            .await;
+        assert!(matches!(result, Err(CredentialError::InvalidCredentials)));
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_failures_all_counted() {
+        let (service, user) = setup().await;
+        let tenant = TenantId::default();
+        let wrong = login_request("Wrong-Horse-42");
+        let (first, second) = tokio::join!(
+            service.login(&tenant, &wrong),
+            service.login(&tenant, &wrong)
+        );
+        assert!(first.is_err() && second.is_err());
+        assert!(is_locked(&service, &user).await);
+    }
+
+    #[tokio::test]
+    async fn test_password_change_revokes_refresh_tokens() {
+        let (service, user) = setup().await;
+        let tokens = service
+            .login(&TenantId::default(), &login_request(PASSWORD))
+            .await
+            .unwrap();
+        service
+            .set_password(
+                &admin(),
+                user.get_id(),
+                &SetPasswordRequest::new("Battery-Staple-7"),
+            )
+            .await
+            .unwrap();
+        let result = service
+            .refresh(&RefreshRequest::new(tokens.get_refresh_token()))
+            .await;
+        assert!(matches!(result, Err(CredentialError::InvalidRefreshToken)));
+        let tokens = service
+            .login(&TenantId::default(), &login_request("Battery-Staple-7"))
+            .await
+            .unwrap();
+        assert!(service
+            .refresh(&RefreshRequest::new(tokens.get_refresh_token()))
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_login_suspended_ko() {
+        let (service, mut user) = setup().await;
+        let tokens = service
+            .login(&TenantId::default(), &login_request(PASSWORD))
+            .await
+            .unwrap();
+        user.suspend("Fraud investigation").unwrap();
+        service
+            .user_repository
+            .update(user.get_id(), &user)
+            .await
+            .unwrap();
+        let result = service
+            .login(&TenantId::default(), &login_request(PASSWORD))
+            .await;
+        assert!(matches!(
+            result,
+            Err(CredentialError::AccountDisabled {
+                status: UserStatus::Suspended
+            })
+        ));
+        let result = service
+            .refresh(&RefreshRequest::new(tokens.get_refresh_token()))
+            .await;
+        assert!(matches!(
+            result,
+            Err(CredentialError::AccountDisabled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_login_carries_assigned_roles() {
+        let (service, user) = setup().await;
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let policy = UserPolicy::default_rules();
+        let login = || async {
+            let tokens = service
+                .login(&TenantId::default(), &login_request(PASSWORD))
+                .await
+                .unwrap();
+            authenticator
+                .authenticate(&AuthToken::Bearer(tokens.get_access_token().to_string()))
+                .await
+                .unwrap()
+        };
+        let principal = login().await;
+        assert!(policy
+            .authorize(&principal, UserOperation::List, None)
+            .is_err());
+
+        service
+            .set_roles(
+                &admin(),
+                user.get_id(),
+                &SetRolesRequest::new(&[Role::new("admin")]),
+            )
+            .await
+            .unwrap();
+        let principal = login().await;
+        assert!(policy
+            .authorize(&principal, UserOperation::List, None)
+            .is_ok());
+        assert!(policy
+            .authorize(
+                &principal,
+                UserOperation::Delete,
+                Some(&uuid::Uuid::new_v4())
+            )
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_set_roles_forbidden_for_self() {
+        let (service, user) = setup().await;
+        let principal = RequestContext::new(
+            &Principal::new("john", Some(user.get_id()), &[], AuthenticationMethod::Jwt),
+            "test-request",
+        );
+        let result = service
+            .set_roles(
+                &principal,
+                user.get_id(),
+                &SetRolesRequest::new(&[Role::new("admin")]),
+            )
+            .await;
+        assert!(matches!(result, Err(CredentialError::Forbidden { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_set_password_forbidden_for_self() {
+        let (service, user) = setup().await;
+        let principal = RequestContext::new(
+            &Principal::new("john", Some(user.get_id()), &[], AuthenticationMethod::Jwt),
+            "test-request",
+        );
+        let result = service
+            .set_password(
+                &principal,
+                user.get_id(),
+                &SetPasswordRequest::new(PASSWORD),
+            )
+            .await;
+        assert!(matches!(result, Err(CredentialError::Forbidden { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_change_password() {
+        let (service, user) = setup().await;
+        let principal = RequestContext::new(
+            &Principal::new("john", Some(user.get_id()), &[], AuthenticationMethod::Jwt),
+            "test-request",
+        );
+        let wrong = ChangePasswordRequest::new("Wrong-Horse-42", "Battery-Staple-7");
+        let result = service
+            .change_password(&principal, user.get_id(), &wrong)
+            .await;
+        assert!(matches!(
+            result,
+            Err(CredentialError::CurrentPasswordMismatch)
+        ));
+        let weak = ChangePasswordRequest::new(PASSWORD, "weak");
+        let result = service
+            .change_password(&principal, user.get_id(), &weak)
+            .await;
+        assert!(matches!(result, Err(CredentialError::WeakPassword(_))));
+        let ok = ChangePasswordRequest::new(PASSWORD, "Battery-Staple-7");
+        service
+            .change_password(&principal, user.get_id(), &ok)
+            .await
+            .unwrap();
+        assert!(service
+            .login(&TenantId::default(), &login_request("Battery-Staple-7"))
+            .await
+            .is_ok());
+    }
+}
@@ -0,0 +1 @@
+pub mod credential_service;
@@ -0,0 +1,29 @@
+use serde::Serialize;
+
+use crate::outbound::repository_trait::FindResultTrait;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CrudPage<T> {
+    items: Vec<T>,
+    num_pages: u64,
+}
+
+impl<T> CrudPage<T> {
+    pub fn new(items: Vec<T>, num_pages: u64) -> Self {
+        Self { items, num_pages }
+    }
+
+    pub fn get_items(&self) -> &[T] {
+        &self.items
+    }
+}
+
+impl<T: Clone + Sync + Send + 'static> FindResultTrait for CrudPage<T> {
+    type Entity = T;
+    fn get_result(&self) -> impl Iterator<Item = Self::Entity> {
+        self.items.clone().into_iter()
+    }
+    fn get_page_count(&self) -> u64 {
+        self.num_pages
+    }
+}
@@ -0,0 +1,3 @@
+pub mod crud_page;
+
+pub use crud_page::CrudPage;
@@ -0,0 +1,9 @@
+pub mod dtos;
+pub mod model;
+pub mod ports;
+pub mod service;
+
+pub use dtos::CrudPage;
+pub use model::{CrudEntityTrait, CrudErrorKind, CrudErrorTrait, CrudFindRequestTrait};
+
+pub use ports::{CrudRepositoryTrait, CrudServiceTrait};
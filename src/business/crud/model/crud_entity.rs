@@ -0,0 +1,51 @@
+use std::{cmp::Ordering, error::Error};
+
+use serde::{de::DeserializeOwned, Serialize};
+use utoipa::{IntoParams, ToSchema};
+use uuid::Uuid;
+
+use crate::{business::user::UserOperation, outbound::repository_trait::FindOptionTrait};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrudErrorKind {
+    NotFound,
+    Conflict,
+    Invalid,
+    Forbidden,
+    Internal,
+}
+
+pub trait CrudErrorTrait: Error + Sync + Send + 'static {
+    fn kind(&self) -> CrudErrorKind;
+    fn not_exists(id: &Uuid) -> Self;
+    fn mismatch_id(id1: &Uuid, id2: &Uuid) -> Self;
+    fn forbidden(operation: UserOperation) -> Self;
+}
+
+pub trait CrudFindRequestTrait<T>: FindOptionTrait + DeserializeOwned {
+    fn matches(&self, entity: &T) -> bool;
+    /// Orders two entities by `get_order_by`, ties are broken by the caller.
+    fn compare(&self, a: &T, b: &T) -> Ordering;
+}
+
+/// An entity served by the generic CRUD service, repository and routes: only
+/// the model, its DTOs and their validation are entity specific.
+pub trait CrudEntityTrait: Clone + Serialize + ToSchema + Sync + Send + 'static {
+    type AddRequest: DeserializeOwned + ToSchema + Sync + Send + 'static;
+    type UpdateRequest: DeserializeOwned + ToSchema + Sync + Send + 'static;
+    type FindRequest: CrudFindRequestTrait<Self>;
+    /// Query parameters documenting `FindRequest`.
+    type FindParams: IntoParams;
+    type Error: CrudErrorTrait;
+
+    fn get_id(&self) -> &Uuid;
+    fn with_id(&self, id: &Uuid) -> Self;
+    fn from_add_request(req: &Self::AddRequest) -> Result<Self, Self::Error>;
+    fn from_update_request(id: &Uuid, req: &Self::UpdateRequest) -> Result<Self, Self::Error>;
+
+    /// Error to raise when `self` cannot be stored next to `other`, such as a
+    /// duplicated unique field.
+    fn conflict_with(&self, _other: &Self) -> Option<Self::Error> {
+        None
+    }
+}
@@ -0,0 +1,3 @@
+pub mod crud_entity;
+
+pub use crud_entity::{CrudEntityTrait, CrudErrorKind, CrudErrorTrait, CrudFindRequestTrait};
@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use crate::{
+    business::crud::{CrudEntityTrait, CrudPage},
+    outbound::repository_trait::RepositoryTrait,
+};
+
+/// Shorthand for a `RepositoryTrait` storing a CRUD entity.
+pub trait CrudRepositoryTrait<T: CrudEntityTrait>:
+    RepositoryTrait<
+    Id = Uuid,
+    Entity = T,
+    Error = T::Error,
+    FindOptions = T::FindRequest,
+    FindResult = CrudPage<T>,
+>
+{
+}
+
+impl<T, R> CrudRepositoryTrait<T> for R
+where
+    T: CrudEntityTrait,
+    R: RepositoryTrait<
+        Id = Uuid,
+        Entity = T,
+        Error = T::Error,
+        FindOptions = T::FindRequest,
+        FindResult = CrudPage<T>,
+    >,
+{
+}
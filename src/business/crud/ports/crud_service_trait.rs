@@ -0,0 +1,44 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::RequestContext,
+    crud::{CrudEntityTrait, CrudPage},
+};
+
+pub trait CrudServiceTrait: Sync + Send + Clone + 'static {
+    type Entity: CrudEntityTrait;
+
+    fn create(
+        &self,
+        context: &RequestContext,
+        req: &<Self::Entity as CrudEntityTrait>::AddRequest,
+    ) -> impl Future<Output = Result<Self::Entity, <Self::Entity as CrudEntityTrait>::Error>> + Send;
+
+    fn update(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+        req: &<Self::Entity as CrudEntityTrait>::UpdateRequest,
+    ) -> impl Future<Output = Result<Self::Entity, <Self::Entity as CrudEntityTrait>::Error>> + Send;
+
+    fn find_one(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<Self::Entity, <Self::Entity as CrudEntityTrait>::Error>> + Send;
+
+    fn find(
+        &self,
+        context: &RequestContext,
+        req: &<Self::Entity as CrudEntityTrait>::FindRequest,
+    ) -> impl Future<Output = Result<CrudPage<Self::Entity>, <Self::Entity as CrudEntityTrait>::Error>>
+           + Send;
+
+    fn delete(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<(), <Self::Entity as CrudEntityTrait>::Error>> + Send;
+}
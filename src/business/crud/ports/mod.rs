@@ -0,0 +1,5 @@
+pub mod crud_repository_trait;
+pub mod crud_service_trait;
+
+pub use crud_repository_trait::CrudRepositoryTrait;
+pub use crud_service_trait::CrudServiceTrait;
@@ -0,0 +1,108 @@
+use std::{future::Future, marker::PhantomData};
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::RequestContext,
+    crud::{CrudEntityTrait, CrudErrorTrait, CrudPage, CrudRepositoryTrait, CrudServiceTrait},
+    user::{UserOperation, UserPolicy},
+};
+
+#[derive(Debug, Clone)]
+pub struct CrudService<T: CrudEntityTrait, R: CrudRepositoryTrait<T>> {
+    repository: R,
+    policy: UserPolicy,
+    entity: PhantomData<T>,
+}
+
+impl<T: CrudEntityTrait, R: CrudRepositoryTrait<T>> CrudService<T, R> {
+    pub fn new(repository: R) -> Self {
+        Self {
+            repository,
+            policy: UserPolicy::default_rules(),
+            entity: PhantomData,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UserPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn get_repository(&self) -> &R {
+        &self.repository
+    }
+
+    /// Entities have no owner, so only role rules apply.
+    pub fn authorize(
+        &self,
+        context: &RequestContext,
+        operation: UserOperation,
+    ) -> Result<(), T::Error> {
+        self.policy
+            .authorize(context.get_principal(), operation, None)
+            .map_err(|_| T::Error::forbidden(operation))
+    }
+}
+
+impl<T: CrudEntityTrait, R: CrudRepositoryTrait<T>> CrudServiceTrait for CrudService<T, R> {
+    type Entity = T;
+
+    fn create(
+        &self,
+        context: &RequestContext,
+        req: &T::AddRequest,
+    ) -> impl Future<Output = Result<T, T::Error>> + Send {
+        Box::pin(async {
+            self.authorize(context, UserOperation::Create)?;
+            self.repository.save(&T::from_add_request(req)?).await
+        })
+    }
+
+    fn update(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+        req: &T::UpdateRequest,
+    ) -> impl Future<Output = Result<T, T::Error>> + Send {
+        Box::pin(async {
+            self.authorize(context, UserOperation::Update)?;
+            self.repository
+                .update(id, &T::from_update_request(id, req)?)
+                .await
+        })
+    }
+
+    fn find_one(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<T, T::Error>> + Send {
+        Box::pin(async {
+            self.authorize(context, UserOperation::Read)?;
+            self.repository.find_by_id(id).await
+        })
+    }
+
+    fn find(
+        &self,
+        context: &RequestContext,
+        req: &T::FindRequest,
+    ) -> impl Future<Output = Result<CrudPage<T>, T::Error>> + Send {
+        Box::pin(async {
+            self.authorize(context, UserOperation::List)?;
+            self.repository.find_all(req).await
+        })
+    }
+
+    fn delete(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<(), T::Error>> + Send {
+        Box::pin(async {
+            self.authorize(context, UserOperation::Delete)?;
+            self.repository.delete(id).await
+        })
+    }
+}
@@ -0,0 +1 @@
+pub mod crud_service;
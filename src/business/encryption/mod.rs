@@ -0,0 +1,6 @@
+pub mod model;
+pub mod ports;
+
+pub use model::EncryptionError;
+
+pub use ports::FieldEncryptorTrait;
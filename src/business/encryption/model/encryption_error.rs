@@ -0,0 +1,15 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum EncryptionError {
+    #[error("Invalid key file: {reason}")]
+    InvalidKeyFile { reason: String },
+    #[error("Key {key_id} is unknown")]
+    UnknownKey { key_id: String },
+    #[error("Ciphertext is malformed")]
+    MalformedCiphertext,
+    #[error("Ciphertext could not be decrypted")]
+    DecryptionFailed,
+    #[error("Value could not be encrypted")]
+    EncryptionFailed,
+}
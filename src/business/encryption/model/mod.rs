@@ -0,0 +1,3 @@
+pub mod encryption_error;
+
+pub use encryption_error::EncryptionError;
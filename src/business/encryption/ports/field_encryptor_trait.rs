@@ -0,0 +1,17 @@
+use crate::business::encryption::EncryptionError;
+
+/// Encrypts personal data before a repository stores it.
+pub trait FieldEncryptorTrait: Sync + Send + Clone + 'static {
+    /// Ciphertext under the active key, naming the key it was produced with.
+    fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError>;
+
+    /// Decrypts with the key named in the ciphertext, which need not be the active one.
+    fn decrypt(&self, ciphertext: &str) -> Result<String, EncryptionError>;
+
+    /// Keyed hash of the value, always the same for the same value, used to
+    /// look values up without decrypting them.
+    fn blind_index(&self, value: &str) -> String;
+
+    /// Whether the stored value is not encrypted under the active key yet.
+    fn needs_rotation(&self, stored: &str) -> bool;
+}
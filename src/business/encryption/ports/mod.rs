@@ -0,0 +1,3 @@
+pub mod field_encryptor_trait;
+
+pub use field_encryptor_trait::FieldEncryptorTrait;
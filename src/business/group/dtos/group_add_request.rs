@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::business::user::Name;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct GroupAddRequest {
+    name: Name,
+}
+
+impl GroupAddRequest {
+    pub fn new(name: &Name) -> Self {
+        Self { name: name.clone() }
+    }
+
+    pub fn get_name(&self) -> &Name {
+        &self.name
+    }
+}
@@ -0,0 +1,73 @@
+use std::cmp::Ordering;
+
+use serde::Deserialize;
+use utoipa::IntoParams;
+
+use crate::{
+    business::{crud::CrudFindRequestTrait, group::Group, user::dtos::UserFindRequestFilter},
+    outbound::repository_trait::FindOptionTrait,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, IntoParams)]
+pub struct GroupFindRequest {
+    /// Exact name, or a prefix when ending with `*`
+    pub name: Option<String>,
+    #[serde(default)]
+    pub order_by: String,
+    #[serde(default = "GroupFindRequest::default_per_page")]
+    pub per_page: u16,
+    #[serde(default = "GroupFindRequest::default_offset")]
+    pub offset: u64,
+}
+
+impl GroupFindRequest {
+    fn default_per_page() -> u16 {
+        25
+    }
+
+    fn default_offset() -> u64 {
+        1
+    }
+}
+
+impl Default for GroupFindRequest {
+    fn default() -> Self {
+        Self {
+            name: None,
+            order_by: String::new(),
+            per_page: Self::default_per_page(),
+            offset: Self::default_offset(),
+        }
+    }
+}
+
+impl FindOptionTrait for GroupFindRequest {
+    type QueryFilter = Option<String>;
+    fn get_query(&self) -> Self::QueryFilter {
+        self.name.clone()
+    }
+    fn get_order_by(&self) -> String {
+        self.order_by.clone()
+    }
+    fn get_limit(&self) -> u16 {
+        self.per_page.clamp(1, 1000)
+    }
+    fn get_offset(&self) -> u64 {
+        self.offset.max(1)
+    }
+}
+
+impl CrudFindRequestTrait<Group> for GroupFindRequest {
+    fn matches(&self, group: &Group) -> bool {
+        self.name.as_ref().map_or(true, |name| {
+            UserFindRequestFilter::matches_text(name, group.get_name())
+        })
+    }
+
+    fn compare(&self, a: &Group, b: &Group) -> Ordering {
+        match self.order_by.as_str() {
+            "name" => a.get_name().cmp(b.get_name()),
+            _ => a.get_id().cmp(b.get_id()),
+        }
+    }
+}
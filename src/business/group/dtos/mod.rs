@@ -0,0 +1,7 @@
+pub mod group_add_request;
+pub mod group_find_request;
+pub mod group_update_request;
+
+pub use group_add_request::GroupAddRequest;
+pub use group_find_request::GroupFindRequest;
+pub use group_update_request::GroupUpdateRequest;
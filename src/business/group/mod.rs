@@ -0,0 +1,9 @@
+pub mod dtos;
+pub mod model;
+pub mod ports;
+pub mod service;
+
+pub use dtos::{GroupAddRequest, GroupFindRequest, GroupUpdateRequest};
+pub use model::{Group, GroupError};
+
+pub use ports::{GroupRepositoryTrait, GroupServiceTrait};
@@ -0,0 +1,124 @@
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    crud::{CrudEntityTrait, CrudErrorKind, CrudErrorTrait},
+    group::{GroupAddRequest, GroupFindRequest, GroupUpdateRequest},
+    user::{Name, UserOperation},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct Group {
+    id: Uuid,
+    name: Name,
+    #[serde(default)]
+    tenant: TenantId,
+}
+
+#[derive(Debug, Error)]
+pub enum GroupError {
+    #[error("The id {id1} in the request differ the id {id2}")]
+    MismatchGroupId { id1: Uuid, id2: Uuid },
+    #[error("Group with id {id} does not exists")]
+    GroupNotExists { id: Uuid },
+    #[error("User with id {id} does not exists")]
+    UserNotExists { id: Uuid },
+    #[error("Group name {name} already used")]
+    NameAlreadyUsed { name: Name },
+    #[error("Operation {operation} forbidden")]
+    Forbidden { operation: UserOperation },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+impl Group {
+    pub fn new(id: &Uuid, name: &Name) -> Self {
+        Self {
+            id: *id,
+            name: name.clone(),
+            tenant: TenantId::default(),
+        }
+    }
+
+    pub fn with_tenant(mut self, tenant: &TenantId) -> Self {
+        self.tenant = tenant.clone();
+        self
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn get_name(&self) -> &Name {
+        &self.name
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+}
+
+impl CrudErrorTrait for GroupError {
+    fn kind(&self) -> CrudErrorKind {
+        match self {
+            GroupError::MismatchGroupId { .. } => CrudErrorKind::Invalid,
+            GroupError::GroupNotExists { .. } | GroupError::UserNotExists { .. } => {
+                CrudErrorKind::NotFound
+            }
+            GroupError::NameAlreadyUsed { .. } => CrudErrorKind::Conflict,
+            GroupError::Forbidden { .. } => CrudErrorKind::Forbidden,
+            GroupError::Unknown(_) => CrudErrorKind::Internal,
+        }
+    }
+
+    fn not_exists(id: &Uuid) -> Self {
+        GroupError::GroupNotExists { id: *id }
+    }
+
+    fn mismatch_id(id1: &Uuid, id2: &Uuid) -> Self {
+        GroupError::MismatchGroupId {
+            id1: *id1,
+            id2: *id2,
+        }
+    }
+
+    fn forbidden(operation: UserOperation) -> Self {
+        GroupError::Forbidden { operation }
+    }
+}
+
+impl CrudEntityTrait for Group {
+    type AddRequest = GroupAddRequest;
+    type UpdateRequest = GroupUpdateRequest;
+    type FindRequest = GroupFindRequest;
+    type FindParams = GroupFindRequest;
+    type Error = GroupError;
+
+    fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+
+    fn with_id(&self, id: &Uuid) -> Self {
+        Self::new(id, &self.name).with_tenant(&self.tenant)
+    }
+
+    fn from_add_request(req: &GroupAddRequest) -> Result<Self, GroupError> {
+        Ok(Self::new(&Uuid::nil(), req.get_name()))
+    }
+
+    fn from_update_request(id: &Uuid, req: &GroupUpdateRequest) -> Result<Self, GroupError> {
+        Ok(Self::new(id, req.get_name()))
+    }
+
+    /// Names are unique within a tenant.
+    fn conflict_with(&self, other: &Self) -> Option<GroupError> {
+        (self.tenant.eq(&other.tenant) && self.name.eq(&other.name)).then(|| {
+            GroupError::NameAlreadyUsed {
+                name: self.name.clone(),
+            }
+        })
+    }
+}
@@ -0,0 +1,3 @@
+pub mod group;
+
+pub use group::{Group, GroupError};
@@ -0,0 +1,41 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    crud::CrudRepositoryTrait,
+    group::{Group, GroupError},
+};
+
+/// Stores groups and their memberships; deleting a group drops its memberships.
+pub trait GroupRepositoryTrait: CrudRepositoryTrait<Group> {
+    /// Handle over the groups of `tenant` only; handles built by the adapter
+    /// constructors see the default tenant.
+    fn for_tenant(&self, tenant: &TenantId) -> Self;
+
+    fn add_member(
+        &self,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send;
+
+    fn remove_member(
+        &self,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send;
+
+    fn find_members(
+        &self,
+        group_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<Uuid>, GroupError>> + Send;
+
+    fn find_groups_of(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<Group>, GroupError>> + Send;
+
+    /// Removes the user from every group of the tenant it belongs to.
+    fn remove_user(&self, user_id: &Uuid) -> impl Future<Output = Result<(), GroupError>> + Send;
+}
@@ -0,0 +1,32 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::RequestContext,
+    crud::CrudServiceTrait,
+    group::{Group, GroupError},
+    user::User,
+};
+
+pub trait GroupServiceTrait: CrudServiceTrait<Entity = Group> {
+    fn add_member(
+        &self,
+        context: &RequestContext,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send;
+
+    fn remove_member(
+        &self,
+        context: &RequestContext,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send;
+
+    fn find_members(
+        &self,
+        context: &RequestContext,
+        group_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<User>, GroupError>> + Send;
+}
@@ -0,0 +1,5 @@
+pub mod group_repository_trait;
+pub mod group_service_trait;
+
+pub use group_repository_trait::GroupRepositoryTrait;
+pub use group_service_trait::GroupServiceTrait;
@@ -0,0 +1,213 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::RequestContext,
+    crud::{service::crud_service::CrudService, CrudEntityTrait, CrudPage, CrudServiceTrait},
+    group::{
+        Group, GroupAddRequest, GroupError, GroupFindRequest, GroupRepositoryTrait,
+        GroupServiceTrait, GroupUpdateRequest,
+    },
+    user::{
+        model::user::UserError, User, UserEvent, UserFindRequest, UserFindResponse, UserOperation,
+        UserPolicy, UserRepositoryTrait,
+    },
+};
+
+#[derive(Debug, Clone)]
+pub struct GroupService<G, R>
+where
+    G: GroupRepositoryTrait,
+    R: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+        Event = UserEvent,
+    >,
+{
+    groups: CrudService<Group, G>,
+    user_repository: R,
+}
+
+impl<G, R> GroupService<G, R>
+where
+    G: GroupRepositoryTrait,
+    R: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+        Event = UserEvent,
+    >,
+{
+    pub fn new(group_repository: G, user_repository: R) -> Self {
+        Self {
+            groups: CrudService::new(group_repository),
+            user_repository,
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UserPolicy) -> Self {
+        self.groups = self.groups.with_policy(policy);
+        self
+    }
+
+    fn repository(&self, context: &RequestContext) -> G {
+        self.groups
+            .get_repository()
+            .for_tenant(context.get_tenant())
+    }
+
+    async fn find_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> Result<User, GroupError> {
+        self.user_repository
+            .for_tenant(context.get_tenant())
+            .find_by_id(user_id)
+            .await
+            .map_err(|e| match e {
+                UserError::UserNotExists { id } => GroupError::UserNotExists { id },
+                e => GroupError::Unknown(e.into()),
+            })
+    }
+}
+
+impl<G, R> CrudServiceTrait for GroupService<G, R>
+where
+    G: GroupRepositoryTrait,
+    R: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+        Event = UserEvent,
+    >,
+{
+    type Entity = Group;
+
+    fn create(
+        &self,
+        context: &RequestContext,
+        req: &GroupAddRequest,
+    ) -> impl Future<Output = Result<Group, GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Create)?;
+            self.repository(context)
+                .save(&Group::from_add_request(req)?)
+                .await
+        })
+    }
+
+    fn update(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+        req: &GroupUpdateRequest,
+    ) -> impl Future<Output = Result<Group, GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Update)?;
+            self.repository(context)
+                .update(id, &Group::from_update_request(id, req)?)
+                .await
+        })
+    }
+
+    fn find_one(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<Group, GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Read)?;
+            self.repository(context).find_by_id(id).await
+        })
+    }
+
+    fn find(
+        &self,
+        context: &RequestContext,
+        req: &GroupFindRequest,
+    ) -> impl Future<Output = Result<CrudPage<Group>, GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::List)?;
+            self.repository(context).find_all(req).await
+        })
+    }
+
+    fn delete(
+        &self,
+        context: &RequestContext,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Delete)?;
+            self.repository(context).delete(id).await
+        })
+    }
+}
+
+impl<G, R> GroupServiceTrait for GroupService<G, R>
+where
+    G: GroupRepositoryTrait,
+    R: UserRepositoryTrait<
+        Id = Uuid,
+        Entity = User,
+        Error = UserError,
+        FindOptions = UserFindRequest,
+        FindResult = UserFindResponse,
+        Event = UserEvent,
+    >,
+{
+    fn add_member(
+        &self,
+        context: &RequestContext,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Update)?;
+            self.find_user(context, user_id).await?;
+            self.repository(context).add_member(group_id, user_id).await
+        })
+    }
+
+    fn remove_member(
+        &self,
+        context: &RequestContext,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Update)?;
+            self.repository(context)
+                .remove_member(group_id, user_id)
+                .await
+        })
+    }
+
+    fn find_members(
+        &self,
+        context: &RequestContext,
+        group_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<User>, GroupError>> + Send {
+        Box::pin(async {
+            self.groups.authorize(context, UserOperation::Read)?;
+            let mut members = Vec::new();
+            for user_id in self.repository(context).find_members(group_id).await? {
+                // Members belonging to another tenant are not visible.
+                match self.find_user(context, &user_id).await {
+                    Err(GroupError::UserNotExists { .. }) => continue,
+                    member => members.push(member?),
+                }
+            }
+            Ok(members)
+        })
+    }
+}
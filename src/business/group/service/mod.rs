@@ -0,0 +1 @@
+pub mod group_service;
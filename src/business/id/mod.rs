@@ -0,0 +1,3 @@
+pub mod ports;
+
+pub use ports::IdGeneratorTrait;
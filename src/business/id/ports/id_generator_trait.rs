@@ -0,0 +1,6 @@
+use uuid::Uuid;
+
+/// Mints the ids of new entities.
+pub trait IdGeneratorTrait: Sync + Send + Clone + 'static {
+    fn generate(&self) -> Uuid;
+}
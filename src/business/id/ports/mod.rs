@@ -0,0 +1,3 @@
+pub mod id_generator_trait;
+
+pub use id_generator_trait::IdGeneratorTrait;
@@ -0,0 +1,6 @@
+pub mod model;
+pub mod ports;
+
+pub use model::{IdempotencyError, IdempotencyRecord, StoredResponse};
+
+pub use ports::IdempotencyStoreTrait;
@@ -0,0 +1,7 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum IdempotencyError {
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
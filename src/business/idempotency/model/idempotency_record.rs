@@ -0,0 +1,59 @@
+/// Response kept to be replayed to retries of the same request.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredResponse {
+    status: u16,
+    headers: Vec<(String, String)>,
+    body: Vec<u8>,
+}
+
+impl StoredResponse {
+    pub fn new(status: u16, headers: &[(String, String)], body: &[u8]) -> Self {
+        Self {
+            status,
+            headers: headers.to_vec(),
+            body: body.to_vec(),
+        }
+    }
+
+    pub fn get_status(&self) -> u16 {
+        self.status
+    }
+
+    pub fn get_headers(&self) -> &[(String, String)] {
+        &self.headers
+    }
+
+    pub fn get_body(&self) -> &[u8] {
+        &self.body
+    }
+}
+
+/// A request seen under an idempotency key, with its response once it is
+/// complete.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct IdempotencyRecord {
+    fingerprint: String,
+    response: Option<StoredResponse>,
+}
+
+impl IdempotencyRecord {
+    pub fn new(fingerprint: &str) -> Self {
+        Self {
+            fingerprint: fingerprint.to_string(),
+            response: None,
+        }
+    }
+
+    pub fn with_response(mut self, response: &StoredResponse) -> Self {
+        self.response = Some(response.clone());
+        self
+    }
+
+    pub fn get_fingerprint(&self) -> &str {
+        &self.fingerprint
+    }
+
+    pub fn get_response(&self) -> Option<&StoredResponse> {
+        self.response.as_ref()
+    }
+}
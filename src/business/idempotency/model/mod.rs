@@ -0,0 +1,5 @@
+pub mod idempotency_error;
+pub mod idempotency_record;
+
+pub use idempotency_error::IdempotencyError;
+pub use idempotency_record::{IdempotencyRecord, StoredResponse};
@@ -0,0 +1,26 @@
+use std::{future::Future, time::Duration};
+
+use crate::business::idempotency::{IdempotencyError, IdempotencyRecord, StoredResponse};
+
+pub trait IdempotencyStoreTrait: Sync + Send + Clone + 'static {
+    /// Records the request under `key` as in progress for `lease`, unless an
+    /// unexpired record is already there, in which case that record is
+    /// returned instead.
+    fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lease: &Duration,
+    ) -> impl Future<Output = Result<Option<IdempotencyRecord>, IdempotencyError>> + Send;
+
+    /// Keeps the response of the request under `key` for `ttl` from now.
+    fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: &Duration,
+    ) -> impl Future<Output = Result<(), IdempotencyError>> + Send;
+
+    /// Forgets the key, so that the request can be retried.
+    fn release(&self, key: &str) -> impl Future<Output = Result<(), IdempotencyError>> + Send;
+}
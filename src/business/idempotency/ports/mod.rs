@@ -0,0 +1,3 @@
+pub mod idempotency_store_trait;
+
+pub use idempotency_store_trait::IdempotencyStoreTrait;
@@ -0,0 +1,6 @@
+pub mod model;
+pub mod ports;
+
+pub use model::{EmailMessage, MailerError};
+
+pub use ports::MailerTrait;
@@ -0,0 +1,40 @@
+use thiserror::Error;
+
+use crate::business::user::EmailAddress;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct EmailMessage {
+    to: EmailAddress,
+    subject: String,
+    body: String,
+}
+
+impl EmailMessage {
+    pub fn new(to: &EmailAddress, subject: &str, body: &str) -> Self {
+        Self {
+            to: to.clone(),
+            subject: subject.to_string(),
+            body: body.to_string(),
+        }
+    }
+
+    pub fn get_to(&self) -> &EmailAddress {
+        &self.to
+    }
+
+    pub fn get_subject(&self) -> &str {
+        &self.subject
+    }
+
+    pub fn get_body(&self) -> &str {
+        &self.body
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum MailerError {
+    #[error("Mail transport failed: {reason}")]
+    Transport { reason: String },
+    #[error("Mail server rejected the message with {code}: {reply}")]
+    Rejected { code: u16, reply: String },
+}
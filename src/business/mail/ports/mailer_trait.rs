@@ -0,0 +1,7 @@
+use std::future::Future;
+
+use crate::business::mail::{EmailMessage, MailerError};
+
+pub trait MailerTrait: Sync + Send + Clone + 'static {
+    fn send(&self, message: &EmailMessage) -> impl Future<Output = Result<(), MailerError>> + Send;
+}
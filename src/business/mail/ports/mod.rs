@@ -0,0 +1,3 @@
+pub mod mailer_trait;
+
+pub use mailer_trait::MailerTrait;
@@ -1 +1,12 @@
+pub mod audit;
+pub mod auth;
+pub mod credential;
+pub mod crud;
+pub mod encryption;
+pub mod group;
+pub mod id;
+pub mod idempotency;
+pub mod mail;
+pub mod rate_limit;
 pub mod user;
+pub mod webhook;
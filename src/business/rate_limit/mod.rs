@@ -0,0 +1,6 @@
+pub mod model;
+pub mod ports;
+
+pub use model::{RateLimit, RateLimitDecision, RateLimitError, TokenBucket};
+
+pub use ports::RateLimitStoreTrait;
@@ -0,0 +1,5 @@
+pub mod rate_limit;
+pub mod rate_limit_error;
+
+pub use rate_limit::{RateLimit, RateLimitDecision, TokenBucket};
+pub use rate_limit_error::RateLimitError;
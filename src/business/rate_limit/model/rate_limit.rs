@@ -0,0 +1,162 @@
+use std::time::{Duration, Instant};
+
+use serde::Deserialize;
+
+use super::RateLimitError;
+
+/// Allows bursts of `requests` requests, refilled evenly over `period`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "RateLimitConfig")]
+pub struct RateLimit {
+    requests: u32,
+    period: Duration,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RateLimitConfig {
+    requests: u32,
+    period_secs: u64,
+}
+
+impl TryFrom<RateLimitConfig> for RateLimit {
+    type Error = RateLimitError;
+
+    fn try_from(value: RateLimitConfig) -> Result<Self, Self::Error> {
+        RateLimit::new(value.requests, Duration::from_secs(value.period_secs))
+    }
+}
+
+impl RateLimit {
+    pub fn new(requests: u32, period: Duration) -> Result<Self, RateLimitError> {
+        if requests == 0 || period.is_zero() {
+            return Err(RateLimitError::InvalidLimit {
+                reason: String::from("requests and period must be positive"),
+            });
+        }
+        Ok(Self { requests, period })
+    }
+
+    pub fn get_requests(&self) -> u32 {
+        self.requests
+    }
+
+    pub fn get_period(&self) -> &Duration {
+        &self.period
+    }
+
+    fn refill_time(&self, tokens: f64) -> Duration {
+        self.period
+            .mul_f64(tokens.max(0.0) / f64::from(self.requests))
+    }
+}
+
+/// Outcome of taking a token, as reported in the `RateLimit-*` headers.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RateLimitDecision {
+    allowed: bool,
+    limit: RateLimit,
+    remaining: u32,
+    reset: Duration,
+    retry_after: Option<Duration>,
+}
+
+impl RateLimitDecision {
+    pub fn is_allowed(&self) -> bool {
+        self.allowed
+    }
+
+    pub fn get_limit(&self) -> &RateLimit {
+        &self.limit
+    }
+
+    pub fn get_remaining(&self) -> u32 {
+        self.remaining
+    }
+
+    /// Time until the bucket is full again.
+    pub fn get_reset(&self) -> &Duration {
+        &self.reset
+    }
+
+    /// Time until the next token, when the request was denied.
+    pub fn get_retry_after(&self) -> Option<&Duration> {
+        self.retry_after.as_ref()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct TokenBucket {
+    tokens: f64,
+    updated_at: Instant,
+}
+
+impl TokenBucket {
+    pub fn new(limit: &RateLimit, now: Instant) -> Self {
+        Self {
+            tokens: f64::from(limit.requests),
+            updated_at: now,
+        }
+    }
+
+    fn refill(&mut self, limit: &RateLimit, now: Instant) {
+        let elapsed = now.saturating_duration_since(self.updated_at);
+        let refilled =
+            elapsed.as_secs_f64() / limit.period.as_secs_f64() * f64::from(limit.requests);
+        self.tokens = (self.tokens + refilled).min(f64::from(limit.requests));
+        self.updated_at = now;
+    }
+
+    pub fn acquire(&mut self, limit: &RateLimit, now: Instant) -> RateLimitDecision {
+        self.refill(limit, now);
+        let allowed = self.tokens >= 1.0;
+        if allowed {
+            self.tokens -= 1.0;
+        }
+        RateLimitDecision {
+            allowed,
+            limit: *limit,
+            remaining: self.tokens.floor() as u32,
+            reset: limit.refill_time(f64::from(limit.requests) - self.tokens),
+            retry_after: (!allowed).then(|| limit.refill_time(1.0 - self.tokens)),
+        }
+    }
+
+    /// A full bucket is the same as no bucket, so stores may forget it.
+    pub fn is_full(&self, limit: &RateLimit, now: Instant) -> bool {
+        let mut bucket = *self;
+        bucket.refill(limit, now);
+        bucket.tokens >= f64::from(limit.requests)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use super::{RateLimit, TokenBucket};
+
+    #[test]
+    fn test_token_bucket() {
+        let limit = RateLimit::new(2, Duration::from_secs(10)).unwrap();
+        let start = Instant::now();
+        let mut bucket = TokenBucket::new(&limit, start);
+
+        let first = bucket.acquire(&limit, start);
+        assert!(first.is_allowed());
+        assert_eq!(first.get_remaining(), 1);
+        assert_eq!(first.get_reset(), &Duration::from_secs(5));
+        assert!(bucket.acquire(&limit, start).is_allowed());
+        let denied = bucket.acquire(&limit, start);
+        assert!(!denied.is_allowed());
+        assert_eq!(denied.get_remaining(), 0);
+        assert_eq!(denied.get_retry_after(), Some(&Duration::from_secs(5)));
+
+        let later = start + Duration::from_secs(5);
+        assert!(bucket.acquire(&limit, later).is_allowed());
+        assert!(!bucket.acquire(&limit, later).is_allowed());
+        assert!(!bucket.is_full(&limit, later + Duration::from_secs(9)));
+        assert!(bucket.is_full(&limit, later + Duration::from_secs(10)));
+        assert!(RateLimit::new(0, Duration::from_secs(1)).is_err());
+    }
+}
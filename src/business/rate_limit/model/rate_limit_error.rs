@@ -0,0 +1,11 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum RateLimitError {
+    #[error("Invalid rate limit: {reason}")]
+    InvalidLimit { reason: String },
+    #[error("Invalid rate limit configuration: {reason}")]
+    InvalidConfig { reason: String },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
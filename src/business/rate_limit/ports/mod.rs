@@ -0,0 +1,3 @@
+pub mod rate_limit_store_trait;
+
+pub use rate_limit_store_trait::RateLimitStoreTrait;
@@ -0,0 +1,13 @@
+use std::future::Future;
+
+use crate::business::rate_limit::{RateLimit, RateLimitDecision, RateLimitError};
+
+/// Keeps one token bucket per key.
+pub trait RateLimitStoreTrait: Sync + Send + Clone + 'static {
+    /// Takes a token from the bucket of `key`, created full on first use.
+    fn acquire(
+        &self,
+        key: &str,
+        limit: &RateLimit,
+    ) -> impl Future<Output = Result<RateLimitDecision, RateLimitError>> + Send;
+}
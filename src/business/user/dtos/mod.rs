@@ -1,9 +1,17 @@
 pub mod user_add_request;
+pub mod user_data_export;
 pub mod user_delete_request;
 pub mod user_find_request;
+pub mod user_suspend_request;
 pub mod user_update_request;
+pub mod user_verify_email_request;
 
 pub use user_add_request::UserAddRequest;
+pub use user_data_export::UserDataExport;
 pub use user_delete_request::{UserDeleteRequest, UserDeleteRequestError};
-pub use user_find_request::{UserFindRequest, UserFindRequestError, UserFindResponse};
+pub use user_find_request::{
+    AttributeFilter, UserFindRequest, UserFindRequestError, UserFindRequestFilter, UserFindResponse,
+};
+pub use user_suspend_request::UserSuspendRequest;
 pub use user_update_request::{UserUpdateRequest, UserUpdateRequestError};
+pub use user_verify_email_request::UserVerifyEmailRequest;
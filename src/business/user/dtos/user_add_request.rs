@@ -2,30 +2,96 @@ use serde::Deserialize;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
-use crate::business::user::{EmailAddress, Name, User};
+use crate::business::user::{
+    AvatarUrl, CustomAttributes, EmailAddress, Locale, Name, PhoneNumber, TimeZone, User,
+};
 
 #[derive(Debug, PartialEq, Eq, Deserialize, ToSchema)]
 pub struct UserAddRequest {
+    /// Chosen by the client instead of generated, rejected if already taken.
+    #[serde(default)]
+    id: Option<Uuid>,
     firstname: Name,
     lastname: Name,
     email: EmailAddress,
+    #[serde(default)]
+    phone: Option<PhoneNumber>,
+    #[serde(default)]
+    locale: Option<Locale>,
+    #[serde(default)]
+    timezone: Option<TimeZone>,
+    #[serde(default)]
+    avatar_url: Option<AvatarUrl>,
+    #[serde(default)]
+    attributes: CustomAttributes,
 }
 
 impl From<&UserAddRequest> for User {
     fn from(val: &UserAddRequest) -> Self {
-        User::new(&Uuid::nil(), &val.firstname, &val.lastname, &val.email)
+        let mut user = User::new(
+            &val.id.unwrap_or_default(),
+            &val.firstname,
+            &val.lastname,
+            &val.email,
+        );
+        user.set_phone(val.phone.as_ref());
+        user.set_locale(val.locale.as_ref());
+        user.set_timezone(val.timezone.as_ref());
+        user.set_avatar_url(val.avatar_url.as_ref());
+        user.set_attributes(&val.attributes);
+        user
     }
 }
 
 impl UserAddRequest {
     pub fn new(firstname: &Name, lastname: &Name, email: &EmailAddress) -> Self {
         Self {
+            id: None,
             email: email.clone(),
             firstname: firstname.clone(),
             lastname: lastname.clone(),
+            phone: None,
+            locale: None,
+            timezone: None,
+            avatar_url: None,
+            attributes: CustomAttributes::default(),
         }
     }
 
+    pub fn with_id(mut self, id: &Uuid) -> Self {
+        self.id = Some(*id);
+        self
+    }
+
+    pub fn with_phone(mut self, phone: &PhoneNumber) -> Self {
+        self.phone = Some(phone.clone());
+        self
+    }
+
+    pub fn with_locale(mut self, locale: &Locale) -> Self {
+        self.locale = Some(locale.clone());
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: &TimeZone) -> Self {
+        self.timezone = Some(timezone.clone());
+        self
+    }
+
+    pub fn with_avatar_url(mut self, avatar_url: &AvatarUrl) -> Self {
+        self.avatar_url = Some(avatar_url.clone());
+        self
+    }
+
+    pub fn with_attributes(mut self, attributes: &CustomAttributes) -> Self {
+        self.attributes = attributes.clone();
+        self
+    }
+
+    pub fn get_id(&self) -> Option<&Uuid> {
+        self.id.as_ref()
+    }
+
     pub fn get_firstname(&self) -> &Name {
         &self.firstname
     }
@@ -37,4 +103,8 @@ impl UserAddRequest {
     pub fn get_lastname(&self) -> &Name {
         &self.lastname
     }
+
+    pub fn get_attributes(&self) -> &CustomAttributes {
+        &self.attributes
+    }
 }
@@ -0,0 +1,39 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+
+use crate::business::{audit::AuditEntry, group::Group, user::User};
+
+/// Everything held about a user, as handed over on a data subject request.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct UserDataExport {
+    exported_at: u64,
+    user: User,
+    groups: Vec<Group>,
+    history: Vec<AuditEntry>,
+}
+
+impl UserDataExport {
+    pub fn new(user: &User, groups: &[Group], history: &[AuditEntry]) -> Self {
+        Self {
+            exported_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_millis() as u64),
+            user: user.clone(),
+            groups: groups.to_vec(),
+            history: history.to_vec(),
+        }
+    }
+
+    pub fn get_user(&self) -> &User {
+        &self.user
+    }
+
+    pub fn get_groups(&self) -> &[Group] {
+        &self.groups
+    }
+
+    pub fn get_history(&self) -> &[AuditEntry] {
+        &self.history
+    }
+}
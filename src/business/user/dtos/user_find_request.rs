@@ -1,15 +1,19 @@
-use std::borrow::Borrow;
+use std::{
+    borrow::Borrow,
+    collections::{BTreeMap, BTreeSet},
+};
 
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 use utoipa::{IntoParams, ToSchema};
 
 use crate::{
-    business::user::{model::user::UserError, User},
+    business::user::{model::user::UserError, AttributeError, CustomAttributes, User, UserStatus},
     outbound::repository_trait::{FindOptionTrait, FindResultTrait},
 };
 
-#[derive(Debug, Clone, PartialEq, Eq, Deserialize, IntoParams)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "UserFindRequestQuery")]
 pub struct UserFindRequest {
     filters: UserFindRequestFilter,
     order_by: String,
@@ -84,6 +88,172 @@ pub struct UserFindRequestFilter {
     pub firstname: Option<String>,
     pub lastname: Option<String>,
     pub email: Option<String>,
+    pub verified: Option<bool>,
+    pub status: Option<UserStatus>,
+    pub phone: Option<String>,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    pub avatar_url: Option<String>,
+    /// Only members of this group
+    pub group: Option<uuid::Uuid>,
+    /// Comma separated `name:value` custom attribute filters
+    #[param(value_type = Option<String>)]
+    #[schema(value_type = Option<String>)]
+    pub attributes: Option<AttributeFilter>,
+    /// Members of `group`, resolved by the service before querying.
+    #[serde(skip)]
+    #[param(ignore)]
+    #[schema(ignore)]
+    pub members: Option<BTreeSet<uuid::Uuid>>,
+}
+
+impl UserFindRequestFilter {
+    /// A text filter ending with `*` matches values starting with what precedes it.
+    pub fn matches_text(pattern: &str, value: &str) -> bool {
+        match pattern.strip_suffix('*') {
+            Some(prefix) => value.starts_with(prefix),
+            None => pattern.eq(value),
+        }
+    }
+
+    /// An optional field only matches a text filter when it is set.
+    fn matches_optional(pattern: &Option<String>, value: Option<&str>) -> bool {
+        pattern.as_ref().map_or(true, |pattern| {
+            value.is_some_and(|value| Self::matches_text(pattern, value))
+        })
+    }
+
+    pub fn matches(&self, user: &User) -> bool {
+        self.id.map_or(true, |id| id.eq(user.get_id()))
+            && self
+                .email
+                .as_ref()
+                .map_or(true, |email| Self::matches_text(email, user.get_email()))
+            && self.firstname.as_ref().map_or(true, |firstname| {
+                Self::matches_text(firstname, user.get_firstname())
+            })
+            && self.lastname.as_ref().map_or(true, |lastname| {
+                Self::matches_text(lastname, user.get_lastname())
+            })
+            && self
+                .verified
+                .map_or(true, |verified| verified == user.is_verified())
+            && self
+                .status
+                .map_or(true, |status| status.eq(user.get_status()))
+            && Self::matches_optional(&self.phone, user.get_phone().map(|phone| &**phone))
+            && Self::matches_optional(&self.locale, user.get_locale().map(|locale| &**locale))
+            && Self::matches_optional(
+                &self.timezone,
+                user.get_timezone().map(|timezone| &**timezone),
+            )
+            && Self::matches_optional(
+                &self.avatar_url,
+                user.get_avatar_url().map(|avatar_url| &**avatar_url),
+            )
+            && self
+                .members
+                .as_ref()
+                .map_or(true, |members| members.contains(user.get_id()))
+            && self
+                .attributes
+                .as_ref()
+                .map_or(true, |filter| filter.matches(user.get_attributes()))
+    }
+}
+
+/// Custom attribute filters parsed from `name:value,other:value`, each value
+/// being matched like other text filters.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct AttributeFilter(BTreeMap<String, String>);
+
+impl AttributeFilter {
+    pub fn new(raw: &str) -> Result<Self, AttributeError> {
+        raw.split(',')
+            .map(|pair| match pair.split_once(':') {
+                Some((name, value)) if !name.trim().is_empty() => {
+                    Ok((name.trim().to_string(), value.trim().to_string()))
+                }
+                _ => Err(AttributeError::InvalidFilter {
+                    filter: raw.to_string(),
+                }),
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+
+    pub fn matches(&self, attributes: &CustomAttributes) -> bool {
+        self.0.iter().all(|(name, pattern)| {
+            attributes.get(name).is_some_and(|value| {
+                UserFindRequestFilter::matches_text(pattern, &value.to_string())
+            })
+        })
+    }
+}
+
+impl TryFrom<String> for AttributeFilter {
+    type Error = AttributeError;
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, IntoParams)]
+pub struct UserFindRequestQuery {
+    pub id: Option<uuid::Uuid>,
+    pub firstname: Option<String>,
+    pub lastname: Option<String>,
+    pub email: Option<String>,
+    pub verified: Option<bool>,
+    pub status: Option<UserStatus>,
+    pub phone: Option<String>,
+    pub locale: Option<String>,
+    pub timezone: Option<String>,
+    pub avatar_url: Option<String>,
+    pub group: Option<uuid::Uuid>,
+    /// Comma separated `name:value` custom attribute filters
+    #[param(value_type = Option<String>)]
+    pub attributes: Option<AttributeFilter>,
+    /// `email`, `firstname`, `lastname` or `attributes.<name>`
+    #[serde(default)]
+    pub order_by: String,
+    #[serde(default = "UserFindRequestQuery::default_per_page")]
+    pub per_page: u16,
+    #[serde(default = "UserFindRequestQuery::default_offset")]
+    pub offset: u64,
+}
+
+impl UserFindRequestQuery {
+    fn default_per_page() -> u16 {
+        25
+    }
+
+    fn default_offset() -> u64 {
+        1
+    }
+}
+
+impl TryFrom<UserFindRequestQuery> for UserFindRequest {
+    type Error = UserFindRequestError;
+    fn try_from(value: UserFindRequestQuery) -> Result<Self, Self::Error> {
+        let filters = UserFindRequestFilter {
+            id: value.id,
+            firstname: value.firstname,
+            lastname: value.lastname,
+            email: value.email,
+            verified: value.verified,
+            status: value.status,
+            phone: value.phone,
+            locale: value.locale,
+            timezone: value.timezone,
+            avatar_url: value.avatar_url,
+            group: value.group,
+            attributes: value.attributes,
+            members: None,
+        };
+        Self::new(&filters, &value.order_by, &value.per_page, &value.offset)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize)]
@@ -181,8 +351,12 @@ where
 mod tests {
     use uuid::Uuid;
 
-    use crate::business::user::dtos::{
-        user_find_request::UserFindRequestFilter, UserFindRequestError,
+    use crate::business::user::{
+        dtos::{
+            user_find_request::{UserFindRequestFilter, UserFindRequestQuery},
+            UserFindRequestError,
+        },
+        EmailAddress, Locale, Name, PhoneNumber, TimeZone, User,
     };
 
     use super::UserFindRequest;
@@ -236,6 +410,78 @@ mod tests {
         assert!(user_find_request.is_ok());
     }
 
+    #[test]
+    fn test_user_find_request_from_query_ok() {
+        let query = UserFindRequestQuery {
+            id: None,
+            firstname: None,
+            lastname: None,
+            email: Some(String::from("test@example.com")),
+            verified: Some(true),
+            status: None,
+            phone: None,
+            locale: None,
+            timezone: None,
+            avatar_url: None,
+            group: None,
+            attributes: None,
+            order_by: String::from("email"),
+            per_page: 10,
+            offset: 2,
+        };
+        let user_find_request = UserFindRequest::try_from(query).unwrap();
+        assert_eq!(user_find_request.per_page, 10);
+        assert_eq!(user_find_request.offset, 2);
+        assert_eq!(user_find_request.filters.verified, Some(true));
+    }
+
+    #[test]
+    fn test_user_find_request_from_query_ko() {
+        let query = UserFindRequestQuery {
+            id: None,
+            firstname: None,
+            lastname: None,
+            email: None,
+            verified: None,
+            status: None,
+            phone: None,
+            locale: None,
+            timezone: None,
+            avatar_url: None,
+            group: None,
+            attributes: None,
+            order_by: String::new(),
+            per_page: 0,
+            offset: 1,
+        };
+        assert!(UserFindRequest::try_from(query).is_err());
+    }
+
+    #[test]
+    fn test_user_find_request_filter_profile() {
+        let mut user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        user.set_phone(Some(&PhoneNumber::new("+261340000000").unwrap()));
+        user.set_locale(Some(&Locale::new("fr-MG").unwrap()));
+        let filter = UserFindRequestFilter {
+            phone: Some(String::from("+261*")),
+            locale: Some(String::from("fr*")),
+            ..Default::default()
+        };
+        assert!(filter.matches(&user));
+        let filter = UserFindRequestFilter {
+            timezone: Some(String::from("Indian/*")),
+            ..Default::default()
+        };
+        assert!(!filter.matches(&user));
+        user.set_timezone(Some(&TimeZone::new("Indian/Antananarivo").unwrap()));
+        assert!(filter.matches(&user));
+    }
+
     #[test]
     fn test_user_find_request_set_filter_ok() {
         let user_find_request_filter = UserFindRequestFilter {
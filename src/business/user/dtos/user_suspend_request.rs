@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct UserSuspendRequest {
+    reason: String,
+}
+
+impl UserSuspendRequest {
+    pub fn new(reason: &str) -> Self {
+        Self {
+            reason: reason.to_string(),
+        }
+    }
+
+    pub fn get_reason(&self) -> &str {
+        &self.reason
+    }
+}
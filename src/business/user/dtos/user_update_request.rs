@@ -2,7 +2,9 @@ use serde::Deserialize;
 use thiserror::Error;
 use utoipa::ToSchema;
 
-use crate::business::user::{EmailAddress, Name, User};
+use crate::business::user::{
+    AvatarUrl, CustomAttributes, EmailAddress, Locale, Name, PhoneNumber, TimeZone, User,
+};
 
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
 pub struct UserUpdateRequest {
@@ -10,6 +12,16 @@ pub struct UserUpdateRequest {
     firstname: Name,
     lastname: Name,
     email: EmailAddress,
+    #[serde(default)]
+    phone: Option<PhoneNumber>,
+    #[serde(default)]
+    locale: Option<Locale>,
+    #[serde(default)]
+    timezone: Option<TimeZone>,
+    #[serde(default)]
+    avatar_url: Option<AvatarUrl>,
+    #[serde(default)]
+    attributes: CustomAttributes,
 }
 
 impl UserUpdateRequest {
@@ -19,13 +31,49 @@ impl UserUpdateRequest {
             firstname: firstname.clone(),
             lastname: lastname.clone(),
             email: email.clone(),
+            phone: None,
+            locale: None,
+            timezone: None,
+            avatar_url: None,
+            attributes: CustomAttributes::default(),
         }
     }
+
+    pub fn with_phone(mut self, phone: &PhoneNumber) -> Self {
+        self.phone = Some(phone.clone());
+        self
+    }
+
+    pub fn with_locale(mut self, locale: &Locale) -> Self {
+        self.locale = Some(locale.clone());
+        self
+    }
+
+    pub fn with_timezone(mut self, timezone: &TimeZone) -> Self {
+        self.timezone = Some(timezone.clone());
+        self
+    }
+
+    pub fn with_avatar_url(mut self, avatar_url: &AvatarUrl) -> Self {
+        self.avatar_url = Some(avatar_url.clone());
+        self
+    }
+
+    pub fn with_attributes(mut self, attributes: &CustomAttributes) -> Self {
+        self.attributes = attributes.clone();
+        self
+    }
 }
 
 impl From<&UserUpdateRequest> for User {
     fn from(val: &UserUpdateRequest) -> Self {
-        User::new(&val.id, &val.firstname, &val.lastname, &val.email)
+        let mut user = User::new(&val.id, &val.firstname, &val.lastname, &val.email);
+        user.set_phone(val.phone.as_ref());
+        user.set_locale(val.locale.as_ref());
+        user.set_timezone(val.timezone.as_ref());
+        user.set_avatar_url(val.avatar_url.as_ref());
+        user.set_attributes(&val.attributes);
+        user
     }
 }
 
@@ -0,0 +1,19 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct UserVerifyEmailRequest {
+    token: String,
+}
+
+impl UserVerifyEmailRequest {
+    pub fn new(token: &str) -> Self {
+        Self {
+            token: token.to_string(),
+        }
+    }
+
+    pub fn get_token(&self) -> &str {
+        &self.token
+    }
+}
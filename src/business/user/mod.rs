@@ -1,12 +1,23 @@
 pub mod dtos;
 pub mod model;
+pub mod policy;
 pub mod ports;
 pub mod service;
 
 pub use dtos::{
-    UserAddRequest, UserDeleteRequest, UserDeleteRequestError, UserFindRequest,
-    UserFindRequestError, UserFindResponse, UserUpdateRequest, UserUpdateRequestError,
+    UserAddRequest, UserDataExport, UserDeleteRequest, UserDeleteRequestError, UserFindRequest,
+    UserFindRequestError, UserFindResponse, UserSuspendRequest, UserUpdateRequest,
+    UserUpdateRequestError, UserVerifyEmailRequest,
 };
-pub use model::{EmailAddress, EmailAddressError, Name, NameError, User};
+pub use model::{
+    AttributeDefinition, AttributeError, AttributeSchema, AttributeType, AttributeValue, AvatarUrl,
+    AvatarUrlError, CustomAttributes, EmailAddress, EmailAddressError, Locale, LocaleError, Name,
+    NameError, PhoneNumber, PhoneNumberError, TimeZone, TimeZoneError, User, UserChange,
+    UserChangeSubscription, UserEvent, UserEventKind, UserEventType, UserStatus, VerificationToken,
+};
+pub use policy::{UserOperation, UserPolicy};
 
-pub use ports::{UserRepositoryTrait, UserServiceTrait};
+pub use ports::{
+    EventPublisherTrait, UserChangeFeedTrait, UserRepositoryTrait, UserServiceTrait,
+    VerificationTokenStoreTrait,
+};
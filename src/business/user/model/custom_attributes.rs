@@ -0,0 +1,438 @@
+use std::{collections::BTreeMap, fmt::Display, ops::Deref, path::Path};
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+lazy_static! {
+    static ref ATTRIBUTE_NAME_REGEX: regex::Regex =
+        regex::Regex::new(r"^[a-z][a-z0-9_]{0,63}$").unwrap();
+}
+
+/// Prefix of `order_by` values sorting users by a custom attribute.
+pub const ATTRIBUTE_ORDER_PREFIX: &str = "attributes.";
+
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(untagged)]
+pub enum AttributeValue {
+    Boolean(bool),
+    Integer(i64),
+    String(String),
+}
+
+impl AttributeValue {
+    fn get_type(&self) -> AttributeType {
+        match self {
+            Self::Boolean(_) => AttributeType::Boolean,
+            Self::Integer(_) => AttributeType::Integer,
+            Self::String(_) => AttributeType::String,
+        }
+    }
+}
+
+impl Display for AttributeValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Boolean(value) => value.fmt(f),
+            Self::Integer(value) => value.fmt(f),
+            Self::String(value) => f.write_str(value),
+        }
+    }
+}
+
+/// Extra profile fields of a user, checked against an [`AttributeSchema`].
+#[repr(C)]
+#[derive(
+    Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema,
+)]
+#[serde(transparent)]
+pub struct CustomAttributes(BTreeMap<String, AttributeValue>);
+
+impl CustomAttributes {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with(mut self, name: &str, value: AttributeValue) -> Self {
+        self.0.insert(name.to_string(), value);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Name of the attribute an `order_by` value sorts on, if any.
+    pub fn order_key(order_by: &str) -> Option<&str> {
+        order_by.strip_prefix(ATTRIBUTE_ORDER_PREFIX)
+    }
+}
+
+impl Deref for CustomAttributes {
+    type Target = BTreeMap<String, AttributeValue>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AttributeType {
+    Boolean,
+    Integer,
+    String,
+}
+
+impl Display for AttributeType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Self::Boolean => "boolean",
+            Self::Integer => "integer",
+            Self::String => "string",
+        })
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct AttributeDefinition {
+    #[serde(rename = "type")]
+    attribute_type: AttributeType,
+    #[serde(default)]
+    required: bool,
+    /// Bounds of an integer attribute.
+    min: Option<i64>,
+    max: Option<i64>,
+    /// Constraints of a string attribute.
+    max_length: Option<usize>,
+    pattern: Option<String>,
+    #[serde(default)]
+    values: Vec<String>,
+    description: Option<String>,
+}
+
+impl AttributeDefinition {
+    pub fn new(attribute_type: AttributeType) -> Self {
+        Self {
+            attribute_type,
+            required: false,
+            min: None,
+            max: None,
+            max_length: None,
+            pattern: None,
+            values: Vec::new(),
+            description: None,
+        }
+    }
+
+    pub fn with_required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    pub fn with_range(mut self, min: Option<i64>, max: Option<i64>) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    pub fn with_max_length(mut self, max_length: usize) -> Self {
+        self.max_length = Some(max_length);
+        self
+    }
+
+    pub fn with_pattern(mut self, pattern: &str) -> Self {
+        self.pattern = Some(pattern.to_string());
+        self
+    }
+
+    pub fn with_values(mut self, values: &[&str]) -> Self {
+        self.values = values.iter().map(|value| value.to_string()).collect();
+        self
+    }
+
+    pub fn with_description(mut self, description: &str) -> Self {
+        self.description = Some(description.to_string());
+        self
+    }
+
+    pub fn get_type(&self) -> AttributeType {
+        self.attribute_type
+    }
+
+    pub fn is_required(&self) -> bool {
+        self.required
+    }
+
+    pub fn get_min(&self) -> Option<i64> {
+        self.min
+    }
+
+    pub fn get_max(&self) -> Option<i64> {
+        self.max
+    }
+
+    pub fn get_max_length(&self) -> Option<usize> {
+        self.max_length
+    }
+
+    pub fn get_pattern(&self) -> Option<&str> {
+        self.pattern.as_deref()
+    }
+
+    pub fn get_values(&self) -> &[String] {
+        &self.values
+    }
+
+    pub fn get_description(&self) -> Option<&str> {
+        self.description.as_deref()
+    }
+}
+
+/// Allowed custom attributes. The default schema allows none.
+#[derive(Clone, Debug, Default)]
+pub struct AttributeSchema {
+    definitions: BTreeMap<String, AttributeDefinition>,
+    patterns: BTreeMap<String, regex::Regex>,
+}
+
+impl AttributeSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_attribute(
+        mut self,
+        name: &str,
+        definition: AttributeDefinition,
+    ) -> Result<Self, AttributeError> {
+        if !ATTRIBUTE_NAME_REGEX.is_match(name) {
+            return Err(AttributeError::InvalidSchema {
+                reason: format!("{name} is not a valid attribute name"),
+            });
+        }
+        if let Some(pattern) = definition.get_pattern() {
+            let regex = regex::Regex::new(pattern).map_err(|e| AttributeError::InvalidSchema {
+                reason: format!("pattern of {name}: {e}"),
+            })?;
+            self.patterns.insert(name.to_string(), regex);
+        }
+        self.definitions.insert(name.to_string(), definition);
+        Ok(self)
+    }
+
+    /// Reads a JSON object mapping attribute names to their definition.
+    pub fn from_json(content: &[u8]) -> Result<Self, AttributeError> {
+        let definitions: BTreeMap<String, AttributeDefinition> = serde_json::from_slice(content)
+            .map_err(|e| AttributeError::InvalidSchema {
+                reason: e.to_string(),
+            })?;
+        definitions
+            .into_iter()
+            .try_fold(Self::new(), |schema, (name, definition)| {
+                schema.with_attribute(&name, definition)
+            })
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AttributeError> {
+        let content = std::fs::read(path).map_err(|e| AttributeError::InvalidSchema {
+            reason: format!("cannot read {}: {}", path.display(), e),
+        })?;
+        Self::from_json(&content)
+    }
+
+    pub fn get_definitions(&self) -> &BTreeMap<String, AttributeDefinition> {
+        &self.definitions
+    }
+
+    pub fn validate(&self, attributes: &CustomAttributes) -> Result<(), AttributeError> {
+        if let Some(name) = attributes
+            .keys()
+            .find(|name| !self.definitions.contains_key(*name))
+        {
+            return Err(AttributeError::UnknownAttribute { name: name.clone() });
+        }
+        for (name, definition) in self.definitions.iter() {
+            match attributes.get(name) {
+                None if definition.is_required() => {
+                    return Err(AttributeError::MissingAttribute { name: name.clone() })
+                }
+                None => {}
+                Some(value) => self.validate_value(name, definition, value)?,
+            }
+        }
+        Ok(())
+    }
+
+    fn validate_value(
+        &self,
+        name: &str,
+        definition: &AttributeDefinition,
+        value: &AttributeValue,
+    ) -> Result<(), AttributeError> {
+        let violation = |reason: String| AttributeError::ConstraintViolation {
+            name: name.to_string(),
+            reason,
+        };
+        if value.get_type() != definition.get_type() {
+            return Err(AttributeError::InvalidType {
+                name: name.to_string(),
+                expected: definition.get_type(),
+            });
+        }
+        match value {
+            AttributeValue::Integer(number) => {
+                if let Some(min) = definition.get_min().filter(|min| number < min) {
+                    return Err(violation(format!("must be at least {min}")));
+                }
+                if let Some(max) = definition.get_max().filter(|max| number > max) {
+                    return Err(violation(format!("must be at most {max}")));
+                }
+            }
+            AttributeValue::String(text) => {
+                if let Some(max_length) = definition
+                    .get_max_length()
+                    .filter(|max_length| text.chars().count() > *max_length)
+                {
+                    return Err(violation(format!(
+                        "must be at most {max_length} characters"
+                    )));
+                }
+                if self
+                    .patterns
+                    .get(name)
+                    .is_some_and(|pattern| !pattern.is_match(text))
+                {
+                    return Err(violation(format!(
+                        "must match {}",
+                        definition.get_pattern().unwrap_or_default()
+                    )));
+                }
+                if !definition.get_values().is_empty() && !definition.get_values().contains(text) {
+                    return Err(violation(format!(
+                        "must be one of {}",
+                        definition.get_values().join(", ")
+                    )));
+                }
+            }
+            AttributeValue::Boolean(_) => {}
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum AttributeError {
+    #[error("Attribute {name} is not defined")]
+    UnknownAttribute { name: String },
+    #[error("Attribute {name} is required")]
+    MissingAttribute { name: String },
+    #[error("Attribute {name} must be a {expected}")]
+    InvalidType {
+        name: String,
+        expected: AttributeType,
+    },
+    #[error("Attribute {name} {reason}")]
+    ConstraintViolation { name: String, reason: String },
+    #[error("Attribute filter {filter} must be comma separated name:value pairs")]
+    InvalidFilter { filter: String },
+    #[error("Invalid attribute schema: {reason}")]
+    InvalidSchema { reason: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        AttributeDefinition, AttributeError, AttributeSchema, AttributeType, AttributeValue,
+        CustomAttributes,
+    };
+
+    fn schema() -> AttributeSchema {
+        AttributeSchema::from_json(
+            br#"{
+                "department": {"type": "string", "values": ["Sales", "Engineering"]},
+                "employee_number": {"type": "integer", "required": true, "min": 1},
+                "phone": {"type": "string", "pattern": "^\\+[0-9]+$", "max_length": 16},
+                "remote": {"type": "boolean"}
+            }"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_ok() {
+        let attributes = CustomAttributes::new()
+            .with("employee_number", AttributeValue::Integer(42))
+            .with("department", AttributeValue::String(String::from("Sales")))
+            .with(
+                "phone",
+                AttributeValue::String(String::from("+261340000000")),
+            )
+            .with("remote", AttributeValue::Boolean(true));
+        assert!(schema().validate(&attributes).is_ok());
+    }
+
+    #[test]
+    fn test_validate_ko() {
+        let schema = schema();
+        let base = CustomAttributes::new().with("employee_number", AttributeValue::Integer(42));
+        assert!(matches!(
+            schema.validate(&CustomAttributes::new()),
+            Err(AttributeError::MissingAttribute { .. })
+        ));
+        assert!(matches!(
+            schema.validate(&base.clone().with("badge", AttributeValue::Integer(1))),
+            Err(AttributeError::UnknownAttribute { .. })
+        ));
+        assert!(matches!(
+            schema.validate(&base.clone().with("remote", AttributeValue::Integer(1))),
+            Err(AttributeError::InvalidType {
+                expected: AttributeType::Boolean,
+                ..
+            })
+        ));
+        for invalid in [
+            base.clone()
+                .with("employee_number", AttributeValue::Integer(0)),
+            base.clone()
+                .with("department", AttributeValue::String(String::from("Legal"))),
+            base.clone()
+                .with("phone", AttributeValue::String(String::from("0340000000"))),
+            base.with(
+                "phone",
+                AttributeValue::String(format!("+{}", "1".repeat(16))),
+            ),
+        ] {
+            assert!(matches!(
+                schema.validate(&invalid),
+                Err(AttributeError::ConstraintViolation { .. })
+            ));
+        }
+    }
+
+    #[test]
+    fn test_invalid_schema() {
+        assert!(AttributeSchema::from_json(br#"{"Department": {"type": "string"}}"#).is_err());
+        assert!(AttributeSchema::from_json(br#"{"phone": {"type": "date"}}"#).is_err());
+        assert!(AttributeSchema::new()
+            .with_attribute(
+                "phone",
+                AttributeDefinition::new(AttributeType::String).with_pattern("(")
+            )
+            .is_err());
+    }
+
+    #[test]
+    fn test_attributes_serde() {
+        let attributes: CustomAttributes =
+            serde_json::from_str(r#"{"department": "Sales", "level": 3, "remote": false}"#)
+                .unwrap();
+        assert_eq!(attributes.get("level"), Some(&AttributeValue::Integer(3)));
+        assert_eq!(
+            serde_json::to_string(&attributes).unwrap(),
+            r#"{"department":"Sales","level":3,"remote":false}"#
+        );
+    }
+}
@@ -1,3 +1,21 @@
+pub mod custom_attributes;
+pub mod profile;
 pub mod user;
+pub mod user_change;
+pub mod user_event;
+pub mod user_status;
+pub mod verification_token;
 
+pub use custom_attributes::{
+    AttributeDefinition, AttributeError, AttributeSchema, AttributeType, AttributeValue,
+    CustomAttributes,
+};
+pub use profile::{
+    AvatarUrl, AvatarUrlError, Locale, LocaleError, PhoneNumber, PhoneNumberError, TimeZone,
+    TimeZoneError,
+};
 pub use user::{EmailAddress, EmailAddressError, Name, NameError, User};
+pub use user_change::{UserChange, UserChangeSubscription};
+pub use user_event::{UserEvent, UserEventKind, UserEventType};
+pub use user_status::UserStatus;
+pub use verification_token::VerificationToken;
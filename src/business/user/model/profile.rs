@@ -0,0 +1,311 @@
+use std::{fmt::Display, ops::Deref};
+
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use utoipa::ToSchema;
+
+lazy_static! {
+    static ref PHONE_NUMBER_REGEX: regex::Regex = regex::Regex::new(r"^\+[1-9][0-9]{6,14}$").unwrap();
+    static ref LOCALE_REGEX: regex::Regex = regex::Regex::new(
+        r"^[a-z]{2,3}(-[A-Z][a-z]{3})?(-([A-Z]{2}|[0-9]{3}))?(-([a-z0-9]{5,8}|[0-9][a-z0-9]{3}))*$"
+    )
+    .unwrap();
+    static ref TIME_ZONE_REGEX: regex::Regex = regex::Regex::new(
+        r"^(UTC|Etc/(UTC|GMT([+-]([0-9]|1[0-4]))?)|(Africa|America|Antarctica|Arctic|Asia|Atlantic|Australia|Europe|Indian|Pacific)(/[A-Z][A-Za-z_-]+){1,2})$"
+    )
+    .unwrap();
+}
+
+const AVATAR_URL_MAX_LENGTH: usize = 2048;
+
+/// Phone number in E.164 format, e.g. `+261340000000`.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "&str")]
+pub struct PhoneNumber(String);
+
+impl PhoneNumber {
+    /// Spaces, dots, dashes and parentheses are dropped before validation.
+    pub fn new(raw: &str) -> Result<Self, PhoneNumberError> {
+        let compact: String = raw
+            .trim()
+            .chars()
+            .filter(|c| !matches!(c, ' ' | '.' | '-' | '(' | ')'))
+            .collect();
+        if PHONE_NUMBER_REGEX.is_match(&compact) {
+            return Ok(Self(compact));
+        }
+        Err(PhoneNumberError {
+            invalid_phone_number: raw.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&str> for PhoneNumber {
+    type Error = PhoneNumberError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Deref for PhoneNumber {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for PhoneNumber {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("{invalid_phone_number} is not a valid phone number. Phone number must be in E.164 format, e.g. +261340000000.")]
+pub struct PhoneNumberError {
+    pub invalid_phone_number: String,
+}
+
+/// BCP-47 language tag, e.g. `fr-FR` or `zh-Hant-TW`.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "&str")]
+pub struct Locale(String);
+
+impl Locale {
+    /// Accepts `_` as separator and normalizes the case of each subtag.
+    pub fn new(raw: &str) -> Result<Self, LocaleError> {
+        let normalized = raw
+            .trim()
+            .split(['-', '_'])
+            .enumerate()
+            .map(|(position, subtag)| match subtag.len() {
+                _ if position == 0 => subtag.to_lowercase(),
+                2 => subtag.to_uppercase(),
+                4 if subtag.chars().all(|c| c.is_ascii_alphabetic()) => {
+                    subtag[..1].to_uppercase() + &subtag[1..].to_lowercase()
+                }
+                _ => subtag.to_lowercase(),
+            })
+            .collect::<Vec<_>>()
+            .join("-");
+        if LOCALE_REGEX.is_match(&normalized) {
+            return Ok(Self(normalized));
+        }
+        Err(LocaleError {
+            invalid_locale: raw.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&str> for Locale {
+    type Error = LocaleError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Deref for Locale {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error(
+    "{invalid_locale} is not a valid locale. Locale must be a BCP-47 language tag, e.g. fr-FR."
+)]
+pub struct LocaleError {
+    pub invalid_locale: String,
+}
+
+/// IANA time zone name, e.g. `Indian/Antananarivo`.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "&str")]
+pub struct TimeZone(String);
+
+impl TimeZone {
+    /// Checks the shape of the name against the IANA areas, not the zone
+    /// database itself.
+    pub fn new(raw: &str) -> Result<Self, TimeZoneError> {
+        let trimed = raw.trim();
+        if TIME_ZONE_REGEX.is_match(trimed) {
+            return Ok(Self(trimed.to_string()));
+        }
+        Err(TimeZoneError {
+            invalid_time_zone: raw.to_string(),
+        })
+    }
+}
+
+impl TryFrom<&str> for TimeZone {
+    type Error = TimeZoneError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Deref for TimeZone {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for TimeZone {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("{invalid_time_zone} is not a valid time zone. Time zone must be an IANA name, e.g. Europe/Paris.")]
+pub struct TimeZoneError {
+    pub invalid_time_zone: String,
+}
+
+/// Absolute `http` or `https` URL of the user picture.
+#[repr(C)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "&str")]
+pub struct AvatarUrl(String);
+
+impl AvatarUrl {
+    pub fn new(raw: &str) -> Result<Self, AvatarUrlError> {
+        let invalid = || AvatarUrlError {
+            invalid_avatar_url: raw.to_string(),
+        };
+        let url = url::Url::parse(raw.trim()).map_err(|_| invalid())?;
+        if !matches!(url.scheme(), "http" | "https")
+            || url.host_str().is_none()
+            || url.as_str().len() > AVATAR_URL_MAX_LENGTH
+        {
+            return Err(invalid());
+        }
+        Ok(Self(url.to_string()))
+    }
+}
+
+impl TryFrom<&str> for AvatarUrl {
+    type Error = AvatarUrlError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Deref for AvatarUrl {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for AvatarUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("{invalid_avatar_url} is not a valid avatar URL. Avatar URL must be an absolute http or https URL.")]
+pub struct AvatarUrlError {
+    pub invalid_avatar_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{AvatarUrl, Locale, PhoneNumber, TimeZone};
+
+    #[test]
+    fn test_phone_number() {
+        assert_eq!(
+            &*PhoneNumber::new(" +261 34 00-000.00 ").unwrap(),
+            "+261340000000"
+        );
+        assert_eq!(
+            PhoneNumber::try_from("+33 (1) 23 45 67 89")
+                .unwrap()
+                .to_string(),
+            "+33123456789"
+        );
+        for invalid in [
+            "0340000000",
+            "+0340000000",
+            "+12345",
+            "+1234567890123456",
+            "+33 1 23 AB",
+        ] {
+            assert!(PhoneNumber::new(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_locale() {
+        assert_eq!(&*Locale::new("fr").unwrap(), "fr");
+        assert_eq!(&*Locale::new("en_us").unwrap(), "en-US");
+        assert_eq!(&*Locale::new("ZH-hant-tw").unwrap(), "zh-Hant-TW");
+        assert_eq!(&*Locale::new("es-419").unwrap(), "es-419");
+        assert_eq!(&*Locale::new("de-CH-1996").unwrap(), "de-CH-1996");
+        for invalid in ["", "f", "french", "fr-FRA", "fr--FR", "12-FR"] {
+            assert!(Locale::new(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_time_zone() {
+        for valid in [
+            "UTC",
+            "Etc/GMT+3",
+            "Europe/Paris",
+            "Indian/Antananarivo",
+            "America/Argentina/Buenos_Aires",
+            "America/Port-au-Prince",
+        ] {
+            assert_eq!(&*TimeZone::new(valid).unwrap(), valid);
+        }
+        for invalid in [
+            "",
+            "Paris",
+            "europe/paris",
+            "Mars/Olympus",
+            "Etc/GMT+15",
+            "+03:00",
+        ] {
+            assert!(TimeZone::new(invalid).is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_avatar_url() {
+        assert_eq!(
+            &*AvatarUrl::new("https://cdn.example.com/a/john.png").unwrap(),
+            "https://cdn.example.com/a/john.png"
+        );
+        assert_eq!(
+            &*AvatarUrl::new("HTTP://Example.com").unwrap(),
+            "http://example.com/"
+        );
+        for invalid in [
+            "cdn.example.com/john.png",
+            "ftp://example.com/john.png",
+            "data:image/png;base64,AAAA",
+            &format!("https://example.com/{}", "a".repeat(2048)),
+        ] {
+            assert!(AvatarUrl::new(invalid).is_err(), "{invalid}");
+        }
+    }
+}
@@ -5,18 +5,53 @@ use thiserror::Error;
 use utoipa::ToSchema;
 use uuid::Uuid;
 
+use crate::business::{
+    audit::AuditError,
+    auth::TenantId,
+    group::GroupError,
+    mail::MailerError,
+    user::{
+        policy::UserOperation, AttributeError, AvatarUrl, CustomAttributes, Locale, PhoneNumber,
+        TimeZone, UserStatus,
+    },
+};
+
+const ERASED_NAME: &str = "Erased";
+const ERASED_EMAIL_DOMAIN: &str = "erased.invalid";
+const ERASED_STATUS_REASON: &str = "Personal data erased";
+
 lazy_static! {
     static ref EMAIL_REGEX: regex::Regex =
         regex::Regex::new(r"(^[a-zA-Z0-9_.+-]+@[a-zA-Z0-9-]+\.[a-zA-Z0-9-.]+$)").unwrap();
 }
 
 #[repr(C)]
-#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
 pub struct User {
     id: uuid::Uuid,
     firstname: Name,
     lastname: Name,
     email: EmailAddress,
+    verified: bool,
+    #[serde(default)]
+    status: UserStatus,
+    /// Why the account was suspended.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    status_reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    phone: Option<PhoneNumber>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    locale: Option<Locale>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    timezone: Option<TimeZone>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    avatar_url: Option<AvatarUrl>,
+    /// Not serialized, so responses and events do not disclose it; stores
+    /// that need it write it themselves.
+    #[serde(default, skip_serializing)]
+    tenant: TenantId,
+    #[serde(default, skip_serializing_if = "CustomAttributes::is_empty")]
+    attributes: CustomAttributes,
 }
 
 #[derive(Debug, Error)]
@@ -27,6 +62,12 @@ pub enum UserError {
     EmailAlreadyUsed { email: EmailAddress },
     #[error("User with id {id} does not exists")]
     UserNotExists { id: uuid::Uuid },
+    /// Ids are unique across tenants, so this does not tell whether the user
+    /// holding it is visible to the caller.
+    #[error("Id {id} already used")]
+    IdAlreadyUsed { id: uuid::Uuid },
+    #[error("A user cannot be stored without an id")]
+    MissingUserId,
     #[error("Email {email} already used by other user")]
     EmailAlreadyUsedByOther { email: EmailAddress },
     #[error("offset value {offset} cannot be less than 1, please choose higher value")]
@@ -35,6 +76,26 @@ pub enum UserError {
     PerPageValueTooLow { per_page: u16 },
     #[error("per_page value {per_page} is too high, please choose lower value")]
     PerPageValueTooHigh { per_page: u16 },
+    #[error("Operation {operation} forbidden")]
+    Forbidden { operation: UserOperation },
+    #[error("Verification token is invalid or already used")]
+    InvalidVerificationToken,
+    #[error("Verification token has expired")]
+    ExpiredVerificationToken,
+    #[error("Email {email} is already verified")]
+    EmailAlreadyVerified { email: EmailAddress },
+    #[error("User status cannot change from {from} to {to}")]
+    InvalidStatusTransition { from: UserStatus, to: UserStatus },
+    #[error(transparent)]
+    InvalidAttribute(#[from] AttributeError),
+    #[error("Event {event_id} could not be published: {reason}")]
+    EventPublishing { event_id: Uuid, reason: String },
+    #[error(transparent)]
+    MailDelivery(#[from] MailerError),
+    #[error(transparent)]
+    Audit(#[from] AuditError),
+    #[error(transparent)]
+    Group(#[from] GroupError),
     #[error(transparent)]
     Unknown(#[from] anyhow::Error),
 }
@@ -46,6 +107,23 @@ impl User {
             firstname: firstname.clone(),
             lastname: lastname.clone(),
             email: email.clone(),
+            verified: false,
+            status: UserStatus::default(),
+            status_reason: None,
+            phone: None,
+            locale: None,
+            timezone: None,
+            avatar_url: None,
+            tenant: TenantId::default(),
+            attributes: CustomAttributes::default(),
+        }
+    }
+
+    /// Copy of the user under another id.
+    pub fn with_id(&self, id: &uuid::Uuid) -> Self {
+        Self {
+            id: *id,
+            ..self.clone()
         }
     }
 
@@ -64,6 +142,134 @@ impl User {
     pub fn get_email(&self) -> &EmailAddress {
         &self.email
     }
+
+    pub fn is_verified(&self) -> bool {
+        self.verified
+    }
+
+    pub fn set_verified(&mut self, verified: &bool) {
+        self.verified = *verified;
+    }
+
+    pub fn get_status(&self) -> &UserStatus {
+        &self.status
+    }
+
+    pub fn get_status_reason(&self) -> Option<&str> {
+        self.status_reason.as_deref()
+    }
+
+    pub fn set_status(&mut self, status: &UserStatus, reason: Option<&str>) {
+        self.status = *status;
+        self.status_reason = reason.map(str::to_string);
+    }
+
+    fn transition(&mut self, to: UserStatus, reason: Option<&str>) -> Result<(), UserError> {
+        if !self.status.can_transition_to(&to) {
+            return Err(UserError::InvalidStatusTransition {
+                from: self.status,
+                to,
+            });
+        }
+        self.set_status(&to, reason);
+        Ok(())
+    }
+
+    /// Pending or suspended to active; a locked account must be unlocked.
+    pub fn activate(&mut self) -> Result<(), UserError> {
+        match self.status {
+            UserStatus::Locked => Err(UserError::InvalidStatusTransition {
+                from: self.status,
+                to: UserStatus::Active,
+            }),
+            _ => self.transition(UserStatus::Active, None),
+        }
+    }
+
+    pub fn suspend(&mut self, reason: &str) -> Result<(), UserError> {
+        self.transition(UserStatus::Suspended, Some(reason))
+    }
+
+    pub fn lock(&mut self) -> Result<(), UserError> {
+        self.transition(UserStatus::Locked, None)
+    }
+
+    pub fn unlock(&mut self) -> Result<(), UserError> {
+        match self.status {
+            UserStatus::Locked => self.transition(UserStatus::Active, None),
+            _ => Err(UserError::InvalidStatusTransition {
+                from: self.status,
+                to: UserStatus::Active,
+            }),
+        }
+    }
+
+    /// Replaces the personal data by placeholders that cannot be traced back to
+    /// it, keeping the id so that references to the user stay valid, and
+    /// suspends the account.
+    pub fn anonymize(&mut self) {
+        let mut erased = Self::new(
+            &self.id,
+            &Name(ERASED_NAME.to_string()),
+            &Name(ERASED_NAME.to_string()),
+            &EmailAddress(format!(
+                "erased-{}@{}",
+                Uuid::new_v4().simple(),
+                ERASED_EMAIL_DOMAIN
+            )),
+        );
+        erased.set_tenant(&self.tenant);
+        erased.set_status(&UserStatus::Suspended, Some(ERASED_STATUS_REASON));
+        *self = erased;
+    }
+
+    pub fn get_phone(&self) -> Option<&PhoneNumber> {
+        self.phone.as_ref()
+    }
+
+    pub fn set_phone(&mut self, phone: Option<&PhoneNumber>) {
+        self.phone = phone.cloned();
+    }
+
+    pub fn get_locale(&self) -> Option<&Locale> {
+        self.locale.as_ref()
+    }
+
+    pub fn set_locale(&mut self, locale: Option<&Locale>) {
+        self.locale = locale.cloned();
+    }
+
+    pub fn get_timezone(&self) -> Option<&TimeZone> {
+        self.timezone.as_ref()
+    }
+
+    pub fn set_timezone(&mut self, timezone: Option<&TimeZone>) {
+        self.timezone = timezone.cloned();
+    }
+
+    pub fn get_avatar_url(&self) -> Option<&AvatarUrl> {
+        self.avatar_url.as_ref()
+    }
+
+    pub fn set_avatar_url(&mut self, avatar_url: Option<&AvatarUrl>) {
+        self.avatar_url = avatar_url.cloned();
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    pub fn set_tenant(&mut self, tenant: &TenantId) {
+        self.tenant = tenant.clone();
+    }
+
+    pub fn get_attributes(&self) -> &CustomAttributes {
+        &self.attributes
+    }
+
+    pub fn set_attributes(&mut self, attributes: &CustomAttributes) {
+        self.attributes = attributes.clone();
+    }
 }
 
 #[repr(C)]
@@ -172,9 +378,9 @@ mod tests {
 
     use uuid::Uuid;
 
-    use crate::business::user::EmailAddress;
+    use crate::business::user::{EmailAddress, UserStatus};
 
-    use super::{Name, User};
+    use super::{Name, User, UserError};
 
     #[test]
     fn test_name_ok() {
@@ -255,5 +461,58 @@ mod tests {
         assert_eq!(user.get_lastname(), &lastname);
         assert_eq!(user.get_firstname(), &firstname);
         assert_eq!(user.get_email(), &email);
+        assert!(!user.is_verified());
+        assert_eq!(user.get_status(), &UserStatus::Pending);
+    }
+
+    #[test]
+    fn test_user_status_transitions() {
+        let mut user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("test@example.com").unwrap(),
+        );
+        assert!(user.unlock().is_err());
+        user.activate().unwrap();
+        user.suspend("Unpaid invoice").unwrap();
+        assert_eq!(user.get_status(), &UserStatus::Suspended);
+        assert_eq!(user.get_status_reason(), Some("Unpaid invoice"));
+        assert!(matches!(
+            user.lock(),
+            Err(UserError::InvalidStatusTransition {
+                from: UserStatus::Suspended,
+                to: UserStatus::Locked
+            })
+        ));
+        user.activate().unwrap();
+        assert_eq!(user.get_status_reason(), None);
+        user.lock().unwrap();
+        assert!(user.activate().is_err());
+        user.unlock().unwrap();
+        assert_eq!(user.get_status(), &UserStatus::Active);
+    }
+
+    #[test]
+    fn test_user_anonymize() {
+        let user_id = Uuid::new_v4();
+        let email = EmailAddress::new("john@example.com").unwrap();
+        let mut user = User::new(
+            &user_id,
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &email,
+        );
+        user.set_verified(&true);
+        let mut other = user.clone();
+        user.anonymize();
+        other.anonymize();
+        assert_eq!(user.get_id(), &user_id);
+        assert_eq!(&**user.get_firstname(), "Erased");
+        assert!(!user.get_email().contains("john"));
+        assert!(EmailAddress::new(user.get_email()).is_ok());
+        assert_ne!(user.get_email(), other.get_email());
+        assert!(!user.is_verified());
+        assert!(!user.get_status().can_sign_in());
     }
 }
@@ -0,0 +1,108 @@
+use std::collections::VecDeque;
+
+use tokio::sync::broadcast;
+
+use crate::business::{
+    auth::TenantId,
+    user::{dtos::UserFindRequestFilter, User, UserEvent, UserEventKind},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UserChange {
+    sequence: u64,
+    event: UserEvent,
+}
+
+impl UserChange {
+    pub fn new(sequence: &u64, event: &UserEvent) -> Self {
+        Self {
+            sequence: *sequence,
+            event: event.clone(),
+        }
+    }
+
+    pub fn get_sequence(&self) -> u64 {
+        self.sequence
+    }
+
+    pub fn get_event(&self) -> &UserEvent {
+        &self.event
+    }
+
+    pub fn matches(&self, filter: &UserFindRequestFilter) -> bool {
+        match self.event.get_kind() {
+            UserEventKind::UserCreated { after } => filter.matches(after),
+            UserEventKind::UserUpdated { before, after } => {
+                filter.matches(before) || filter.matches(after)
+            }
+            UserEventKind::UserDeleted { before } => filter.matches(before),
+            UserEventKind::UserErased { after } => filter.matches(after),
+        }
+    }
+
+    pub fn redact(&mut self, erased: &User) {
+        self.event.redact(erased);
+    }
+}
+
+#[derive(Debug)]
+pub struct UserChangeSubscription {
+    backlog: VecDeque<UserChange>,
+    receiver: broadcast::Receiver<UserChange>,
+    filter: UserFindRequestFilter,
+    tenant: Option<TenantId>,
+    last_sequence: Option<u64>,
+}
+
+impl UserChangeSubscription {
+    pub fn new(
+        backlog: Vec<UserChange>,
+        receiver: broadcast::Receiver<UserChange>,
+        last_sequence: Option<u64>,
+    ) -> Self {
+        Self {
+            backlog: backlog.into(),
+            receiver,
+            filter: UserFindRequestFilter::default(),
+            tenant: None,
+            last_sequence,
+        }
+    }
+
+    pub fn with_filter(mut self, filter: &UserFindRequestFilter) -> Self {
+        self.filter = filter.clone();
+        self
+    }
+
+    /// Only yields changes of users of `tenant`.
+    pub fn with_tenant(mut self, tenant: &TenantId) -> Self {
+        self.tenant = Some(tenant.clone());
+        self
+    }
+
+    /// Returns `None` once the subscriber fell behind the live feed or the feed
+    /// closed; clients resume from the last received sequence.
+    pub async fn next(&mut self) -> Option<UserChange> {
+        loop {
+            let change = match self.backlog.pop_front() {
+                Some(change) => change,
+                None => self.receiver.recv().await.ok()?,
+            };
+            if self
+                .last_sequence
+                .is_some_and(|last| change.get_sequence() <= last)
+            {
+                continue;
+            }
+            self.last_sequence = Some(change.get_sequence());
+            if self
+                .tenant
+                .as_ref()
+                .map_or(true, |tenant| change.get_event().get_tenant().eq(tenant))
+                && change.matches(&self.filter)
+            {
+                return Some(change);
+            }
+        }
+    }
+}
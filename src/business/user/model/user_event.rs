@@ -0,0 +1,237 @@
+use std::{
+    fmt::Display,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::business::{audit::AuditRecord, auth::TenantId};
+
+use super::User;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum UserEventType {
+    UserCreated,
+    UserUpdated,
+    UserDeleted,
+    UserErased,
+}
+
+impl Display for UserEventType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserEventType::UserCreated => f.write_str("UserCreated"),
+            UserEventType::UserUpdated => f.write_str("UserUpdated"),
+            UserEventType::UserDeleted => f.write_str("UserDeleted"),
+            UserEventType::UserErased => f.write_str("UserErased"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum UserEventKind {
+    UserCreated {
+        after: Box<User>,
+    },
+    UserUpdated {
+        before: Box<User>,
+        after: Box<User>,
+    },
+    UserDeleted {
+        before: Box<User>,
+    },
+    /// Carries the anonymized user only, so the erased values do not leave
+    /// the store through the event.
+    UserErased {
+        after: Box<User>,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UserEvent {
+    id: Uuid,
+    user_id: Uuid,
+    occurred_at: u64,
+    #[serde(flatten)]
+    kind: UserEventKind,
+    /// Recorded in the same commit as the change and appended to the audit
+    /// trail when the event is relayed; never published.
+    #[serde(skip)]
+    audit: Option<AuditRecord>,
+}
+
+impl UserEvent {
+    pub fn new(kind: UserEventKind) -> Self {
+        let user_id = match &kind {
+            UserEventKind::UserCreated { after } => *after.get_id(),
+            UserEventKind::UserUpdated { after, .. } => *after.get_id(),
+            UserEventKind::UserDeleted { before } => *before.get_id(),
+            UserEventKind::UserErased { after } => *after.get_id(),
+        };
+        let occurred_at = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |elapsed| elapsed.as_millis() as u64);
+        Self {
+            id: Uuid::new_v4(),
+            user_id,
+            occurred_at,
+            kind,
+            audit: None,
+        }
+    }
+
+    pub fn created(after: &User) -> Self {
+        Self::new(UserEventKind::UserCreated {
+            after: Box::new(after.clone()),
+        })
+    }
+
+    pub fn updated(before: &User, after: &User) -> Self {
+        Self::new(UserEventKind::UserUpdated {
+            before: Box::new(before.clone()),
+            after: Box::new(after.clone()),
+        })
+    }
+
+    pub fn deleted(before: &User) -> Self {
+        Self::new(UserEventKind::UserDeleted {
+            before: Box::new(before.clone()),
+        })
+    }
+
+    pub fn erased(after: &User) -> Self {
+        Self::new(UserEventKind::UserErased {
+            after: Box::new(after.clone()),
+        })
+    }
+
+    /// Keeps the identity of an event read back from storage.
+    pub fn with_id(mut self, id: &Uuid) -> Self {
+        self.id = *id;
+        self
+    }
+
+    pub fn with_occurred_at(mut self, occurred_at: u64) -> Self {
+        self.occurred_at = occurred_at;
+        self
+    }
+
+    pub fn with_audit(mut self, audit: &AuditRecord) -> Self {
+        self.audit = Some(audit.clone());
+        self
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn get_user_id(&self) -> &Uuid {
+        &self.user_id
+    }
+
+    pub fn get_occurred_at(&self) -> u64 {
+        self.occurred_at
+    }
+
+    pub fn get_kind(&self) -> &UserEventKind {
+        &self.kind
+    }
+
+    pub fn get_audit(&self) -> Option<&AuditRecord> {
+        self.audit.as_ref()
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        match &self.kind {
+            UserEventKind::UserCreated { after: user }
+            | UserEventKind::UserUpdated { after: user, .. }
+            | UserEventKind::UserDeleted { before: user }
+            | UserEventKind::UserErased { after: user } => user.get_tenant(),
+        }
+    }
+
+    /// The anonymized user of an erasure, whose earlier events must be redacted.
+    pub fn get_erased_user(&self) -> Option<&User> {
+        match &self.kind {
+            UserEventKind::UserErased { after } => Some(after),
+            _ => None,
+        }
+    }
+
+    /// Replaces every snapshot of the user held by the event by its erased
+    /// version, and drops the values of its audit record. Events of other
+    /// users are left untouched.
+    pub fn redact(&mut self, erased: &User) {
+        if self.user_id.ne(erased.get_id()) {
+            return;
+        }
+        if let Some(audit) = self.audit.as_mut() {
+            audit.redact();
+        }
+        match &mut self.kind {
+            UserEventKind::UserCreated { after } | UserEventKind::UserErased { after } => {
+                **after = erased.clone()
+            }
+            UserEventKind::UserUpdated { before, after } => {
+                **before = erased.clone();
+                **after = erased.clone();
+            }
+            UserEventKind::UserDeleted { before } => **before = erased.clone(),
+        }
+    }
+
+    /// Events carry full snapshots, so the resulting state does not depend on
+    /// the previous one.
+    pub fn apply(&self) -> Option<User> {
+        match &self.kind {
+            UserEventKind::UserCreated { after }
+            | UserEventKind::UserUpdated { after, .. }
+            | UserEventKind::UserErased { after } => Some(*after.clone()),
+            UserEventKind::UserDeleted { .. } => None,
+        }
+    }
+
+    pub fn get_event_type(&self) -> UserEventType {
+        match self.kind {
+            UserEventKind::UserCreated { .. } => UserEventType::UserCreated,
+            UserEventKind::UserUpdated { .. } => UserEventType::UserUpdated,
+            UserEventKind::UserDeleted { .. } => UserEventType::UserDeleted,
+            UserEventKind::UserErased { .. } => UserEventType::UserErased,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::user::{EmailAddress, Name, User};
+
+    use super::{UserEvent, UserEventType};
+
+    #[test]
+    fn test_updated_event_serialization() {
+        let before = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let after = User::new(
+            before.get_id(),
+            &Name::new("Jane").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("jane@example.com").unwrap(),
+        );
+        let event = UserEvent::updated(&before, &after);
+        assert_eq!(event.get_user_id(), before.get_id());
+        assert_eq!(event.get_event_type(), UserEventType::UserUpdated);
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "UserUpdated");
+        assert_eq!(json["before"]["firstname"], "John");
+        assert_eq!(json["after"]["firstname"], "Jane");
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::Display;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+/// Lifecycle of an account. New accounts are pending until activated, active
+/// or pending ones can be suspended or locked, a suspended one is activated
+/// again and a locked one unlocked (or suspended).
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum UserStatus {
+    #[default]
+    Pending,
+    Active,
+    Suspended,
+    Locked,
+}
+
+impl UserStatus {
+    pub fn can_transition_to(&self, target: &UserStatus) -> bool {
+        matches!(
+            (self, target),
+            (
+                UserStatus::Pending | UserStatus::Suspended,
+                UserStatus::Active
+            ) | (UserStatus::Locked, UserStatus::Active)
+                | (
+                    UserStatus::Pending | UserStatus::Active | UserStatus::Locked,
+                    UserStatus::Suspended
+                )
+                | (UserStatus::Pending | UserStatus::Active, UserStatus::Locked)
+        )
+    }
+
+    /// Suspended and locked accounts cannot sign in.
+    pub fn can_sign_in(&self) -> bool {
+        matches!(self, UserStatus::Pending | UserStatus::Active)
+    }
+}
+
+impl Display for UserStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UserStatus::Pending => "pending",
+            UserStatus::Active => "active",
+            UserStatus::Suspended => "suspended",
+            UserStatus::Locked => "locked",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::UserStatus;
+
+    #[test]
+    fn test_transitions() {
+        use UserStatus::*;
+        let allowed = [
+            (Pending, Active),
+            (Pending, Suspended),
+            (Pending, Locked),
+            (Active, Suspended),
+            (Active, Locked),
+            (Suspended, Active),
+            (Locked, Active),
+            (Locked, Suspended),
+        ];
+        for from in [Pending, Active, Suspended, Locked] {
+            for to in [Pending, Active, Suspended, Locked] {
+                assert_eq!(
+                    from.can_transition_to(&to),
+                    allowed.contains(&(from, to)),
+                    "{from} -> {to}"
+                );
+            }
+        }
+    }
+}
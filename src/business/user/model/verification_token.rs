@@ -0,0 +1,115 @@
+use std::time::{Duration, SystemTime};
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    user::{EmailAddress, User},
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerificationToken {
+    token_hash: String,
+    user_id: Uuid,
+    email: EmailAddress,
+    tenant: TenantId,
+    expires_at: SystemTime,
+}
+
+impl VerificationToken {
+    pub fn new(
+        token_hash: &str,
+        user_id: &Uuid,
+        email: &EmailAddress,
+        expires_at: &SystemTime,
+    ) -> Self {
+        Self {
+            token_hash: token_hash.to_string(),
+            user_id: *user_id,
+            email: email.clone(),
+            tenant: TenantId::default(),
+            expires_at: *expires_at,
+        }
+    }
+
+    pub fn with_tenant(mut self, tenant: &TenantId) -> Self {
+        self.tenant = tenant.clone();
+        self
+    }
+
+    pub fn issue(user: &User, ttl: &Duration) -> (String, Self) {
+        let mut raw = [0u8; 32];
+        rand::rngs::OsRng.fill_bytes(&mut raw);
+        let token = hex::encode(raw);
+        let verification_token = Self::new(
+            &Self::hash(&token),
+            user.get_id(),
+            user.get_email(),
+            &(SystemTime::now() + *ttl),
+        )
+        .with_tenant(user.get_tenant());
+        (token, verification_token)
+    }
+
+    pub fn hash(token: &str) -> String {
+        hex::encode(Sha256::digest(token.trim().as_bytes()))
+    }
+
+    pub fn get_token_hash(&self) -> &str {
+        &self.token_hash
+    }
+
+    pub fn get_user_id(&self) -> &Uuid {
+        &self.user_id
+    }
+
+    pub fn get_email(&self) -> &EmailAddress {
+        &self.email
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    pub fn get_expires_at(&self) -> &SystemTime {
+        &self.expires_at
+    }
+
+    pub fn is_expired(&self, now: &SystemTime) -> bool {
+        &self.expires_at <= now
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use uuid::Uuid;
+
+    use crate::business::user::{EmailAddress, Name, User};
+
+    use super::VerificationToken;
+
+    #[test]
+    fn test_issue_verification_token() {
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let (token, verification_token) = VerificationToken::issue(&user, &Duration::from_secs(60));
+        assert_eq!(token.len(), 64);
+        assert_ne!(verification_token.get_token_hash(), token);
+        assert_eq!(
+            verification_token.get_token_hash(),
+            VerificationToken::hash(&token)
+        );
+        assert_eq!(verification_token.get_user_id(), user.get_id());
+        assert_eq!(verification_token.get_tenant(), user.get_tenant());
+        assert!(!verification_token.is_expired(&SystemTime::now()));
+        assert!(verification_token.is_expired(&(SystemTime::now() + Duration::from_secs(61))));
+    }
+}
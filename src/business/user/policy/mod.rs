@@ -0,0 +1,3 @@
+pub mod user_policy;
+
+pub use user_policy::{UserOperation, UserPolicy};
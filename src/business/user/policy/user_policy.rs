@@ -0,0 +1,188 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+};
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::{Principal, Role},
+    user::model::user::UserError,
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+pub enum UserOperation {
+    Create,
+    Update,
+    Delete,
+    Read,
+    List,
+    /// Activate, suspend, lock or unlock an account.
+    ChangeStatus,
+    /// Anonymise the personal data of a user.
+    Erase,
+    /// Grant roles to the password credential of a user.
+    AssignRoles,
+}
+
+impl Display for UserOperation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            UserOperation::Create => "create",
+            UserOperation::Update => "update",
+            UserOperation::Delete => "delete",
+            UserOperation::Read => "read",
+            UserOperation::List => "list",
+            UserOperation::ChangeStatus => "change_status",
+            UserOperation::Erase => "erase",
+            UserOperation::AssignRoles => "assign_roles",
+        })
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct UserPolicy {
+    roles: HashMap<UserOperation, HashSet<Role>>,
+    self_operations: HashSet<UserOperation>,
+}
+
+impl UserPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn allow(mut self, operation: UserOperation, role: &Role) -> Self {
+        self.roles
+            .entry(operation)
+            .or_default()
+            .insert(role.clone());
+        self
+    }
+
+    pub fn allow_self(mut self, operation: UserOperation) -> Self {
+        self.self_operations.insert(operation);
+        self
+    }
+
+    pub fn authorize(
+        &self,
+        principal: &Principal,
+        operation: UserOperation,
+        target_id: Option<&Uuid>,
+    ) -> Result<(), UserError> {
+        let allowed_by_role = self.roles.get(&operation).is_some_and(|roles| {
+            principal
+                .get_roles()
+                .iter()
+                .any(|role| roles.contains(role))
+        });
+        let allowed_by_self = self.self_operations.contains(&operation)
+            && target_id.is_some()
+            && principal.get_user_id() == target_id;
+        if allowed_by_role || allowed_by_self {
+            Ok(())
+        } else {
+            Err(UserError::Forbidden { operation })
+        }
+    }
+
+    pub fn default_rules() -> Self {
+        let admin = Role::new("admin");
+        let reader = Role::new("reader");
+        Self::new()
+            .allow(UserOperation::Create, &admin)
+            .allow(UserOperation::Update, &admin)
+            .allow(UserOperation::Delete, &admin)
+            .allow(UserOperation::Read, &admin)
+            .allow(UserOperation::List, &admin)
+            .allow(UserOperation::ChangeStatus, &admin)
+            .allow(UserOperation::Erase, &admin)
+            .allow(UserOperation::AssignRoles, &admin)
+            .allow(UserOperation::Read, &reader)
+            .allow(UserOperation::List, &reader)
+            .allow_self(UserOperation::Read)
+            .allow_self(UserOperation::Update)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::{
+        auth::{AuthenticationMethod, Principal, Role},
+        user::model::user::UserError,
+    };
+
+    use super::{UserOperation, UserPolicy};
+
+    fn principal(user_id: Option<&Uuid>, roles: &[&str]) -> Principal {
+        let roles: Vec<Role> = roles.iter().map(|r| Role::new(r)).collect();
+        Principal::new("john", user_id, &roles, AuthenticationMethod::Jwt)
+    }
+
+    #[test]
+    fn test_admin_allowed_everything() {
+        let policy = UserPolicy::default_rules();
+        let admin = principal(None, &["admin"]);
+        for operation in [
+            UserOperation::Create,
+            UserOperation::Update,
+            UserOperation::Delete,
+            UserOperation::Read,
+            UserOperation::List,
+            UserOperation::ChangeStatus,
+            UserOperation::Erase,
+            UserOperation::AssignRoles,
+        ] {
+            assert!(policy
+                .authorize(&admin, operation, Some(&Uuid::new_v4()))
+                .is_ok());
+        }
+    }
+
+    #[test]
+    fn test_reader_cannot_write() {
+        let policy = UserPolicy::default_rules();
+        let reader = principal(None, &["reader"]);
+        assert!(policy.authorize(&reader, UserOperation::List, None).is_ok());
+        let result = policy.authorize(&reader, UserOperation::Delete, Some(&Uuid::new_v4()));
+        assert!(matches!(
+            result,
+            Err(UserError::Forbidden {
+                operation: UserOperation::Delete
+            })
+        ));
+    }
+
+    #[test]
+    fn test_self_rule() {
+        let policy = UserPolicy::default_rules();
+        let user_id = Uuid::new_v4();
+        let user = principal(Some(&user_id), &[]);
+        assert!(policy
+            .authorize(&user, UserOperation::Read, Some(&user_id))
+            .is_ok());
+        assert!(policy
+            .authorize(&user, UserOperation::Update, Some(&user_id))
+            .is_ok());
+        assert!(policy
+            .authorize(&user, UserOperation::Delete, Some(&user_id))
+            .is_err());
+        assert!(policy
+            .authorize(&user, UserOperation::Read, Some(&Uuid::new_v4()))
+            .is_err());
+        assert!(policy.authorize(&user, UserOperation::List, None).is_err());
+    }
+
+    #[test]
+    fn test_empty_policy_denies() {
+        let policy = UserPolicy::new();
+        let admin = principal(None, &["admin"]);
+        assert!(policy
+            .authorize(&admin, UserOperation::Create, None)
+            .is_err());
+    }
+}
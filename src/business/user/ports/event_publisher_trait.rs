@@ -0,0 +1,7 @@
+use std::future::Future;
+
+use crate::business::user::model::{user::UserError, UserEvent};
+
+pub trait EventPublisherTrait: Sync + Send + Clone + 'static {
+    fn publish(&self, event: &UserEvent) -> impl Future<Output = Result<(), UserError>> + Send;
+}
@@ -1,5 +1,11 @@
+pub mod event_publisher_trait;
+pub mod user_change_feed_trait;
 pub mod user_repository_trait;
 pub mod user_service_trait;
+pub mod verification_token_store_trait;
 
+pub use event_publisher_trait::EventPublisherTrait;
+pub use user_change_feed_trait::UserChangeFeedTrait;
 pub use user_repository_trait::UserRepositoryTrait;
 pub use user_service_trait::UserServiceTrait;
+pub use verification_token_store_trait::VerificationTokenStoreTrait;
@@ -0,0 +1,10 @@
+use std::future::Future;
+
+use crate::business::user::{model::user::UserError, UserChangeSubscription};
+
+pub trait UserChangeFeedTrait: Sync + Send + Clone + 'static {
+    fn subscribe_changes(
+        &self,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send;
+}
@@ -1,2 +1,15 @@
-use crate::outbound::repository_trait::RepositoryTrait;
-pub trait UserRepositoryTrait: RepositoryTrait + Sync + Send + 'static {}
+use crate::{
+    business::{audit::AuditContext, auth::TenantId, user::UserChangeFeedTrait},
+    outbound::repository_trait::{OutboxTrait, RepositoryTrait, UnitOfWorkTrait},
+};
+pub trait UserRepositoryTrait:
+    RepositoryTrait + OutboxTrait + UnitOfWorkTrait + UserChangeFeedTrait + Sync + Send + 'static
+{
+    /// Handle over the users of `tenant` only; handles built by the adapter
+    /// constructors see the default tenant.
+    fn for_tenant(&self, tenant: &TenantId) -> Self;
+
+    /// Handle whose transactions record an audit record under `audit` with
+    /// every event, in the same commit as the change.
+    fn audited(&self, audit: &AuditContext) -> Self;
+}
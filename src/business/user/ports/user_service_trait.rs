@@ -2,34 +2,115 @@ use std::future::Future;
 
 use uuid::Uuid;
 
-use crate::business::user::{
-    dtos::{UserFindRequest, UserFindResponse},
-    model::user::UserError,
-    User, UserAddRequest, UserDeleteRequest, UserUpdateRequest,
+use crate::business::{
+    audit::AuditEntry,
+    auth::RequestContext,
+    user::{
+        dtos::{UserFindRequest, UserFindRequestFilter, UserFindResponse},
+        model::user::UserError,
+        AttributeSchema, User, UserAddRequest, UserChangeSubscription, UserDataExport,
+        UserDeleteRequest, UserSuspendRequest, UserUpdateRequest, UserVerifyEmailRequest,
+    },
 };
 
 pub trait UserServiceTrait: Sync + Send + Clone + 'static {
     fn create_user(
         &self,
+        context: &RequestContext,
         req: &UserAddRequest,
     ) -> impl Future<Output = Result<User, UserError>> + Send;
 
     fn update_user(
         &self,
+        context: &RequestContext,
         user_id: &Uuid,
         req: &UserUpdateRequest,
     ) -> impl Future<Output = Result<User, UserError>> + Send;
 
-    fn find_one_user(&self, user_id: &Uuid)
-        -> impl Future<Output = Result<User, UserError>> + Send;
+    fn find_one_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
 
     fn find_user(
         &self,
+        context: &RequestContext,
         req: &UserFindRequest,
     ) -> impl Future<Output = Result<UserFindResponse, UserError>> + Send;
 
+    fn find_user_history(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<AuditEntry>, UserError>> + Send;
+
+    fn watch_users(
+        &self,
+        context: &RequestContext,
+        filter: &UserFindRequestFilter,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send;
+
     fn delete_user(
         &self,
+        context: &RequestContext,
         req: &UserDeleteRequest,
     ) -> impl Future<Output = Result<(), UserError>> + Send;
+
+    fn send_verification(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), UserError>> + Send;
+
+    /// Acts on behalf of the user the token was issued to.
+    fn verify_email(
+        &self,
+        request_id: &str,
+        req: &UserVerifyEmailRequest,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
+
+    fn activate_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
+
+    fn suspend_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &UserSuspendRequest,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
+
+    fn lock_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
+
+    fn unlock_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
+
+    /// Profile, audit entries and group memberships of the user.
+    fn export_user_data(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<UserDataExport, UserError>> + Send;
+
+    /// Anonymises the personal data of the user while keeping its id, and
+    /// redacts it from its recorded events and audit entries.
+    fn erase_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send;
+
+    /// Custom attributes accepted on create and update.
+    fn get_attribute_schema(&self) -> &AttributeSchema;
 }
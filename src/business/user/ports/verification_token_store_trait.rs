@@ -0,0 +1,20 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::user::{model::user::UserError, VerificationToken};
+
+pub trait VerificationTokenStoreTrait: Sync + Send + Clone + 'static {
+    fn store(
+        &self,
+        token: &VerificationToken,
+    ) -> impl Future<Output = Result<(), UserError>> + Send;
+
+    fn take(
+        &self,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<VerificationToken>, UserError>> + Send;
+
+    fn revoke_for_user(&self, user_id: &Uuid)
+        -> impl Future<Output = Result<(), UserError>> + Send;
+}
@@ -0,0 +1,36 @@
+use std::future::Future;
+
+use crate::business::user::{model::user::UserError, EventPublisherTrait, UserEvent};
+
+#[derive(Debug, Clone)]
+pub struct EventPublisherChain<A, B>
+where
+    A: EventPublisherTrait,
+    B: EventPublisherTrait,
+{
+    first: A,
+    second: B,
+}
+
+impl<A, B> EventPublisherChain<A, B>
+where
+    A: EventPublisherTrait,
+    B: EventPublisherTrait,
+{
+    pub fn new(first: A, second: B) -> Self {
+        Self { first, second }
+    }
+}
+
+impl<A, B> EventPublisherTrait for EventPublisherChain<A, B>
+where
+    A: EventPublisherTrait,
+    B: EventPublisherTrait,
+{
+    fn publish(&self, event: &UserEvent) -> impl Future<Output = Result<(), UserError>> + Send {
+        Box::pin(async move {
+            self.first.publish(event).await?;
+            self.second.publish(event).await
+        })
+    }
+}
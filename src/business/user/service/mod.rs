@@ -1 +1,2 @@
+pub mod event_publisher_chain;
 pub mod user_service;
@@ -1,16 +1,49 @@
-use std::future::Future;
+use std::{
+    future::Future,
+    sync::Arc,
+    time::{Duration, SystemTime},
+};
 
+use tokio::sync::{Mutex, Notify};
+use tracing::{info_span, warn, Instrument, Span};
 use uuid::Uuid;
 
-use crate::business::user::{
-    dtos::{UserFindRequest, UserFindResponse},
-    model::user::UserError,
-    User, UserAddRequest, UserDeleteRequest, UserRepositoryTrait, UserServiceTrait,
-    UserUpdateRequest,
+use crate::{
+    business::{
+        audit::{AuditContext, AuditEntry, AuditTrailTrait},
+        auth::{AuthenticationMethod, Principal, RequestContext, TenantId},
+        group::{GroupError, GroupRepositoryTrait},
+        id::IdGeneratorTrait,
+        mail::{EmailMessage, MailerTrait},
+        user::{
+            dtos::{UserFindRequest, UserFindRequestFilter, UserFindResponse},
+            model::user::UserError,
+            AttributeSchema, EventPublisherTrait, User, UserAddRequest, UserChangeSubscription,
+            UserDataExport, UserDeleteRequest, UserEvent, UserEventType, UserOperation, UserPolicy,
+            UserRepositoryTrait, UserServiceTrait, UserStatus, UserSuspendRequest,
+            UserUpdateRequest, UserVerifyEmailRequest, VerificationToken,
+            VerificationTokenStoreTrait,
+        },
+    },
+    outbound::repository_trait::{FindOptionTrait, TransactionTrait},
 };
 
+const RELAY_BATCH_SIZE: usize = 100;
+
+/// Span around one service call, tagged with who made it and on behalf of
+/// which request.
+fn operation_span(operation: &'static str, context: &RequestContext) -> Span {
+    info_span!(
+        "user_service",
+        operation,
+        tenant = %context.get_tenant(),
+        principal = context.get_principal().get_subject(),
+        request_id = context.get_request_id(),
+    )
+}
+
 #[derive(Debug, Clone)]
-pub struct UserService<R>
+pub struct UserService<R, M, V, P, A, G, I>
 where
     R: UserRepositoryTrait<
         Id = Uuid,
@@ -18,12 +51,31 @@ where
         Error = UserError,
         FindOptions = UserFindRequest,
         FindResult = UserFindResponse,
+        Event = UserEvent,
     >,
+    M: MailerTrait,
+    V: VerificationTokenStoreTrait,
+    P: EventPublisherTrait,
+    A: AuditTrailTrait,
+    G: GroupRepositoryTrait,
+    I: IdGeneratorTrait,
 {
     user_repository: R,
+    mailer: M,
+    verification_token_store: V,
+    event_publisher: P,
+    audit_trail: A,
+    group_repository: G,
+    id_generator: I,
+    relay_lock: Arc<Mutex<()>>,
+    relay_trigger: Arc<Notify>,
+    policy: UserPolicy,
+    verification_ttl: Duration,
+    verification_url: Option<String>,
+    attribute_schema: Arc<AttributeSchema>,
 }
 
-impl<R> UserService<R>
+impl<R, M, V, P, A, G, I> UserService<R, M, V, P, A, G, I>
 where
     R: UserRepositoryTrait<
         Id = Uuid,
@@ -31,14 +83,227 @@ where
         Error = UserError,
         FindOptions = UserFindRequest,
         FindResult = UserFindResponse,
+        Event = UserEvent,
     >,
+    M: MailerTrait,
+    V: VerificationTokenStoreTrait,
+    P: EventPublisherTrait,
+    A: AuditTrailTrait,
+    G: GroupRepositoryTrait,
+    I: IdGeneratorTrait,
 {
-    pub fn new(user_repository: R) -> Self {
-        Self { user_repository }
+    pub fn new(
+        user_repository: R,
+        mailer: M,
+        verification_token_store: V,
+        event_publisher: P,
+        audit_trail: A,
+        group_repository: G,
+        id_generator: I,
+    ) -> Self {
+        Self {
+            user_repository,
+            mailer,
+            verification_token_store,
+            event_publisher,
+            audit_trail,
+            group_repository,
+            id_generator,
+            relay_lock: Arc::new(Mutex::new(())),
+            relay_trigger: Arc::new(Notify::new()),
+            policy: UserPolicy::default_rules(),
+            verification_ttl: Duration::from_secs(24 * 60 * 60),
+            verification_url: None,
+            attribute_schema: Arc::new(AttributeSchema::default()),
+        }
+    }
+
+    pub fn with_policy(mut self, policy: UserPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+
+    pub fn with_verification_ttl(mut self, verification_ttl: Duration) -> Self {
+        self.verification_ttl = verification_ttl;
+        self
+    }
+
+    pub fn with_verification_url(mut self, verification_url: &str) -> Self {
+        self.verification_url = Some(verification_url.to_string());
+        self
+    }
+
+    pub fn with_attribute_schema(mut self, attribute_schema: AttributeSchema) -> Self {
+        self.attribute_schema = Arc::new(attribute_schema);
+        self
+    }
+
+    /// Resolves once a mutation recorded events, so the relaying task can
+    /// publish them without the request waiting for it.
+    pub async fn relay_requested(&self) {
+        self.relay_trigger.notified().await
+    }
+
+    fn request_relay(&self) {
+        self.relay_trigger.notify_one();
+    }
+
+    pub async fn relay_events(&self) -> Result<usize, UserError> {
+        let _relay = self.relay_lock.lock().await;
+        let mut published = 0;
+        loop {
+            let pending = self
+                .user_repository
+                .pending_events(RELAY_BATCH_SIZE)
+                .await?;
+            if pending.is_empty() {
+                return Ok(published);
+            }
+            for event in pending.iter() {
+                self.apply_effects(event).await?;
+                self.event_publisher.publish(event).await?;
+                self.user_repository.acknowledge_event(event).await?;
+                published += 1;
+            }
+        }
+    }
+
+    /// Repository handle scoped to the tenant of the request.
+    fn users(&self, tenant: &TenantId) -> R {
+        self.user_repository.for_tenant(tenant)
+    }
+
+    /// Group repository handle scoped to the tenant of the request.
+    fn groups(&self, tenant: &TenantId) -> G {
+        self.group_repository.for_tenant(tenant)
+    }
+
+    /// Replaces a `group` filter by the ids of its members; groups of other
+    /// tenants match nobody.
+    async fn resolve_group(
+        &self,
+        tenant: &TenantId,
+        filter: &UserFindRequestFilter,
+    ) -> Result<UserFindRequestFilter, UserError> {
+        let mut filter = filter.clone();
+        if let Some(group_id) = filter.group {
+            let members = match self.groups(tenant).find_members(&group_id).await {
+                Err(GroupError::GroupNotExists { .. }) => Vec::new(),
+                members => members?,
+            };
+            filter.members = Some(members.into_iter().collect());
+        }
+        Ok(filter)
+    }
+
+    /// Audit entries of the user recorded in the tenant of the request.
+    async fn tenant_history(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> Result<Vec<AuditEntry>, UserError> {
+        let mut history = self.audit_trail.find_by_user(user_id).await?;
+        history.retain(|entry| entry.get_tenant() == context.get_tenant());
+        Ok(history)
+    }
+
+    /// Repository handle scoped to the tenant of the request, whose
+    /// transactions record the audit entry of their changes as `operation`.
+    fn audited_users(&self, context: &RequestContext, operation: UserOperation) -> R {
+        self.users(context.get_tenant())
+            .audited(&AuditContext::new(context, operation))
+    }
+
+    /// Appends the audit record the event was committed with and cleans up
+    /// after a deleted or erased user. A failed relay repeats all of it, so
+    /// every step must tolerate running twice.
+    async fn apply_effects(&self, event: &UserEvent) -> Result<(), UserError> {
+        if let Some(audit) = event.get_audit() {
+            self.audit_trail.append(audit).await?;
+        }
+        let user_id = event.get_user_id();
+        match event.get_event_type() {
+            UserEventType::UserDeleted => {
+                self.groups(event.get_tenant()).remove_user(user_id).await?;
+                self.verification_token_store
+                    .revoke_for_user(user_id)
+                    .await?;
+            }
+            UserEventType::UserErased => {
+                self.audit_trail.redact_user(user_id).await?;
+                self.verification_token_store
+                    .revoke_for_user(user_id)
+                    .await?;
+            }
+            UserEventType::UserCreated | UserEventType::UserUpdated => {}
+        }
+        Ok(())
+    }
+
+    /// Applies a status transition to the user and audits it.
+    async fn change_status(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        transition: impl FnOnce(&mut User) -> Result<(), UserError> + Send,
+    ) -> Result<User, UserError> {
+        self.policy.authorize(
+            context.get_principal(),
+            UserOperation::ChangeStatus,
+            Some(user_id),
+        )?;
+        let mut transaction = self
+            .audited_users(context, UserOperation::ChangeStatus)
+            .begin()
+            .await?;
+        let mut user = transaction.find_by_id(user_id).await?;
+        transition(&mut user)?;
+        let updated = transaction.update(user_id, &user).await?;
+        transaction.commit().await?;
+        self.request_relay();
+        Ok(updated)
+    }
+
+    /// Once the user is committed a failed mail must not fail the request,
+    /// since a retry would conflict with it; `send_verification` resends it.
+    async fn issue_verification_after_commit(&self, user: &User) {
+        if let Err(error) = self.issue_verification(user).await {
+            warn!(%error, user_id = %user.get_id(), "verification mail was not sent");
+        }
+    }
+
+    async fn issue_verification(&self, user: &User) -> Result<(), UserError> {
+        self.verification_token_store
+            .revoke_for_user(user.get_id())
+            .await?;
+        let (token, verification_token) = VerificationToken::issue(user, &self.verification_ttl);
+        self.verification_token_store
+            .store(&verification_token)
+            .await?;
+        let link = self
+            .verification_url
+            .as_ref()
+            .map(|url| format!("\n\n{}?token={}", url, token))
+            .unwrap_or_default();
+        let body = format!(
+            "Hello {},\n\nUse the following token to verify your email address: {}{}\n\nIt expires in {} minutes.",
+            user.get_firstname(),
+            token,
+            link,
+            self.verification_ttl.as_secs() / 60
+        );
+        self.mailer
+            .send(&EmailMessage::new(
+                user.get_email(),
+                "Verify your email address",
+                &body,
+            ))
+            .await?;
+        Ok(())
     }
 }
 
-impl<R> UserServiceTrait for UserService<R>
+impl<R, M, V, P, A, G, I> UserServiceTrait for UserService<R, M, V, P, A, G, I>
 where
     R: UserRepositoryTrait<
         Id = Uuid,
@@ -46,41 +311,1116 @@ where
         Error = UserError,
         FindOptions = UserFindRequest,
         FindResult = UserFindResponse,
+        Event = UserEvent,
     >,
+    M: MailerTrait,
+    V: VerificationTokenStoreTrait,
+    P: EventPublisherTrait,
+    A: AuditTrailTrait,
+    G: GroupRepositoryTrait,
+    I: IdGeneratorTrait,
 {
     fn create_user(
         &self,
+        context: &RequestContext,
         req: &UserAddRequest,
     ) -> impl Future<Output = Result<User, UserError>> + Send {
-        Box::pin(async { self.user_repository.save(&req.into()).await })
+        Box::pin(
+            async {
+                self.policy
+                    .authorize(context.get_principal(), UserOperation::Create, None)?;
+                self.attribute_schema.validate(req.get_attributes())?;
+                let mut transaction = self
+                    .audited_users(context, UserOperation::Create)
+                    .begin()
+                    .await?;
+                let user: User = req.into();
+                let user = match req.get_id() {
+                    Some(_) => user,
+                    None => user.with_id(&self.id_generator.generate()),
+                };
+                let user = transaction.save(&user).await?;
+                transaction.commit().await?;
+                self.request_relay();
+                self.issue_verification_after_commit(&user).await;
+                Ok(user)
+            }
+            .instrument(operation_span("create_user", context)),
+        )
     }
 
     fn update_user(
         &self,
+        context: &RequestContext,
         user_id: &uuid::Uuid,
         req: &UserUpdateRequest,
     ) -> impl Future<Output = Result<User, UserError>> + Send {
-        Box::pin(async { self.user_repository.update(user_id, &req.into()).await })
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Update,
+                    Some(user_id),
+                )?;
+                let mut user: User = req.into();
+                self.attribute_schema.validate(user.get_attributes())?;
+                let mut transaction = self
+                    .audited_users(context, UserOperation::Update)
+                    .begin()
+                    .await?;
+                let current = transaction.find_by_id(user_id).await?;
+                let email_changed = current.get_email().ne(user.get_email());
+                user.set_verified(&(current.is_verified() && !email_changed));
+                user.set_status(current.get_status(), current.get_status_reason());
+                let updated = transaction.update(user_id, &user).await?;
+                transaction.commit().await?;
+                self.request_relay();
+                if email_changed {
+                    self.issue_verification_after_commit(&updated).await;
+                }
+                Ok(updated)
+            }
+            .instrument(operation_span("update_user", context)),
+        )
     }
 
     fn find_one_user(
         &self,
+        context: &RequestContext,
         user_id: &Uuid,
     ) -> impl Future<Output = Result<User, UserError>> + Send {
-        Box::pin(async { self.user_repository.find_by_id(user_id).await })
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Read,
+                    Some(user_id),
+                )?;
+                self.users(context.get_tenant()).find_by_id(user_id).await
+            }
+            .instrument(operation_span("find_one_user", context)),
+        )
     }
 
     fn find_user(
         &self,
+        context: &RequestContext,
         req: &UserFindRequest,
     ) -> impl Future<Output = Result<UserFindResponse, UserError>> + Send {
-        Box::pin(async { self.user_repository.find_all(req).await })
+        Box::pin(
+            async {
+                self.policy
+                    .authorize(context.get_principal(), UserOperation::List, None)?;
+                let mut req = req.clone();
+                req.set_filters(
+                    &self
+                        .resolve_group(context.get_tenant(), &req.get_query())
+                        .await?,
+                );
+                self.users(context.get_tenant()).find_all(&req).await
+            }
+            .instrument(operation_span("find_user", context)),
+        )
+    }
+
+    fn find_user_history(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<AuditEntry>, UserError>> + Send {
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Read,
+                    Some(user_id),
+                )?;
+                self.tenant_history(context, user_id).await
+            }
+            .instrument(operation_span("find_user_history", context)),
+        )
+    }
+
+    fn watch_users(
+        &self,
+        context: &RequestContext,
+        filter: &UserFindRequestFilter,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send {
+        Box::pin(
+            async move {
+                self.policy
+                    .authorize(context.get_principal(), UserOperation::List, None)?;
+                let filter = self.resolve_group(context.get_tenant(), filter).await?;
+                Ok(self
+                    .users(context.get_tenant())
+                    .subscribe_changes(last_sequence)
+                    .await?
+                    .with_filter(&filter))
+            }
+            .instrument(operation_span("watch_users", context)),
+        )
     }
 
     fn delete_user(
         &self,
+        context: &RequestContext,
         req: &UserDeleteRequest,
     ) -> impl Future<Output = Result<(), UserError>> + Send {
-        Box::pin(async { self.user_repository.delete(req.get_user_id()).await })
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Delete,
+                    Some(req.get_user_id()),
+                )?;
+                let mut transaction = self
+                    .audited_users(context, UserOperation::Delete)
+                    .begin()
+                    .await?;
+                transaction.delete(req.get_user_id()).await?;
+                transaction.commit().await?;
+                self.request_relay();
+                Ok(())
+            }
+            .instrument(operation_span("delete_user", context)),
+        )
+    }
+
+    fn send_verification(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), UserError>> + Send {
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Update,
+                    Some(user_id),
+                )?;
+                let user = self.users(context.get_tenant()).find_by_id(user_id).await?;
+                if user.is_verified() {
+                    return Err(UserError::EmailAlreadyVerified {
+                        email: user.get_email().clone(),
+                    });
+                }
+                self.issue_verification(&user).await
+            }
+            .instrument(operation_span("send_verification", context)),
+        )
+    }
+
+    fn verify_email(
+        &self,
+        request_id: &str,
+        req: &UserVerifyEmailRequest,
+    ) -> impl Future<Output = Result<User, UserError>> + Send {
+        Box::pin(
+            async {
+                let token = self
+                    .verification_token_store
+                    .take(&VerificationToken::hash(req.get_token()))
+                    .await?
+                    .ok_or(UserError::InvalidVerificationToken)?;
+                if token.is_expired(&SystemTime::now()) {
+                    return Err(UserError::ExpiredVerificationToken);
+                }
+                let principal = Principal::new(
+                    &token.get_user_id().to_string(),
+                    Some(token.get_user_id()),
+                    &[],
+                    AuthenticationMethod::VerificationToken,
+                )
+                .with_tenant(token.get_tenant());
+                let context =
+                    RequestContext::new(&principal, request_id).with_tenant(token.get_tenant());
+                let users = self.users(token.get_tenant());
+                // Verifying a pending user activates it, which is audited as
+                // a status change. A user never becomes pending again, so the
+                // guess is retried at most once.
+                let current = users.find_by_id(token.get_user_id()).await?;
+                let mut pending = matches!(current.get_status(), UserStatus::Pending);
+                loop {
+                    let operation = match pending {
+                        true => UserOperation::ChangeStatus,
+                        false => UserOperation::Update,
+                    };
+                    let mut transaction = users
+                        .audited(&AuditContext::new(&context, operation))
+                        .begin()
+                        .await?;
+                    let mut user = transaction.find_by_id(token.get_user_id()).await?;
+                    if user.get_email().ne(token.get_email()) {
+                        return Err(UserError::InvalidVerificationToken);
+                    }
+                    if matches!(user.get_status(), UserStatus::Pending) != pending {
+                        transaction.rollback().await?;
+                        pending = !pending;
+                        continue;
+                    }
+                    user.set_verified(&true);
+                    if pending {
+                        user.activate()?;
+                    }
+                    let verified = transaction.update(token.get_user_id(), &user).await?;
+                    transaction.commit().await?;
+                    self.request_relay();
+                    return Ok(verified);
+                }
+            }
+            .instrument(info_span!("user_service", operation = "verify_email")),
+        )
+    }
+
+    fn activate_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send {
+        self.change_status(context, user_id, User::activate)
+            .instrument(operation_span("activate_user", context))
+    }
+
+    fn suspend_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+        req: &UserSuspendRequest,
+    ) -> impl Future<Output = Result<User, UserError>> + Send {
+        let reason = req.get_reason().trim().to_string();
+        self.change_status(context, user_id, move |user| user.suspend(&reason))
+            .instrument(operation_span("suspend_user", context))
+    }
+
+    fn lock_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send {
+        self.change_status(context, user_id, User::lock)
+            .instrument(operation_span("lock_user", context))
+    }
+
+    fn unlock_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send {
+        self.change_status(context, user_id, User::unlock)
+            .instrument(operation_span("unlock_user", context))
+    }
+
+    fn export_user_data(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<UserDataExport, UserError>> + Send {
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Read,
+                    Some(user_id),
+                )?;
+                let user = self.users(context.get_tenant()).find_by_id(user_id).await?;
+                let groups = self
+                    .groups(context.get_tenant())
+                    .find_groups_of(user_id)
+                    .await?;
+                let history = self.tenant_history(context, user_id).await?;
+                Ok(UserDataExport::new(&user, &groups, &history))
+            }
+            .instrument(operation_span("export_user_data", context)),
+        )
+    }
+
+    fn erase_user(
+        &self,
+        context: &RequestContext,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<User, UserError>> + Send {
+        Box::pin(
+            async {
+                self.policy.authorize(
+                    context.get_principal(),
+                    UserOperation::Erase,
+                    Some(user_id),
+                )?;
+                let mut transaction = self
+                    .audited_users(context, UserOperation::Erase)
+                    .begin()
+                    .await?;
+                let mut user = transaction.find_by_id(user_id).await?;
+                user.anonymize();
+                let erased = transaction.erase(user_id, &user).await?;
+                transaction.commit().await?;
+                self.request_relay();
+                Ok(erased)
+            }
+            .instrument(operation_span("erase_user", context)),
+        )
+    }
+
+    fn get_attribute_schema(&self) -> &AttributeSchema {
+        &self.attribute_schema
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        time::Duration,
+    };
+
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            audit::{AuditEntry, AuditError, AuditRecord, AuditTrailTrait},
+            auth::{AuthenticationMethod, Principal, RequestContext, Role, TenantId},
+            group::{Group, GroupRepositoryTrait},
+            mail::{EmailMessage, MailerError, MailerTrait},
+            user::{
+                dtos::UserFindRequestFilter, model::user::UserError,
+                service::event_publisher_chain::EventPublisherChain, AttributeDefinition,
+                AttributeSchema, AttributeType, AttributeValue, CustomAttributes, EmailAddress,
+                EventPublisherTrait, Name, UserAddRequest, UserChangeFeedTrait, UserDeleteRequest,
+                UserEvent, UserEventType, UserFindRequest, UserOperation, UserServiceTrait,
+                UserStatus, UserSuspendRequest, UserUpdateRequest, UserVerifyEmailRequest,
+            },
+            webhook::{
+                service::webhook_service::WebhookService, WebhookAddRequest,
+                WebhookDeliveryStoreTrait, WebhookServiceTrait, WebhookUrl,
+            },
+        },
+        outbound::{
+            event_publisher_adapter::broadcast_event_publisher::BroadcastEventPublisher,
+            id_generator_adapter::sequential_id_generator::SequentialIdGenerator,
+            in_memory_repository_adapter::{
+                in_memory_audit_trail::InMemoryAuditTrail,
+                in_memory_group_repository::InMemoryGroupRepository,
+                in_memory_user_repository::InMemoryUserRepository,
+                in_memory_verification_token_store::InMemoryVerificationTokenStore,
+                in_memory_webhook_delivery_store::InMemoryWebhookDeliveryStore,
+                in_memory_webhook_subscription_store::InMemoryWebhookSubscriptionStore,
+            },
+            repository_trait::{FindResultTrait, OutboxTrait, RepositoryTrait},
+            webhook_sender_adapter::http_webhook_sender::HttpWebhookSender,
+        },
+    };
+
+    use super::UserService;
+
+    #[derive(Debug, Clone, Default)]
+    struct RecordingMailer {
+        messages: Arc<Mutex<Vec<EmailMessage>>>,
+        failing: Arc<AtomicBool>,
+    }
+
+    impl RecordingMailer {
+        fn last_token(&self) -> String {
+            let messages = self.messages.lock().unwrap();
+            let body = messages.last().unwrap().get_body();
+            let start = body.find("address: ").unwrap() + "address: ".len();
+            body[start..start + 64].to_string()
+        }
+    }
+
+    impl MailerTrait for RecordingMailer {
+        fn send(
+            &self,
+            message: &EmailMessage,
+        ) -> impl Future<Output = Result<(), MailerError>> + Send {
+            let result = if self.failing.load(Ordering::SeqCst) {
+                Err(MailerError::Transport {
+                    reason: String::from("connection refused"),
+                })
+            } else {
+                self.messages.lock().unwrap().push(message.clone());
+                Ok(())
+            };
+            Box::pin(async { result })
+        }
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct FlakyPublisher {
+        failing: Arc<AtomicBool>,
+        published: Arc<Mutex<Vec<UserEvent>>>,
+    }
+
+    impl EventPublisherTrait for FlakyPublisher {
+        fn publish(&self, event: &UserEvent) -> impl Future<Output = Result<(), UserError>> + Send {
+            let result = if self.failing.load(Ordering::SeqCst) {
+                Err(UserError::EventPublishing {
+                    event_id: *event.get_id(),
+                    reason: "broker unavailable".to_string(),
+                })
+            } else {
+                self.published.lock().unwrap().push(event.clone());
+                Ok(())
+            };
+            Box::pin(async { result })
+        }
+    }
+
+    fn admin() -> RequestContext {
+        RequestContext::new(
+            &Principal::new(
+                "admin",
+                None,
+                &[Role::new("admin")],
+                AuthenticationMethod::ApiKey,
+            ),
+            "test-request",
+        )
+    }
+
+    fn service(
+        mailer: &RecordingMailer,
+    ) -> UserService<
+        InMemoryUserRepository,
+        RecordingMailer,
+        InMemoryVerificationTokenStore,
+        BroadcastEventPublisher,
+        InMemoryAuditTrail,
+        InMemoryGroupRepository,
+        SequentialIdGenerator,
+    > {
+        UserService::new(
+            InMemoryUserRepository::new(),
+            mailer.clone(),
+            InMemoryVerificationTokenStore::new(),
+            BroadcastEventPublisher::default(),
+            InMemoryAuditTrail::new(),
+            InMemoryGroupRepository::new(),
+            SequentialIdGenerator::new(),
+        )
+    }
+
+    fn add_request() -> UserAddRequest {
+        UserAddRequest::new(
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_verify_email_single_use() {
+        let mailer = RecordingMailer::default();
+        let service = service(&mailer);
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        assert!(!user.is_verified());
+        let request = UserVerifyEmailRequest::new(&mailer.last_token());
+        let verified = service
+            .verify_email("test-request", &request)
+            .await
+            .unwrap();
+        assert!(verified.is_verified());
+        assert_eq!(verified.get_status(), &UserStatus::Active);
+        let reused = service.verify_email("test-request", &request).await;
+        assert!(matches!(reused, Err(UserError::InvalidVerificationToken)));
+
+        service.relay_events().await.unwrap();
+        let history = service
+            .find_user_history(&admin(), user.get_id())
+            .await
+            .unwrap();
+        let entry = history.last().unwrap();
+        assert_eq!(entry.get_operation(), UserOperation::ChangeStatus);
+        assert_eq!(entry.get_actor(), user.get_id().to_string());
+        assert_eq!(entry.get_request_id(), "test-request");
+    }
+
+    #[tokio::test]
+    async fn test_mail_failure_keeps_user() {
+        let mailer = RecordingMailer::default();
+        let service = service(&mailer);
+        mailer.failing.store(true, Ordering::SeqCst);
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        assert!(service.find_one_user(&admin(), user.get_id()).await.is_ok());
+        assert!(service
+            .send_verification(&admin(), user.get_id())
+            .await
+            .is_err());
+        mailer.failing.store(false, Ordering::SeqCst);
+        service
+            .send_verification(&admin(), user.get_id())
+            .await
+            .unwrap();
+        service
+            .verify_email(
+                "test-request",
+                &UserVerifyEmailRequest::new(&mailer.last_token()),
+            )
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_email_change_resets_verification() {
+        let mailer = RecordingMailer::default();
+        let service = service(&mailer);
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        service
+            .verify_email(
+                "test-request",
+                &UserVerifyEmailRequest::new(&mailer.last_token()),
+            )
+            .await
+            .unwrap();
+        let update = UserUpdateRequest::new(
+            user.get_id(),
+            user.get_firstname(),
+            user.get_lastname(),
+            &EmailAddress::new("john.doe@example.com").unwrap(),
+        );
+        let updated = service
+            .update_user(&admin(), user.get_id(), &update)
+            .await
+            .unwrap();
+        assert!(!updated.is_verified());
+        assert_eq!(mailer.messages.lock().unwrap().len(), 2);
+        assert_eq!(
+            mailer.messages.lock().unwrap()[1].get_to(),
+            &EmailAddress::new("john.doe@example.com").unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_user_ids() {
+        let service = service(&RecordingMailer::default());
+        let generated = service.create_user(&admin(), &add_request()).await.unwrap();
+        assert_eq!(generated.get_id(), &Uuid::from_u128(1));
+
+        let chosen = Uuid::now_v7();
+        let jane = UserAddRequest::new(
+            &Name::new("Jane").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("jane@example.com").unwrap(),
+        )
+        .with_id(&chosen);
+        let created = service.create_user(&admin(), &jane).await.unwrap();
+        assert_eq!(created.get_id(), &chosen);
+        let taken = add_request().with_id(generated.get_id());
+        assert!(matches!(
+            service.create_user(&admin(), &taken).await,
+            Err(UserError::IdAlreadyUsed { id }) if id == Uuid::from_u128(1)
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_expired_token() {
+        let mailer = RecordingMailer::default();
+        let service = service(&mailer).with_verification_ttl(Duration::ZERO);
+        service.create_user(&admin(), &add_request()).await.unwrap();
+        let result = service
+            .verify_email(
+                "test-request",
+                &UserVerifyEmailRequest::new(&mailer.last_token()),
+            )
+            .await;
+        assert!(matches!(result, Err(UserError::ExpiredVerificationToken)));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_token() {
+        let service = service(&RecordingMailer::default());
+        let result = service
+            .verify_email(
+                "test-request",
+                &UserVerifyEmailRequest::new(&Uuid::new_v4().to_string()),
+            )
+            .await;
+        assert!(matches!(result, Err(UserError::InvalidVerificationToken)));
+    }
+
+    #[tokio::test]
+    async fn test_mutations_request_relay() {
+        let publisher = FlakyPublisher::default();
+        let service = UserService::new(
+            InMemoryUserRepository::new(),
+            RecordingMailer::default(),
+            InMemoryVerificationTokenStore::new(),
+            publisher.clone(),
+            InMemoryAuditTrail::new(),
+            InMemoryGroupRepository::new(),
+            SequentialIdGenerator::new(),
+        );
+        service.create_user(&admin(), &add_request()).await.unwrap();
+        assert!(publisher.published.lock().unwrap().is_empty());
+        tokio::time::timeout(Duration::from_secs(1), service.relay_requested())
+            .await
+            .unwrap();
+        assert_eq!(service.relay_events().await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_events_kept_until_published() {
+        let repository = InMemoryUserRepository::new();
+        let publisher = FlakyPublisher::default();
+        publisher.failing.store(true, Ordering::SeqCst);
+        let service = UserService::new(
+            repository.clone(),
+            RecordingMailer::default(),
+            InMemoryVerificationTokenStore::new(),
+            publisher.clone(),
+            InMemoryAuditTrail::new(),
+            InMemoryGroupRepository::new(),
+            SequentialIdGenerator::new(),
+        );
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        service
+            .delete_user(&admin(), &UserDeleteRequest::new(user.get_id()))
+            .await
+            .unwrap();
+        assert_eq!(repository.pending_events(10).await.unwrap().len(), 2);
+        assert!(publisher.published.lock().unwrap().is_empty());
+
+        publisher.failing.store(false, Ordering::SeqCst);
+        assert_eq!(service.relay_events().await.unwrap(), 2);
+        assert!(repository.pending_events(10).await.unwrap().is_empty());
+        let published = publisher.published.lock().unwrap();
+        assert_eq!(published[0].get_event_type(), UserEventType::UserCreated);
+        assert_eq!(published[1].get_event_type(), UserEventType::UserDeleted);
+    }
+
+    #[tokio::test]
+    async fn test_history_records_mutations() {
+        let service = service(&RecordingMailer::default());
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        let update = UserUpdateRequest::new(
+            user.get_id(),
+            &Name::new("Johnny").unwrap(),
+            user.get_lastname(),
+            user.get_email(),
+        );
+        service
+            .update_user(&admin(), user.get_id(), &update)
+            .await
+            .unwrap();
+        service
+            .delete_user(&admin(), &UserDeleteRequest::new(user.get_id()))
+            .await
+            .unwrap();
+
+        service.relay_events().await.unwrap();
+        let history = service
+            .find_user_history(&admin(), user.get_id())
+            .await
+            .unwrap();
+        let operations: Vec<UserOperation> = history.iter().map(|e| e.get_operation()).collect();
+        assert_eq!(
+            operations,
+            [
+                UserOperation::Create,
+                UserOperation::Update,
+                UserOperation::Delete
+            ]
+        );
+        assert_eq!(history[1].get_changes().len(), 1);
+        assert_eq!(history[1].get_changes()[0].get_field(), "firstname");
+        assert!(history.iter().all(|e| e.get_request_id() == "test-request"));
+        assert_eq!(history[1].get_previous_hash(), history[0].get_hash());
+    }
+
+    #[tokio::test]
+    async fn test_status_transitions() {
+        let mailer = RecordingMailer::default();
+        let service = service(&mailer);
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        assert_eq!(user.get_status(), &UserStatus::Pending);
+        let verified = service
+            .verify_email(
+                "test-request",
+                &UserVerifyEmailRequest::new(&mailer.last_token()),
+            )
+            .await
+            .unwrap();
+        assert_eq!(verified.get_status(), &UserStatus::Active);
+
+        let myself = RequestContext::new(
+            &Principal::new("john", Some(user.get_id()), &[], AuthenticationMethod::Jwt),
+            "test-request",
+        );
+        let result = service.lock_user(&myself, user.get_id()).await;
+        assert!(matches!(
+            result,
+            Err(UserError::Forbidden {
+                operation: UserOperation::ChangeStatus
+            })
+        ));
+
+        let suspended = service
+            .suspend_user(
+                &admin(),
+                user.get_id(),
+                &UserSuspendRequest::new("Chargeback"),
+            )
+            .await
+            .unwrap();
+        assert_eq!(suspended.get_status_reason(), Some("Chargeback"));
+        let result = service.unlock_user(&admin(), user.get_id()).await;
+        assert!(matches!(
+            result,
+            Err(UserError::InvalidStatusTransition {
+                from: UserStatus::Suspended,
+                to: UserStatus::Active
+            })
+        ));
+        let update = UserUpdateRequest::new(
+            user.get_id(),
+            &Name::new("Johnny").unwrap(),
+            user.get_lastname(),
+            user.get_email(),
+        );
+        let updated = service
+            .update_user(&myself, user.get_id(), &update)
+            .await
+            .unwrap();
+        assert_eq!(updated.get_status(), &UserStatus::Suspended);
+
+        let filter = UserFindRequestFilter {
+            status: Some(UserStatus::Suspended),
+            ..Default::default()
+        };
+        let request = UserFindRequest::new(&filter, "", &10, &1).unwrap();
+        let found = service.find_user(&admin(), &request).await.unwrap();
+        assert_eq!(found.get_result().count(), 1);
+
+        service
+            .activate_user(&admin(), user.get_id())
+            .await
+            .unwrap();
+        service.lock_user(&admin(), user.get_id()).await.unwrap();
+        let unlocked = service.unlock_user(&admin(), user.get_id()).await.unwrap();
+        assert_eq!(unlocked.get_status(), &UserStatus::Active);
+
+        service.relay_events().await.unwrap();
+        let history = service
+            .find_user_history(&admin(), user.get_id())
+            .await
+            .unwrap();
+        let status_changes = history
+            .iter()
+            .filter(|entry| entry.get_operation() == UserOperation::ChangeStatus)
+            .count();
+        assert_eq!(status_changes, 5);
+    }
+
+    #[tokio::test]
+    async fn test_export_and_erase() {
+        let service = service(&RecordingMailer::default());
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        let group = service
+            .group_repository
+            .save(&Group::new(&Uuid::nil(), &Name::new("Sales").unwrap()))
+            .await
+            .unwrap();
+        service
+            .group_repository
+            .add_member(group.get_id(), user.get_id())
+            .await
+            .unwrap();
+        let foreign = service
+            .group_repository
+            .for_tenant(&TenantId::new("acme").unwrap());
+        let other = foreign
+            .save(&Group::new(&Uuid::nil(), &Name::new("Sales").unwrap()))
+            .await
+            .unwrap();
+        foreign
+            .add_member(other.get_id(), user.get_id())
+            .await
+            .unwrap();
+
+        service.relay_events().await.unwrap();
+        let export = service
+            .export_user_data(&admin(), user.get_id())
+            .await
+            .unwrap();
+        assert_eq!(export.get_user(), &user);
+        assert_eq!(export.get_groups(), std::slice::from_ref(&group));
+        assert_eq!(export.get_history().len(), 1);
+
+        let erased = service.erase_user(&admin(), user.get_id()).await.unwrap();
+        assert_eq!(erased.get_id(), user.get_id());
+        assert_ne!(erased.get_email(), user.get_email());
+        service.relay_events().await.unwrap();
+        let export = service
+            .export_user_data(&admin(), user.get_id())
+            .await
+            .unwrap();
+        assert_eq!(export.get_user(), &erased);
+        assert_eq!(export.get_groups(), [group]);
+        let erasure = export.get_history().last().unwrap();
+        assert_eq!(erasure.get_operation(), UserOperation::Erase);
+        assert!(erasure
+            .get_changes()
+            .iter()
+            .all(|change| change.get_before().is_none()));
+        let serialized = serde_json::to_string(erasure).unwrap();
+        assert!(!serialized.contains("john") && !serialized.contains("Doe"));
+    }
+
+    #[tokio::test]
+    async fn test_erasure_leaves_no_trace() {
+        let repository = InMemoryUserRepository::new();
+        let audit_trail = InMemoryAuditTrail::new();
+        let broadcast = BroadcastEventPublisher::default();
+        let deliveries = InMemoryWebhookDeliveryStore::new();
+        let webhooks = WebhookService::new(
+            InMemoryWebhookSubscriptionStore::new(),
+            deliveries.clone(),
+            HttpWebhookSender::default(),
+        );
+        let subscription = webhooks
+            .create_subscription(
+                &admin(),
+                &WebhookAddRequest::new(
+                    &WebhookUrl::new("https://partner.example.com/hooks").unwrap(),
+                    &[
+                        UserEventType::UserCreated,
+                        UserEventType::UserUpdated,
+                        UserEventType::UserErased,
+                    ],
+                    "secret",
+                ),
+            )
+            .await
+            .unwrap();
+        let service = UserService::new(
+            repository.clone(),
+            RecordingMailer::default(),
+            InMemoryVerificationTokenStore::new(),
+            EventPublisherChain::new(broadcast.clone(), webhooks),
+            audit_trail.clone(),
+            InMemoryGroupRepository::new(),
+            SequentialIdGenerator::new(),
+        );
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        let update = UserUpdateRequest::new(
+            user.get_id(),
+            user.get_firstname(),
+            user.get_lastname(),
+            &EmailAddress::new("john.doe@example.com").unwrap(),
+        );
+        service
+            .update_user(&admin(), user.get_id(), &update)
+            .await
+            .unwrap();
+        service.relay_events().await.unwrap();
+        let mut published = broadcast.subscribe();
+        service.erase_user(&admin(), user.get_id()).await.unwrap();
+        assert_eq!(service.relay_events().await.unwrap(), 1);
+
+        let mut traces = vec![serde_json::to_string(&published.recv().await.unwrap()).unwrap()];
+        for event in repository.pending_events(100).await.unwrap() {
+            traces.push(serde_json::to_string(&event).unwrap());
+        }
+        let mut changes = repository.subscribe_changes(Some(0)).await.unwrap();
+        for _ in 0..3 {
+            let change = changes.next().await.unwrap();
+            traces.push(serde_json::to_string(change.get_event()).unwrap());
+        }
+        for entry in audit_trail.find_by_user(user.get_id()).await.unwrap() {
+            traces.push(serde_json::to_string(&entry).unwrap());
+        }
+        for delivery in deliveries
+            .find_by_subscription(subscription.get_id())
+            .await
+            .unwrap()
+        {
+            traces.push(delivery.get_payload().to_string());
+        }
+        assert_eq!(traces.len(), 10);
+        assert!(traces
+            .iter()
+            .all(|trace| !trace.contains("example.com") && !trace.contains("Doe")));
+        assert_eq!(audit_trail.verify_chain().await.unwrap(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_custom_attributes_validated() {
+        let service = service(&RecordingMailer::default()).with_attribute_schema(
+            AttributeSchema::new()
+                .with_attribute(
+                    "department",
+                    AttributeDefinition::new(AttributeType::String).with_required(true),
+                )
+                .unwrap(),
+        );
+        let created = service.create_user(&admin(), &add_request()).await;
+        assert!(matches!(created, Err(UserError::InvalidAttribute(_))));
+
+        let attributes = CustomAttributes::new()
+            .with("department", AttributeValue::String(String::from("Sales")));
+        let user = service
+            .create_user(&admin(), &add_request().with_attributes(&attributes))
+            .await
+            .unwrap();
+        assert_eq!(user.get_attributes(), &attributes);
+        let update = UserUpdateRequest::new(
+            user.get_id(),
+            user.get_firstname(),
+            user.get_lastname(),
+            user.get_email(),
+        )
+        .with_attributes(&attributes.clone().with("level", AttributeValue::Integer(2)));
+        let updated = service.update_user(&admin(), user.get_id(), &update).await;
+        assert!(matches!(updated, Err(UserError::InvalidAttribute(_))));
+    }
+
+    #[derive(Debug, Clone, Default)]
+    struct FlakyAuditTrail {
+        failing: Arc<AtomicBool>,
+        inner: InMemoryAuditTrail,
+    }
+
+    impl AuditTrailTrait for FlakyAuditTrail {
+        fn append(
+            &self,
+            record: &AuditRecord,
+        ) -> impl Future<Output = Result<AuditEntry, AuditError>> + Send {
+            let failing = self.failing.load(Ordering::SeqCst);
+            let record = record.clone();
+            Box::pin(async move {
+                if failing {
+                    return Err(AuditError::Storage {
+                        reason: String::from("disk full"),
+                    });
+                }
+                self.inner.append(&record).await
+            })
+        }
+
+        fn find_by_user(
+            &self,
+            user_id: &Uuid,
+        ) -> impl Future<Output = Result<Vec<AuditEntry>, AuditError>> + Send {
+            self.inner.find_by_user(user_id)
+        }
+
+        fn redact_user(
+            &self,
+            user_id: &Uuid,
+        ) -> impl Future<Output = Result<usize, AuditError>> + Send {
+            self.inner.redact_user(user_id)
+        }
+
+        fn verify_chain(&self) -> impl Future<Output = Result<u64, AuditError>> + Send {
+            self.inner.verify_chain()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_audit_committed_with_change() {
+        let repository = InMemoryUserRepository::new();
+        let audit_trail = FlakyAuditTrail::default();
+        let publisher = FlakyPublisher::default();
+        let service = UserService::new(
+            repository.clone(),
+            RecordingMailer::default(),
+            InMemoryVerificationTokenStore::new(),
+            publisher.clone(),
+            audit_trail.clone(),
+            InMemoryGroupRepository::new(),
+            SequentialIdGenerator::new(),
+        );
+        audit_trail.failing.store(true, Ordering::SeqCst);
+        let user = service.create_user(&admin(), &add_request()).await.unwrap();
+        let pending = repository.pending_events(10).await.unwrap();
+        let audit = pending[0].get_audit().unwrap();
+        assert_eq!(audit.get_user_id(), user.get_id());
+        assert_eq!(audit.get_event_id(), Some(pending[0].get_id()));
+        assert!(matches!(
+            service.relay_events().await,
+            Err(UserError::Audit(_))
+        ));
+        assert_eq!(repository.pending_events(10).await.unwrap(), pending);
+
+        audit_trail.failing.store(false, Ordering::SeqCst);
+        publisher.failing.store(true, Ordering::SeqCst);
+        assert!(service.relay_events().await.is_err());
+        publisher.failing.store(false, Ordering::SeqCst);
+        assert_eq!(service.relay_events().await.unwrap(), 1);
+        let history = service
+            .find_user_history(&admin(), user.get_id())
+            .await
+            .unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].get_operation(), UserOperation::Create);
+        assert_eq!(history[0].get_event_id(), Some(pending[0].get_id()));
+    }
+
+    #[tokio::test]
+    async fn test_group_filter_and_membership_cleanup() {
+        let groups = InMemoryGroupRepository::new();
+        let service = UserService::new(
+            InMemoryUserRepository::new(),
+            RecordingMailer::default(),
+            InMemoryVerificationTokenStore::new(),
+            BroadcastEventPublisher::default(),
+            InMemoryAuditTrail::new(),
+            groups.clone(),
+            SequentialIdGenerator::new(),
+        );
+        let core = groups
+            .save(&Group::new(&Uuid::nil(), &Name::new("Core").unwrap()))
+            .await
+            .unwrap();
+        let member = service.create_user(&admin(), &add_request()).await.unwrap();
+        service
+            .create_user(
+                &admin(),
+                &UserAddRequest::new(
+                    &Name::new("Jane").unwrap(),
+                    &Name::new("Doe").unwrap(),
+                    &EmailAddress::new("jane@example.com").unwrap(),
+                ),
+            )
+            .await
+            .unwrap();
+        groups
+            .add_member(core.get_id(), member.get_id())
+            .await
+            .unwrap();
+
+        let by_group = |group| {
+            UserFindRequest::new(
+                &UserFindRequestFilter {
+                    group: Some(group),
+                    ..Default::default()
+                },
+                "",
+                &25,
+                &1,
+            )
+            .unwrap()
+        };
+        let found = service
+            .find_user(&admin(), &by_group(*core.get_id()))
+            .await
+            .unwrap();
+        assert_eq!(found.get_result().collect::<Vec<_>>(), vec![member.clone()]);
+        let unknown = service
+            .find_user(&admin(), &by_group(Uuid::new_v4()))
+            .await
+            .unwrap();
+        assert_eq!(unknown.get_result().count(), 0);
+
+        service
+            .delete_user(&admin(), &UserDeleteRequest::new(member.get_id()))
+            .await
+            .unwrap();
+        service.relay_events().await.unwrap();
+        assert!(groups.find_members(core.get_id()).await.unwrap().is_empty());
     }
 }
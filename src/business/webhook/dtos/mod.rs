@@ -0,0 +1,3 @@
+pub mod webhook_add_request;
+
+pub use webhook_add_request::WebhookAddRequest;
@@ -0,0 +1,34 @@
+use serde::Deserialize;
+use utoipa::ToSchema;
+
+use crate::business::{user::UserEventType, webhook::WebhookUrl};
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, ToSchema)]
+pub struct WebhookAddRequest {
+    url: WebhookUrl,
+    #[serde(default)]
+    event_types: Vec<UserEventType>,
+    secret: String,
+}
+
+impl WebhookAddRequest {
+    pub fn new(url: &WebhookUrl, event_types: &[UserEventType], secret: &str) -> Self {
+        Self {
+            url: url.clone(),
+            event_types: event_types.to_vec(),
+            secret: secret.to_string(),
+        }
+    }
+
+    pub fn get_url(&self) -> &WebhookUrl {
+        &self.url
+    }
+
+    pub fn get_event_types(&self) -> &[UserEventType] {
+        &self.event_types
+    }
+
+    pub fn get_secret(&self) -> &str {
+        &self.secret
+    }
+}
@@ -0,0 +1,15 @@
+pub mod dtos;
+pub mod model;
+pub mod ports;
+pub mod service;
+
+pub use dtos::WebhookAddRequest;
+pub use model::{
+    DeliveryAttempt, DeliveryStatus, RetryPolicy, WebhookAllowList, WebhookDelivery, WebhookError,
+    WebhookRequest, WebhookSubscription, WebhookUrl, WebhookUrlError,
+};
+
+pub use ports::{
+    WebhookDeliveryStoreTrait, WebhookSenderTrait, WebhookServiceTrait,
+    WebhookSubscriptionStoreTrait,
+};
@@ -0,0 +1,9 @@
+pub mod webhook_delivery;
+pub mod webhook_subscription;
+
+pub use webhook_delivery::{
+    DeliveryAttempt, DeliveryStatus, RetryPolicy, WebhookDelivery, WebhookRequest,
+};
+pub use webhook_subscription::{
+    WebhookAllowList, WebhookError, WebhookSubscription, WebhookUrl, WebhookUrlError,
+};
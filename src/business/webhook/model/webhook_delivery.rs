@@ -0,0 +1,266 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    user::{User, UserEvent, UserEventType},
+};
+
+use super::{WebhookSubscription, WebhookUrl};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self::new(8, Duration::from_secs(10), Duration::from_secs(60 * 60))
+    }
+}
+
+impl RetryPolicy {
+    pub fn new(max_attempts: u32, base_delay: Duration, max_delay: Duration) -> Self {
+        Self {
+            max_attempts: max_attempts.max(1),
+            base_delay,
+            max_delay,
+        }
+    }
+
+    pub fn get_max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    pub fn delay_after(&self, attempts: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.saturating_sub(1));
+        self.base_delay.saturating_mul(factor).min(self.max_delay)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub enum DeliveryStatus {
+    Pending,
+    Delivered,
+    DeadLettered,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct DeliveryAttempt {
+    attempted_at: u64,
+    status_code: Option<u16>,
+    error: Option<String>,
+}
+
+impl DeliveryAttempt {
+    pub fn get_attempted_at(&self) -> u64 {
+        self.attempted_at
+    }
+
+    pub fn get_status_code(&self) -> Option<u16> {
+        self.status_code
+    }
+
+    pub fn get_error(&self) -> Option<&str> {
+        self.error.as_deref()
+    }
+}
+
+/// The event and its payload are left out of responses; stores keep them.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookDelivery {
+    id: Uuid,
+    subscription_id: Uuid,
+    tenant: TenantId,
+    url: WebhookUrl,
+    event_id: Uuid,
+    event_type: UserEventType,
+    #[serde(skip_serializing)]
+    event: UserEvent,
+    #[serde(skip_serializing)]
+    payload: String,
+    status: DeliveryStatus,
+    attempts: Vec<DeliveryAttempt>,
+    next_attempt_at: Option<u64>,
+}
+
+impl WebhookDelivery {
+    pub fn new(subscription: &WebhookSubscription, event: &UserEvent, now: &u64) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            subscription_id: *subscription.get_id(),
+            tenant: subscription.get_tenant().clone(),
+            url: subscription.get_url().clone(),
+            event_id: *event.get_id(),
+            event_type: event.get_event_type(),
+            event: event.clone(),
+            payload: serde_json::to_string(event).unwrap_or_default(),
+            status: DeliveryStatus::Pending,
+            attempts: Vec::new(),
+            next_attempt_at: Some(*now),
+        }
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn get_subscription_id(&self) -> &Uuid {
+        &self.subscription_id
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    pub fn get_url(&self) -> &WebhookUrl {
+        &self.url
+    }
+
+    pub fn get_event_id(&self) -> &Uuid {
+        &self.event_id
+    }
+
+    pub fn get_event_type(&self) -> UserEventType {
+        self.event_type
+    }
+
+    pub fn get_user_id(&self) -> &Uuid {
+        self.event.get_user_id()
+    }
+
+    pub fn get_event(&self) -> &UserEvent {
+        &self.event
+    }
+
+    pub fn get_payload(&self) -> &str {
+        &self.payload
+    }
+
+    /// Rewrites the payload with the erased user, whether it was sent or not.
+    pub fn redact(&mut self, erased: &User) {
+        self.event.redact(erased);
+        self.payload = serde_json::to_string(&self.event).unwrap_or_default();
+    }
+
+    pub fn get_status(&self) -> DeliveryStatus {
+        self.status
+    }
+
+    pub fn get_attempts(&self) -> &[DeliveryAttempt] {
+        &self.attempts
+    }
+
+    pub fn get_next_attempt_at(&self) -> Option<u64> {
+        self.next_attempt_at
+    }
+
+    pub fn is_due(&self, now: &u64) -> bool {
+        self.status == DeliveryStatus::Pending && self.next_attempt_at.is_some_and(|at| at <= *now)
+    }
+
+    pub fn record_success(&mut self, now: &u64, status_code: &u16) {
+        self.attempts.push(DeliveryAttempt {
+            attempted_at: *now,
+            status_code: Some(*status_code),
+            error: None,
+        });
+        self.status = DeliveryStatus::Delivered;
+        self.next_attempt_at = None;
+    }
+
+    pub fn record_failure(
+        &mut self,
+        now: &u64,
+        status_code: Option<u16>,
+        error: &str,
+        policy: &RetryPolicy,
+    ) {
+        self.attempts.push(DeliveryAttempt {
+            attempted_at: *now,
+            status_code,
+            error: Some(error.to_string()),
+        });
+        let attempts = self.attempts.len() as u32;
+        if attempts >= policy.get_max_attempts() {
+            self.status = DeliveryStatus::DeadLettered;
+            self.next_attempt_at = None;
+        } else {
+            self.next_attempt_at = Some(now + policy.delay_after(attempts).as_millis() as u64);
+        }
+    }
+
+    pub fn requeue(&mut self, now: &u64) {
+        self.status = DeliveryStatus::Pending;
+        self.next_attempt_at = Some(*now);
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WebhookRequest {
+    url: WebhookUrl,
+    delivery_id: Uuid,
+    event_type: UserEventType,
+    timestamp: u64,
+    signature: String,
+    body: String,
+}
+
+impl WebhookRequest {
+    pub fn new(subscription: &WebhookSubscription, delivery: &WebhookDelivery, now: &u64) -> Self {
+        Self {
+            url: subscription.get_url().clone(),
+            delivery_id: *delivery.get_id(),
+            event_type: delivery.get_event_type(),
+            timestamp: *now,
+            signature: subscription.sign(now, delivery.get_payload()),
+            body: delivery.get_payload().to_string(),
+        }
+    }
+
+    pub fn get_url(&self) -> &WebhookUrl {
+        &self.url
+    }
+
+    pub fn get_delivery_id(&self) -> &Uuid {
+        &self.delivery_id
+    }
+
+    pub fn get_event_type(&self) -> UserEventType {
+        self.event_type
+    }
+
+    pub fn get_timestamp(&self) -> u64 {
+        self.timestamp
+    }
+
+    pub fn get_signature(&self) -> &str {
+        &self.signature
+    }
+
+    pub fn get_body(&self) -> &str {
+        &self.body
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::RetryPolicy;
+
+    #[test]
+    fn test_exponential_backoff() {
+        let policy = RetryPolicy::new(10, Duration::from_secs(10), Duration::from_secs(60));
+        assert_eq!(policy.delay_after(1), Duration::from_secs(10));
+        assert_eq!(policy.delay_after(2), Duration::from_secs(20));
+        assert_eq!(policy.delay_after(3), Duration::from_secs(40));
+        assert_eq!(policy.delay_after(4), Duration::from_secs(60));
+        assert_eq!(policy.delay_after(40), Duration::from_secs(60));
+    }
+}
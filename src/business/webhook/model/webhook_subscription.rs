@@ -0,0 +1,308 @@
+use std::{fmt::Display, net::IpAddr, ops::Deref};
+
+use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use thiserror::Error;
+use url::{Host, Url};
+use utoipa::ToSchema;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    user::{UserEvent, UserEventType},
+};
+
+lazy_static! {
+    static ref WEBHOOK_URL_REGEX: regex::Regex =
+        regex::Regex::new(r"^https?://[a-zA-Z0-9.-]+(:[0-9]{1,5})?(/[^\s]*)?$").unwrap();
+}
+
+/// The secret is left out of responses; stores keep it.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct WebhookSubscription {
+    id: Uuid,
+    url: WebhookUrl,
+    event_types: Vec<UserEventType>,
+    #[serde(skip_serializing)]
+    secret: String,
+    created_at: u64,
+    tenant: TenantId,
+}
+
+impl WebhookSubscription {
+    pub fn new(
+        id: &Uuid,
+        url: &WebhookUrl,
+        event_types: &[UserEventType],
+        secret: &str,
+        created_at: &u64,
+    ) -> Self {
+        Self {
+            id: *id,
+            url: url.clone(),
+            event_types: event_types.to_vec(),
+            secret: secret.to_string(),
+            created_at: *created_at,
+            tenant: TenantId::default(),
+        }
+    }
+
+    /// Only events of users of `tenant` are delivered to the subscription.
+    pub fn with_tenant(mut self, tenant: &TenantId) -> Self {
+        self.tenant = tenant.clone();
+        self
+    }
+
+    pub fn get_id(&self) -> &Uuid {
+        &self.id
+    }
+
+    pub fn get_url(&self) -> &WebhookUrl {
+        &self.url
+    }
+
+    pub fn get_event_types(&self) -> &[UserEventType] {
+        &self.event_types
+    }
+
+    pub fn get_secret(&self) -> &str {
+        &self.secret
+    }
+
+    pub fn get_created_at(&self) -> u64 {
+        self.created_at
+    }
+
+    pub fn get_tenant(&self) -> &TenantId {
+        &self.tenant
+    }
+
+    pub fn is_subscribed_to(&self, event: &UserEvent) -> bool {
+        self.tenant.eq(event.get_tenant())
+            && (self.event_types.is_empty() || self.event_types.contains(&event.get_event_type()))
+    }
+
+    pub fn sign(&self, timestamp: &u64, payload: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.secret.as_bytes())
+            .expect("HMAC accepts keys of any length");
+        mac.update(format!("{}.{}", timestamp, payload).as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum WebhookError {
+    #[error("Webhook subscription with id {id} does not exists")]
+    SubscriptionNotExists { id: Uuid },
+    #[error("Webhook delivery with id {id} does not exists")]
+    DeliveryNotExists { id: Uuid },
+    #[error("Webhook delivery with id {id} is not dead-lettered")]
+    DeliveryNotDeadLettered { id: Uuid },
+    #[error("Webhook secret cannot be empty")]
+    EmptySecret,
+    #[error("Webhook management forbidden")]
+    Forbidden,
+    #[error("Webhook url {url} targets an internal host")]
+    InternalTarget { url: WebhookUrl },
+    #[error("Webhook could not be sent to {url}: {reason}")]
+    Transport { url: WebhookUrl, reason: String },
+    #[error(transparent)]
+    Unknown(#[from] anyhow::Error),
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(try_from = "&str")]
+pub struct WebhookUrl(String);
+
+impl WebhookUrl {
+    pub fn new(raw: &str) -> Result<Self, WebhookUrlError> {
+        let trimed = raw.trim();
+        if WEBHOOK_URL_REGEX.is_match(trimed) {
+            Ok(Self(trimed.to_string()))
+        } else {
+            Err(WebhookUrlError {
+                invalid_url: trimed.to_string(),
+            })
+        }
+    }
+
+    /// The host as an HTTP client reads it, with IP addresses normalized.
+    pub fn get_host(&self) -> Option<Host<String>> {
+        Url::parse(&self.0)
+            .ok()
+            .and_then(|url| url.host().map(|host| host.to_owned()))
+    }
+}
+
+impl TryFrom<&str> for WebhookUrl {
+    type Error = WebhookUrlError;
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Self::new(value)
+    }
+}
+
+impl Deref for WebhookUrl {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl Display for WebhookUrl {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+/// Hosts webhooks may target although they are internal. Other loopback,
+/// private, link-local and unspecified addresses, and `localhost`, are
+/// refused, so that subscriptions cannot reach internal services.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct WebhookAllowList {
+    hosts: Vec<String>,
+}
+
+impl WebhookAllowList {
+    /// Reads a comma separated list of host names or IP addresses.
+    pub fn parse(list: &str) -> Self {
+        Self {
+            hosts: list
+                .split(',')
+                .map(|host| host.trim().to_ascii_lowercase())
+                .filter(|host| !host.is_empty())
+                .collect(),
+        }
+    }
+
+    pub fn contains(&self, host: &str) -> bool {
+        self.hosts
+            .iter()
+            .any(|allowed| allowed.eq_ignore_ascii_case(host))
+    }
+
+    /// Whether the host of `url` is internal and not allowed. Names are also
+    /// checked once resolved, by the sender.
+    pub fn refuses(&self, url: &WebhookUrl) -> bool {
+        match url.get_host() {
+            Some(Host::Domain(name)) => {
+                let name = name.to_ascii_lowercase();
+                !self.contains(&name) && (name == "localhost" || name.ends_with(".localhost"))
+            }
+            Some(Host::Ipv4(address)) => {
+                self.refuses_address(&address.to_string(), &address.into())
+            }
+            Some(Host::Ipv6(address)) => {
+                self.refuses_address(&address.to_string(), &address.into())
+            }
+            None => true,
+        }
+    }
+
+    /// Whether `address`, which `host` resolved to, is internal and `host`
+    /// is not allowed.
+    pub fn refuses_address(&self, host: &str, address: &IpAddr) -> bool {
+        !self.contains(host) && is_internal(address)
+    }
+}
+
+fn is_internal(address: &IpAddr) -> bool {
+    match address {
+        IpAddr::V4(v4) => {
+            v4.is_loopback()
+                || v4.is_private()
+                || v4.is_link_local()
+                || v4.is_unspecified()
+                || v4.is_broadcast()
+        }
+        IpAddr::V6(v6) => match v6.to_ipv4_mapped() {
+            Some(v4) => is_internal(&v4.into()),
+            None => {
+                let first = v6.segments()[0];
+                v6.is_loopback()
+                    || v6.is_unspecified()
+                    // Unique local fc00::/7 and link-local fe80::/10.
+                    || first & 0xfe00 == 0xfc00
+                    || first & 0xffc0 == 0xfe80
+            }
+        },
+    }
+}
+
+#[derive(Clone, Debug, Error)]
+#[error("{invalid_url} is not a valid http or https url")]
+pub struct WebhookUrlError {
+    pub invalid_url: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::{
+        auth::TenantId,
+        user::{EmailAddress, Name, User, UserEvent, UserEventType},
+    };
+
+    use super::{WebhookAllowList, WebhookSubscription, WebhookUrl};
+
+    #[test]
+    fn test_webhook_url() {
+        assert!(WebhookUrl::new("https://partner.example.com/hooks/users").is_ok());
+        assert!(WebhookUrl::new("http://127.0.0.1:8081").is_ok());
+        assert!(WebhookUrl::new("ftp://partner.example.com").is_err());
+        assert!(WebhookUrl::new("https://").is_err());
+    }
+
+    #[test]
+    fn test_internal_targets_refused() {
+        let url = |raw: &str| WebhookUrl::new(raw).unwrap();
+        let default = WebhookAllowList::default();
+        assert!(!default.refuses(&url("https://partner.example.com/hooks")));
+        assert!(!default.refuses(&url("http://93.184.216.34/hooks")));
+        for internal in [
+            "http://127.0.0.1:8081",
+            "http://localhost/hooks",
+            "http://10.0.0.8",
+            "http://192.168.1.1",
+            "http://169.254.169.254/latest/meta-data",
+            "http://0.0.0.0",
+            "http://2130706433/",
+        ] {
+            assert!(default.refuses(&url(internal)), "{}", internal);
+        }
+        let allowed = WebhookAllowList::parse(" 127.0.0.1, Localhost ");
+        assert!(!allowed.refuses(&url("http://127.0.0.1:8081")));
+        assert!(!allowed.refuses(&url("http://localhost/hooks")));
+        assert!(allowed.refuses(&url("http://10.0.0.8")));
+        assert!(default.refuses_address("partner.example.com", &"::1".parse().unwrap()));
+        assert!(default.refuses_address("partner.example.com", &"fd00::1".parse().unwrap()));
+    }
+
+    #[test]
+    fn test_subscription_signature_and_filter() {
+        let subscription = WebhookSubscription::new(
+            &Uuid::new_v4(),
+            &WebhookUrl::new("https://partner.example.com").unwrap(),
+            &[UserEventType::UserDeleted],
+            "key",
+            &0,
+        );
+        let mut user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        assert!(subscription.is_subscribed_to(&UserEvent::deleted(&user)));
+        assert!(!subscription.is_subscribed_to(&UserEvent::created(&user)));
+        user.set_tenant(&TenantId::new("acme").unwrap());
+        assert!(!subscription.is_subscribed_to(&UserEvent::deleted(&user)));
+        assert_eq!(
+            subscription.sign(&0, "The quick brown fox jumps over the lazy dog"),
+            "8511f28f7a1949f0c42772b447d68b2daf760f5f0439a20a17e3b4e7cd395763"
+        );
+    }
+}
@@ -0,0 +1,9 @@
+pub mod webhook_delivery_store_trait;
+pub mod webhook_sender_trait;
+pub mod webhook_service_trait;
+pub mod webhook_subscription_store_trait;
+
+pub use webhook_delivery_store_trait::WebhookDeliveryStoreTrait;
+pub use webhook_sender_trait::WebhookSenderTrait;
+pub use webhook_service_trait::WebhookServiceTrait;
+pub use webhook_subscription_store_trait::WebhookSubscriptionStoreTrait;
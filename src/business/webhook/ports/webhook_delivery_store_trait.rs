@@ -0,0 +1,49 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    user::User,
+    webhook::{WebhookDelivery, WebhookError},
+};
+
+pub trait WebhookDeliveryStoreTrait: Sync + Send + Clone + 'static {
+    fn save(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send;
+
+    fn find_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookDelivery, WebhookError>> + Send;
+
+    fn find_by_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+
+    fn find_due(
+        &self,
+        now: &u64,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+
+    fn find_dead_letters(
+        &self,
+        tenant: &TenantId,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+
+    fn delete_for_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send;
+
+    /// Rewrites the payloads of the deliveries of the user with its erased
+    /// version. Returns how many were rewritten.
+    fn redact_user(
+        &self,
+        erased: &User,
+    ) -> impl Future<Output = Result<usize, WebhookError>> + Send;
+}
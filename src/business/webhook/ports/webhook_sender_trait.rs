@@ -0,0 +1,11 @@
+use std::future::Future;
+
+use crate::business::webhook::{WebhookError, WebhookRequest};
+
+pub trait WebhookSenderTrait: Sync + Send + Clone + 'static {
+    /// Returns the HTTP status code answered by the receiver.
+    fn send(
+        &self,
+        request: &WebhookRequest,
+    ) -> impl Future<Output = Result<u16, WebhookError>> + Send;
+}
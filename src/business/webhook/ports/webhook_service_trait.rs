@@ -0,0 +1,50 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::RequestContext,
+    webhook::{WebhookAddRequest, WebhookDelivery, WebhookError, WebhookSubscription},
+};
+
+pub trait WebhookServiceTrait: Sync + Send + Clone + 'static {
+    fn create_subscription(
+        &self,
+        context: &RequestContext,
+        req: &WebhookAddRequest,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send;
+
+    fn find_subscriptions(
+        &self,
+        context: &RequestContext,
+    ) -> impl Future<Output = Result<Vec<WebhookSubscription>, WebhookError>> + Send;
+
+    fn find_one_subscription(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send;
+
+    fn delete_subscription(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send;
+
+    fn find_deliveries(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+
+    fn find_dead_letters(
+        &self,
+        context: &RequestContext,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send;
+
+    fn retry_delivery(
+        &self,
+        context: &RequestContext,
+        delivery_id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookDelivery, WebhookError>> + Send;
+}
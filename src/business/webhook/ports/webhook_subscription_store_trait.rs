@@ -0,0 +1,28 @@
+use std::future::Future;
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    webhook::{WebhookError, WebhookSubscription},
+};
+
+pub trait WebhookSubscriptionStoreTrait: Sync + Send + Clone + 'static {
+    fn save(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send;
+
+    fn delete(&self, id: &Uuid) -> impl Future<Output = Result<(), WebhookError>> + Send;
+
+    fn find_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send;
+
+    /// Subscriptions of `tenant`, oldest first.
+    fn find_all(
+        &self,
+        tenant: &TenantId,
+    ) -> impl Future<Output = Result<Vec<WebhookSubscription>, WebhookError>> + Send;
+}
@@ -0,0 +1,562 @@
+use std::{
+    future::Future,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use uuid::Uuid;
+
+use crate::business::{
+    auth::{Principal, RequestContext, Role},
+    user::{model::user::UserError, EventPublisherTrait, UserEvent},
+    webhook::{
+        DeliveryStatus, RetryPolicy, WebhookAddRequest, WebhookAllowList, WebhookDelivery,
+        WebhookDeliveryStoreTrait, WebhookError, WebhookRequest, WebhookSenderTrait,
+        WebhookServiceTrait, WebhookSubscription, WebhookSubscriptionStoreTrait,
+    },
+};
+
+const DELIVERY_BATCH_SIZE: usize = 100;
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_millis() as u64)
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookService<S, D, H>
+where
+    S: WebhookSubscriptionStoreTrait,
+    D: WebhookDeliveryStoreTrait,
+    H: WebhookSenderTrait,
+{
+    subscription_store: S,
+    delivery_store: D,
+    sender: H,
+    retry_policy: RetryPolicy,
+    allow_list: WebhookAllowList,
+    admin_role: Role,
+}
+
+impl<S, D, H> WebhookService<S, D, H>
+where
+    S: WebhookSubscriptionStoreTrait,
+    D: WebhookDeliveryStoreTrait,
+    H: WebhookSenderTrait,
+{
+    pub fn new(subscription_store: S, delivery_store: D, sender: H) -> Self {
+        Self {
+            subscription_store,
+            delivery_store,
+            sender,
+            retry_policy: RetryPolicy::default(),
+            allow_list: WebhookAllowList::default(),
+            admin_role: Role::new("admin"),
+        }
+    }
+
+    pub fn with_retry_policy(mut self, retry_policy: RetryPolicy) -> Self {
+        self.retry_policy = retry_policy;
+        self
+    }
+
+    pub fn with_allow_list(mut self, allow_list: WebhookAllowList) -> Self {
+        self.allow_list = allow_list;
+        self
+    }
+
+    fn authorize(&self, principal: &Principal) -> Result<(), WebhookError> {
+        if principal.has_role(&self.admin_role) {
+            Ok(())
+        } else {
+            Err(WebhookError::Forbidden)
+        }
+    }
+
+    /// Subscriptions of another tenant are reported as missing.
+    async fn find_subscription(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> Result<WebhookSubscription, WebhookError> {
+        let subscription = self.subscription_store.find_by_id(subscription_id).await?;
+        match subscription.get_tenant().eq(context.get_tenant()) {
+            true => Ok(subscription),
+            false => Err(WebhookError::SubscriptionNotExists {
+                id: *subscription_id,
+            }),
+        }
+    }
+
+    /// An erasure also rewrites the deliveries recorded before for the user.
+    pub async fn enqueue(&self, event: &UserEvent) -> Result<usize, WebhookError> {
+        if let Some(erased) = event.get_erased_user() {
+            self.delivery_store.redact_user(erased).await?;
+        }
+        let now = now();
+        let mut enqueued = 0;
+        for subscription in self.subscription_store.find_all(event.get_tenant()).await? {
+            if subscription.is_subscribed_to(event) {
+                self.delivery_store
+                    .save(&WebhookDelivery::new(&subscription, event, &now))
+                    .await?;
+                enqueued += 1;
+            }
+        }
+        Ok(enqueued)
+    }
+
+    pub async fn deliver_due(&self) -> Result<usize, WebhookError> {
+        let due = self
+            .delivery_store
+            .find_due(&now(), DELIVERY_BATCH_SIZE)
+            .await?;
+        for mut delivery in due.iter().cloned() {
+            let subscription = match self
+                .subscription_store
+                .find_by_id(delivery.get_subscription_id())
+                .await
+            {
+                Ok(subscription) => subscription,
+                Err(WebhookError::SubscriptionNotExists { id: _ }) => continue,
+                Err(e) => return Err(e),
+            };
+            let now = now();
+            match self
+                .sender
+                .send(&WebhookRequest::new(&subscription, &delivery, &now))
+                .await
+            {
+                Ok(status_code) if (200..300).contains(&status_code) => {
+                    delivery.record_success(&now, &status_code)
+                }
+                Ok(status_code) => delivery.record_failure(
+                    &now,
+                    Some(status_code),
+                    &format!("Receiver answered with status {}", status_code),
+                    &self.retry_policy,
+                ),
+                Err(e) => delivery.record_failure(&now, None, &e.to_string(), &self.retry_policy),
+            }
+            self.delivery_store.save(&delivery).await?;
+        }
+        Ok(due.len())
+    }
+}
+
+impl<S, D, H> WebhookServiceTrait for WebhookService<S, D, H>
+where
+    S: WebhookSubscriptionStoreTrait,
+    D: WebhookDeliveryStoreTrait,
+    H: WebhookSenderTrait,
+{
+    fn create_subscription(
+        &self,
+        context: &RequestContext,
+        req: &WebhookAddRequest,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            if req.get_secret().trim().is_empty() {
+                return Err(WebhookError::EmptySecret);
+            }
+            if self.allow_list.refuses(req.get_url()) {
+                return Err(WebhookError::InternalTarget {
+                    url: req.get_url().clone(),
+                });
+            }
+            let subscription = WebhookSubscription::new(
+                &Uuid::new_v4(),
+                req.get_url(),
+                req.get_event_types(),
+                req.get_secret(),
+                &now(),
+            )
+            .with_tenant(context.get_tenant());
+            self.subscription_store.save(&subscription).await?;
+            Ok(subscription)
+        })
+    }
+
+    fn find_subscriptions(
+        &self,
+        context: &RequestContext,
+    ) -> impl Future<Output = Result<Vec<WebhookSubscription>, WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            self.subscription_store.find_all(context.get_tenant()).await
+        })
+    }
+
+    fn find_one_subscription(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            self.find_subscription(context, subscription_id).await
+        })
+    }
+
+    fn delete_subscription(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            self.find_subscription(context, subscription_id).await?;
+            self.subscription_store.delete(subscription_id).await?;
+            self.delivery_store
+                .delete_for_subscription(subscription_id)
+                .await
+        })
+    }
+
+    fn find_deliveries(
+        &self,
+        context: &RequestContext,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            self.find_subscription(context, subscription_id).await?;
+            self.delivery_store
+                .find_by_subscription(subscription_id)
+                .await
+        })
+    }
+
+    fn find_dead_letters(
+        &self,
+        context: &RequestContext,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            self.delivery_store
+                .find_dead_letters(context.get_tenant())
+                .await
+        })
+    }
+
+    fn retry_delivery(
+        &self,
+        context: &RequestContext,
+        delivery_id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookDelivery, WebhookError>> + Send {
+        Box::pin(async {
+            self.authorize(context.get_principal())?;
+            let mut delivery = self.delivery_store.find_by_id(delivery_id).await?;
+            if delivery.get_tenant().ne(context.get_tenant()) {
+                return Err(WebhookError::DeliveryNotExists { id: *delivery_id });
+            }
+            if delivery.get_status() != DeliveryStatus::DeadLettered {
+                return Err(WebhookError::DeliveryNotDeadLettered { id: *delivery_id });
+            }
+            delivery.requeue(&now());
+            self.delivery_store.save(&delivery).await?;
+            Ok(delivery)
+        })
+    }
+}
+
+impl<S, D, H> EventPublisherTrait for WebhookService<S, D, H>
+where
+    S: WebhookSubscriptionStoreTrait,
+    D: WebhookDeliveryStoreTrait,
+    H: WebhookSenderTrait,
+{
+    fn publish(&self, event: &UserEvent) -> impl Future<Output = Result<(), UserError>> + Send {
+        Box::pin(async move {
+            self.enqueue(event)
+                .await
+                .map(|_| ())
+                .map_err(|e| UserError::EventPublishing {
+                    event_id: *event.get_id(),
+                    reason: e.to_string(),
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        future::Future,
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::{AuthenticationMethod, Principal, RequestContext, Role, TenantId},
+            user::{EmailAddress, Name, User, UserEvent, UserEventType},
+            webhook::{
+                DeliveryStatus, RetryPolicy, WebhookAddRequest, WebhookAllowList, WebhookError,
+                WebhookRequest, WebhookSenderTrait, WebhookServiceTrait, WebhookUrl,
+            },
+        },
+        outbound::in_memory_repository_adapter::{
+            in_memory_webhook_delivery_store::InMemoryWebhookDeliveryStore,
+            in_memory_webhook_subscription_store::InMemoryWebhookSubscriptionStore,
+        },
+    };
+
+    use super::WebhookService;
+
+    #[derive(Debug, Clone, Default)]
+    struct ScriptedSender {
+        statuses: Arc<Mutex<Vec<u16>>>,
+        requests: Arc<Mutex<Vec<WebhookRequest>>>,
+    }
+
+    impl WebhookSenderTrait for ScriptedSender {
+        fn send(
+            &self,
+            request: &WebhookRequest,
+        ) -> impl Future<Output = Result<u16, WebhookError>> + Send {
+            self.requests.lock().unwrap().push(request.clone());
+            let status = self.statuses.lock().unwrap().pop().unwrap_or(200);
+            Box::pin(async move { Ok(status) })
+        }
+    }
+
+    type TestService = WebhookService<
+        InMemoryWebhookSubscriptionStore,
+        InMemoryWebhookDeliveryStore,
+        ScriptedSender,
+    >;
+
+    fn context(role: &str) -> RequestContext {
+        let principal = Principal::new(
+            "partner-admin",
+            None,
+            &[Role::new(role)],
+            AuthenticationMethod::ApiKey,
+        );
+        RequestContext::new(&principal, "request-id")
+    }
+
+    fn service(sender: &ScriptedSender, retry_policy: RetryPolicy) -> TestService {
+        WebhookService::new(
+            InMemoryWebhookSubscriptionStore::new(),
+            InMemoryWebhookDeliveryStore::new(),
+            sender.clone(),
+        )
+        .with_retry_policy(retry_policy)
+    }
+
+    fn created_event() -> UserEvent {
+        UserEvent::created(&User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        ))
+    }
+
+    fn tenant_created_event(tenant: &TenantId) -> UserEvent {
+        let mut user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("Jane").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("jane@example.com").unwrap(),
+        );
+        user.set_tenant(tenant);
+        UserEvent::created(&user)
+    }
+
+    fn add_request(event_types: &[UserEventType]) -> WebhookAddRequest {
+        WebhookAddRequest::new(
+            &WebhookUrl::new("https://partner.example.com/hooks").unwrap(),
+            event_types,
+            "secret",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_deliver_to_matching_subscriptions() {
+        let sender = ScriptedSender::default();
+        let service = service(&sender, RetryPolicy::default());
+        let admin = context("admin");
+        let created = service
+            .create_subscription(&admin, &add_request(&[UserEventType::UserCreated]))
+            .await
+            .unwrap();
+        let deleted = service
+            .create_subscription(&admin, &add_request(&[UserEventType::UserDeleted]))
+            .await
+            .unwrap();
+        assert_eq!(service.enqueue(&created_event()).await.unwrap(), 1);
+        assert_eq!(service.deliver_due().await.unwrap(), 1);
+
+        let log = service
+            .find_deliveries(&admin, created.get_id())
+            .await
+            .unwrap();
+        assert_eq!(log[0].get_status(), DeliveryStatus::Delivered);
+        assert!(service
+            .find_deliveries(&admin, deleted.get_id())
+            .await
+            .unwrap()
+            .is_empty());
+        let request = &sender.requests.lock().unwrap()[0];
+        assert_eq!(
+            request.get_signature(),
+            created.sign(&request.get_timestamp(), request.get_body())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_backoff_then_dead_letter() {
+        let sender = ScriptedSender::default();
+        sender.statuses.lock().unwrap().extend([500, 503]);
+        let service = service(
+            &sender,
+            RetryPolicy::new(2, Duration::from_secs(3600), Duration::from_secs(3600)),
+        );
+        let admin = context("admin");
+        let subscription = service
+            .create_subscription(&admin, &add_request(&[]))
+            .await
+            .unwrap();
+        service.enqueue(&created_event()).await.unwrap();
+        service.deliver_due().await.unwrap();
+        assert_eq!(service.deliver_due().await.unwrap(), 0);
+        let delivery = service
+            .find_deliveries(&admin, subscription.get_id())
+            .await
+            .unwrap()
+            .remove(0);
+        assert_eq!(delivery.get_status(), DeliveryStatus::Pending);
+        assert_eq!(
+            delivery.get_next_attempt_at().unwrap(),
+            delivery.get_attempts()[0].get_attempted_at() + 3_600_000
+        );
+
+        let result = service.retry_delivery(&admin, delivery.get_id()).await;
+        assert!(matches!(
+            result,
+            Err(WebhookError::DeliveryNotDeadLettered { id: _ })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_and_retry() {
+        let sender = ScriptedSender::default();
+        sender.statuses.lock().unwrap().extend([500, 503]);
+        let service = service(&sender, RetryPolicy::new(2, Duration::ZERO, Duration::ZERO));
+        let admin = context("admin");
+        service
+            .create_subscription(&admin, &add_request(&[]))
+            .await
+            .unwrap();
+        service.enqueue(&created_event()).await.unwrap();
+        service.deliver_due().await.unwrap();
+        service.deliver_due().await.unwrap();
+        let dead_letters = service.find_dead_letters(&admin).await.unwrap();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].get_attempts().len(), 2);
+        assert_eq!(
+            dead_letters[0].get_attempts()[1].get_status_code(),
+            Some(500)
+        );
+
+        service
+            .retry_delivery(&admin, dead_letters[0].get_id())
+            .await
+            .unwrap();
+        service.deliver_due().await.unwrap();
+        assert!(service.find_dead_letters(&admin).await.unwrap().is_empty());
+        assert_eq!(sender.requests.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_internal_target_refused_unless_allowed() {
+        let sender = ScriptedSender::default();
+        let admin = context("admin");
+        let internal = WebhookAddRequest::new(
+            &WebhookUrl::new("http://169.254.169.254/latest").unwrap(),
+            &[],
+            "secret",
+        );
+        let result = service(&sender, RetryPolicy::default())
+            .create_subscription(&admin, &internal)
+            .await;
+        assert!(matches!(result, Err(WebhookError::InternalTarget { .. })));
+        assert!(service(&sender, RetryPolicy::default())
+            .with_allow_list(WebhookAllowList::parse("169.254.169.254"))
+            .create_subscription(&admin, &internal)
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_forbidden_without_admin_role() {
+        let service = service(&ScriptedSender::default(), RetryPolicy::default());
+        let result = service
+            .create_subscription(&context("reader"), &add_request(&[]))
+            .await;
+        assert!(matches!(result, Err(WebhookError::Forbidden)));
+    }
+
+    #[tokio::test]
+    async fn test_subscriptions_scoped_to_tenant() {
+        let sender = ScriptedSender::default();
+        let service = service(&sender, RetryPolicy::default());
+        let acme = TenantId::new("acme").unwrap();
+        let globex = TenantId::new("globex").unwrap();
+        let acme_admin = context("admin").with_tenant(&acme);
+        let globex_admin = context("admin").with_tenant(&globex);
+        let subscription = service
+            .create_subscription(&acme_admin, &add_request(&[]))
+            .await
+            .unwrap();
+        assert_eq!(subscription.get_tenant(), &acme);
+
+        assert_eq!(
+            service
+                .enqueue(&tenant_created_event(&globex))
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(service.deliver_due().await.unwrap(), 0);
+        assert!(sender.requests.lock().unwrap().is_empty());
+        assert_eq!(
+            service.enqueue(&tenant_created_event(&acme)).await.unwrap(),
+            1
+        );
+
+        assert!(service
+            .find_subscriptions(&globex_admin)
+            .await
+            .unwrap()
+            .is_empty());
+        for result in [
+            service
+                .find_one_subscription(&globex_admin, subscription.get_id())
+                .await
+                .map(|_| ()),
+            service
+                .find_deliveries(&globex_admin, subscription.get_id())
+                .await
+                .map(|_| ()),
+            service
+                .delete_subscription(&globex_admin, subscription.get_id())
+                .await,
+        ] {
+            assert!(matches!(
+                result,
+                Err(WebhookError::SubscriptionNotExists { id: _ })
+            ));
+        }
+        assert_eq!(
+            service.find_subscriptions(&acme_admin).await.unwrap(),
+            vec![subscription]
+        );
+    }
+}
@@ -1,16 +1,409 @@
-use std::sync::Arc;
+use std::{net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
+use i_tantana::business::audit::AuditTrailTrait;
+use i_tantana::business::auth::service::authenticator_chain::AuthenticatorChain;
+use i_tantana::business::credential::service::credential_service::CredentialService;
+use i_tantana::business::credential::{
+    CredentialError, CredentialFindRequest, CredentialFindResponse, CredentialRepositoryTrait,
+    PasswordCredential,
+};
+use i_tantana::business::group::service::group_service::GroupService;
+use i_tantana::business::mail::MailerTrait;
+use i_tantana::business::user::dtos::{UserFindRequest, UserFindResponse};
+use i_tantana::business::user::model::user::UserError;
+use i_tantana::business::user::service::event_publisher_chain::EventPublisherChain;
 use i_tantana::business::user::service::user_service::UserService;
-use i_tantana::inbound::axum_adapter::setup::{setup, AppState};
+use i_tantana::business::user::{
+    AttributeSchema, EmailAddress, User, UserEvent, UserRepositoryTrait,
+};
+use i_tantana::business::webhook::service::webhook_service::WebhookService;
+use i_tantana::business::webhook::{
+    WebhookAllowList, WebhookDeliveryStoreTrait, WebhookSubscriptionStoreTrait,
+};
+use i_tantana::inbound::axum_adapter::idempotency::IdempotencyCache;
+use i_tantana::inbound::axum_adapter::rate_limit::RateLimiter;
+use i_tantana::inbound::axum_adapter::setup::{
+    setup, AppState, CredentialState, CrudState, TrafficState, WebhookState,
+};
+use i_tantana::inbound::axum_adapter::tenant::TenantResolver;
+use i_tantana::outbound::api_key_authenticator_adapter::static_api_key_authenticator::StaticApiKeyAuthenticator;
+use i_tantana::outbound::argon2_password_hasher_adapter::argon2_password_hasher::Argon2PasswordHasher;
+use i_tantana::outbound::audit_trail_adapter::file_audit_trail::FileAuditTrail;
+use i_tantana::outbound::encryption_adapter::local_key_file_encryptor::LocalKeyFileEncryptor;
+use i_tantana::outbound::event_publisher_adapter::broadcast_event_publisher::BroadcastEventPublisher;
+use i_tantana::outbound::file_repository_adapter::file_credential_repository::FileCredentialRepository;
+use i_tantana::outbound::file_repository_adapter::file_user_repository::FileUserRepository;
+use i_tantana::outbound::file_repository_adapter::file_webhook_delivery_store::FileWebhookDeliveryStore;
+use i_tantana::outbound::file_repository_adapter::file_webhook_subscription_store::FileWebhookSubscriptionStore;
+use i_tantana::outbound::id_generator_adapter::uuid_v7_generator::UuidV7Generator;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_audit_trail::InMemoryAuditTrail;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_credential_repository::InMemoryCredentialRepository;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_group_repository::InMemoryGroupRepository;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_idempotency_store::InMemoryIdempotencyStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_rate_limit_store::InMemoryRateLimitStore;
 use i_tantana::outbound::in_memory_repository_adapter::in_memory_user_repository::InMemoryUserRepository;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_verification_token_store::InMemoryVerificationTokenStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_webhook_delivery_store::InMemoryWebhookDeliveryStore;
+use i_tantana::outbound::in_memory_repository_adapter::in_memory_webhook_subscription_store::InMemoryWebhookSubscriptionStore;
+use i_tantana::outbound::jwt_authenticator_adapter::jwt_authenticator::JwtAuthenticator;
+use i_tantana::outbound::jwt_authenticator_adapter::jwt_token_issuer::JwtTokenIssuer;
+use i_tantana::outbound::mailer_adapter::file_mailer::FileMailer;
+use i_tantana::outbound::mailer_adapter::smtp_mailer::SmtpMailer;
+use i_tantana::outbound::tracing_adapter::telemetry::{Telemetry, TelemetryGuard};
+use i_tantana::outbound::tracing_adapter::traced_repository::TracedRepository;
+use i_tantana::outbound::webhook_sender_adapter::http_webhook_sender::HttpWebhookSender;
+use tracing::{debug_span, info, warn, Instrument};
+use uuid::Uuid;
 
-#[tokio::main]
-async fn main() -> Result<(), std::io::Error> {
-    let user_repository = InMemoryUserRepository::new();
-    let user_service = Arc::new(UserService::new(user_repository));
+fn env_path(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name).map(PathBuf::from)
+}
+
+fn jwt_authenticator() -> anyhow::Result<JwtAuthenticator> {
+    let mut authenticator = JwtAuthenticator::new();
+    if let Ok(issuer) = std::env::var("AUTH_JWT_ISSUER") {
+        authenticator = authenticator.with_issuer(&issuer);
+    }
+    if let Ok(audience) = std::env::var("AUTH_JWT_AUDIENCE") {
+        authenticator = authenticator.with_audience(&audience);
+    }
+    if let Some(path) = env_path("AUTH_JWT_HS256_SECRET_FILE") {
+        authenticator = authenticator.with_hs256_secret_file(&path)?;
+    }
+    if let Some(path) = env_path("AUTH_JWT_RS256_PUBLIC_KEY_FILE") {
+        authenticator = authenticator.with_rs256_public_key_file(&path)?;
+    }
+    if let Some(path) = env_path("AUTH_JWKS_FILE") {
+        authenticator = authenticator.with_jwks_file(&path)?;
+    }
+    Ok(authenticator)
+}
+
+fn token_issuer(
+    authenticator: JwtAuthenticator,
+) -> anyhow::Result<(JwtTokenIssuer, JwtAuthenticator)> {
+    let (mut issuer, authenticator) = match (
+        env_path("AUTH_JWT_RS256_PRIVATE_KEY_FILE"),
+        env_path("AUTH_JWT_RS256_PUBLIC_KEY_FILE"),
+        env_path("AUTH_JWT_HS256_SECRET_FILE"),
+    ) {
+        (Some(private_key), Some(public_key), _) => (
+            JwtTokenIssuer::rs256_from_files(&private_key, &public_key)?,
+            authenticator,
+        ),
+        (_, _, Some(secret)) => (JwtTokenIssuer::hs256_from_file(&secret)?, authenticator),
+        _ => {
+            let secret = [*Uuid::new_v4().as_bytes(), *Uuid::new_v4().as_bytes()].concat();
+            (
+                JwtTokenIssuer::hs256(&secret),
+                authenticator.with_hs256_secret(&secret),
+            )
+        }
+    };
+    if let Ok(iss) = std::env::var("AUTH_JWT_ISSUER") {
+        issuer = issuer.with_issuer(&iss);
+    }
+    if let Ok(audience) = std::env::var("AUTH_JWT_AUDIENCE") {
+        issuer = issuer.with_audience(&audience);
+    }
+    Ok((issuer, authenticator))
+}
+
+fn api_key_authenticator() -> anyhow::Result<StaticApiKeyAuthenticator> {
+    match env_path("AUTH_API_KEYS_FILE") {
+        Some(path) => Ok(StaticApiKeyAuthenticator::from_file(&path)?),
+        None => Ok(StaticApiKeyAuthenticator::new()),
+    }
+}
+
+fn tenant_resolver() -> anyhow::Result<TenantResolver> {
+    let mut resolver = TenantResolver::new();
+    if let Ok(header) = std::env::var("TENANT_HEADER") {
+        resolver = resolver.with_header(header.parse()?);
+    }
+    if let Ok(base_domain) = std::env::var("TENANT_BASE_DOMAIN") {
+        resolver = resolver.with_base_domain(&base_domain);
+    }
+    Ok(resolver)
+}
+
+fn telemetry() -> anyhow::Result<TelemetryGuard> {
+    let mut telemetry = Telemetry::new(env!("CARGO_PKG_NAME"));
+    if let Ok(format) = std::env::var("LOG_FORMAT") {
+        telemetry = telemetry.with_format(format.parse()?);
+    }
+    if let Ok(filter) = std::env::var("RUST_LOG") {
+        telemetry = telemetry.with_filter(&filter);
+    }
+    if let Ok(endpoint) = std::env::var("OTEL_EXPORTER_OTLP_ENDPOINT") {
+        telemetry = telemetry.with_otlp_endpoint(&endpoint);
+    }
+    Ok(telemetry.init()?)
+}
+
+fn rate_limiter() -> anyhow::Result<RateLimiter<InMemoryRateLimitStore>> {
+    let store = InMemoryRateLimitStore::new();
+    match env_path("RATE_LIMIT_FILE") {
+        Some(path) => Ok(RateLimiter::from_file(store, &path)?),
+        None => Ok(RateLimiter::new(store)),
+    }
+}
+
+fn idempotency_cache() -> anyhow::Result<IdempotencyCache<InMemoryIdempotencyStore>> {
+    let mut cache = IdempotencyCache::new(InMemoryIdempotencyStore::new());
+    if let Ok(ttl) = std::env::var("IDEMPOTENCY_TTL_SECS") {
+        cache = cache.with_ttl(&Duration::from_secs(ttl.parse()?));
+    }
+    if let Ok(lease) = std::env::var("IDEMPOTENCY_LEASE_SECS") {
+        cache = cache.with_lease(&Duration::from_secs(lease.parse()?));
+    }
+    Ok(cache)
+}
+
+async fn serve<
+    R: UserRepositoryTrait<
+            Id = Uuid,
+            Entity = User,
+            Error = UserError,
+            FindOptions = UserFindRequest,
+            FindResult = UserFindResponse,
+            Event = UserEvent,
+        > + Clone,
+    C: CredentialRepositoryTrait<
+        Id = Uuid,
+        Entity = PasswordCredential,
+        Error = CredentialError,
+        FindOptions = CredentialFindRequest,
+        FindResult = CredentialFindResponse,
+    >,
+    M: MailerTrait,
+    T: AuditTrailTrait,
+    S: WebhookSubscriptionStoreTrait,
+    D: WebhookDeliveryStoreTrait,
+>(
+    user_repository: R,
+    credential_repository: C,
+    mailer: M,
+    audit_trail: T,
+    subscription_store: S,
+    delivery_store: D,
+) -> anyhow::Result<()> {
+    let user_repository = TracedRepository::new(user_repository);
+    let group_repository = InMemoryGroupRepository::new();
+    let (token_issuer, jwt_authenticator) = token_issuer(jwt_authenticator()?)?;
+    let allow_list =
+        WebhookAllowList::parse(&std::env::var("WEBHOOK_ALLOWED_HOSTS").unwrap_or_default());
+    let webhook_service = WebhookService::new(
+        subscription_store,
+        delivery_store,
+        HttpWebhookSender::new(Duration::from_secs(10), allow_list.clone())?,
+    )
+    .with_allow_list(allow_list);
+    let event_publisher =
+        EventPublisherChain::new(BroadcastEventPublisher::default(), webhook_service.clone());
+    let mut user_service = UserService::new(
+        user_repository.clone(),
+        mailer,
+        InMemoryVerificationTokenStore::new(),
+        event_publisher,
+        audit_trail,
+        group_repository.clone(),
+        UuidV7Generator,
+    );
+    if let Ok(url) = std::env::var("MAIL_VERIFICATION_URL") {
+        user_service = user_service.with_verification_url(&url);
+    }
+    if let Some(path) = env_path("USER_ATTRIBUTES_SCHEMA_FILE") {
+        user_service = user_service.with_attribute_schema(AttributeSchema::from_file(&path)?);
+    }
+    let group_service = Arc::new(GroupService::new(group_repository, user_repository.clone()));
+    let credential_service = Arc::new(CredentialService::new(
+        user_repository,
+        credential_repository,
+        Argon2PasswordHasher::new(),
+        token_issuer,
+    ));
+    let user_service = Arc::new(user_service);
+    let relay = user_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(5));
+        loop {
+            tokio::select! {
+                _ = interval.tick() => {}
+                _ = relay.relay_requested() => {}
+            }
+            if let Err(error) = relay
+                .relay_events()
+                .instrument(debug_span!("relay_events"))
+                .await
+            {
+                warn!(%error, "user events could not be relayed");
+            }
+        }
+    });
+    let webhook_service = Arc::new(webhook_service);
+    let worker = webhook_service.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            if let Err(error) = worker
+                .deliver_due()
+                .instrument(debug_span!("deliver_webhooks"))
+                .await
+            {
+                warn!(%error, "webhooks could not be delivered");
+            }
+        }
+    });
     let app_state = AppState { user_service };
-    let router = setup(app_state).await;
-    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await.unwrap();
-    axum::serve(listener, router).await.unwrap();
+    let credential_state = CredentialState { credential_service };
+    let authenticator = AuthenticatorChain::new(jwt_authenticator, api_key_authenticator()?);
+    let webhook_state = WebhookState { webhook_service };
+    let group_state = CrudState {
+        service: group_service,
+    };
+    let traffic_state = TrafficState {
+        rate_limiter: rate_limiter()?,
+        idempotency_cache: idempotency_cache()?,
+    };
+    let router = setup(
+        app_state,
+        credential_state,
+        webhook_state,
+        group_state,
+        tenant_resolver()?,
+        authenticator,
+        traffic_state,
+    )
+    .await;
+    let listener = tokio::net::TcpListener::bind("0.0.0.0:8080").await?;
+    info!(address = %listener.local_addr()?, "listening");
+    axum::serve(
+        listener,
+        router.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .await?;
     Ok(())
 }
+
+/// Credentials and webhooks are kept in `USER_DATA_DIR` too, so users stored
+/// there can still log in, and pending deliveries are still retried, after a
+/// restart.
+async fn serve_with_stores<
+    R: UserRepositoryTrait<
+            Id = Uuid,
+            Entity = User,
+            Error = UserError,
+            FindOptions = UserFindRequest,
+            FindResult = UserFindResponse,
+            Event = UserEvent,
+        > + Clone,
+    M: MailerTrait,
+    T: AuditTrailTrait,
+>(
+    user_repository: R,
+    mailer: M,
+    audit_trail: T,
+) -> anyhow::Result<()> {
+    match env_path("USER_DATA_DIR") {
+        Some(path) => {
+            serve(
+                user_repository,
+                FileCredentialRepository::open(&path).await?,
+                mailer,
+                audit_trail,
+                FileWebhookSubscriptionStore::open(&path).await?,
+                FileWebhookDeliveryStore::open(&path).await?,
+            )
+            .await
+        }
+        None => {
+            serve(
+                user_repository,
+                InMemoryCredentialRepository::new(),
+                mailer,
+                audit_trail,
+                InMemoryWebhookSubscriptionStore::new(),
+                InMemoryWebhookDeliveryStore::new(),
+            )
+            .await
+        }
+    }
+}
+
+async fn serve_with_mailer<
+    R: UserRepositoryTrait<
+            Id = Uuid,
+            Entity = User,
+            Error = UserError,
+            FindOptions = UserFindRequest,
+            FindResult = UserFindResponse,
+            Event = UserEvent,
+        > + Clone,
+    T: AuditTrailTrait,
+>(
+    user_repository: R,
+    audit_trail: T,
+) -> anyhow::Result<()> {
+    let from = EmailAddress::new(
+        &std::env::var("MAIL_FROM").unwrap_or_else(|_| String::from("no-reply@example.com")),
+    )?;
+    match (std::env::var("MAIL_SMTP_HOST"), env_path("MAIL_FILE")) {
+        (Ok(host), _) => {
+            let port = match std::env::var("MAIL_SMTP_PORT") {
+                Ok(port) => port.parse()?,
+                Err(_) => 25,
+            };
+            serve_with_stores(
+                user_repository,
+                SmtpMailer::new(&host, port, &from),
+                audit_trail,
+            )
+            .await
+        }
+        (_, Some(path)) => {
+            serve_with_stores(user_repository, FileMailer::file(&from, &path), audit_trail).await
+        }
+        _ => serve_with_stores(user_repository, FileMailer::stdout(&from), audit_trail).await,
+    }
+}
+
+async fn serve_with_audit_trail<
+    R: UserRepositoryTrait<
+            Id = Uuid,
+            Entity = User,
+            Error = UserError,
+            FindOptions = UserFindRequest,
+            FindResult = UserFindResponse,
+            Event = UserEvent,
+        > + Clone,
+>(
+    user_repository: R,
+) -> anyhow::Result<()> {
+    match env_path("AUDIT_LOG_FILE") {
+        Some(path) => serve_with_mailer(user_repository, FileAuditTrail::open(&path).await?).await,
+        None => serve_with_mailer(user_repository, InMemoryAuditTrail::new()).await,
+    }
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let _telemetry = telemetry()?;
+    match (
+        env_path("USER_DATA_DIR"),
+        env_path("USER_ENCRYPTION_KEY_FILE"),
+    ) {
+        (Some(path), Some(key_file)) => {
+            let encryptor = LocalKeyFileEncryptor::from_file(&key_file)?;
+            let repository = FileUserRepository::open_with_encryptor(&path, encryptor).await?;
+            repository.rotate_keys().await?;
+            serve_with_audit_trail(repository).await
+        }
+        (Some(path), None) => serve_with_audit_trail(FileUserRepository::open(&path).await?).await,
+        (None, Some(_)) => {
+            anyhow::bail!("USER_ENCRYPTION_KEY_FILE is set without USER_DATA_DIR")
+        }
+        (None, None) => serve_with_audit_trail(InMemoryUserRepository::new()).await,
+    }
+}
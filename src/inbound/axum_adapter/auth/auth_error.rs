@@ -0,0 +1,29 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+use crate::business::auth::AuthError;
+
+pub struct AxumAuthError(pub AuthError);
+
+impl IntoResponse for AxumAuthError {
+    fn into_response(self) -> Response {
+        match self.0 {
+            ref e @ AuthError::InvalidKey { .. } | ref e @ AuthError::Unknown(_) => {
+                error!(error = %e, "authentication request failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            }
+            ref e @ AuthError::TenantMismatch { .. } => {
+                (StatusCode::FORBIDDEN, e.to_string()).into_response()
+            }
+            ref e => (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                e.to_string(),
+            )
+                .into_response(),
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Request, State},
+    http::{header, HeaderMap},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::business::auth::{AuthError, AuthToken, AuthenticatorTrait};
+
+use super::{auth_error::AxumAuthError, API_KEY_HEADER};
+
+pub async fn authenticate<A: AuthenticatorTrait>(
+    State(authenticator): State<Arc<A>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    let Some(token) = extract_token(request.headers()) else {
+        return AxumAuthError(AuthError::MissingCredentials).into_response();
+    };
+    match authenticator.authenticate(&token).await {
+        Ok(principal) => {
+            request.extensions_mut().insert(principal);
+            next.run(request).await
+        }
+        Err(e) => AxumAuthError(e).into_response(),
+    }
+}
+
+fn extract_token(headers: &HeaderMap) -> Option<AuthToken> {
+    if let Some(authorization) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+    {
+        if let Some((scheme, token)) = authorization.split_once(' ') {
+            if scheme.eq_ignore_ascii_case("bearer") {
+                return Some(AuthToken::Bearer(token.trim().to_string()));
+            }
+        }
+    }
+    headers
+        .get(API_KEY_HEADER)
+        .and_then(|v| v.to_str().ok())
+        .map(|key| AuthToken::ApiKey(key.trim().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        middleware,
+        routing::get,
+        Extension, Router,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::{
+        business::auth::{Principal, Role},
+        outbound::api_key_authenticator_adapter::static_api_key_authenticator::StaticApiKeyAuthenticator,
+    };
+
+    use super::authenticate;
+
+    fn router() -> Router {
+        let authenticator = Arc::new(StaticApiKeyAuthenticator::new().with_key(
+            "s3cr3t",
+            "batch",
+            &[Role::new("admin")],
+        ));
+        Router::new()
+            .route(
+                "/",
+                get(|Extension(principal): Extension<Principal>| async move {
+                    principal.get_subject().to_string()
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                authenticator,
+                authenticate::<StaticApiKeyAuthenticator>,
+            ))
+    }
+
+    #[tokio::test]
+    async fn test_missing_credentials_unauthorized() {
+        let response = router()
+            .oneshot(Request::get("/").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert!(response.headers().contains_key(header::WWW_AUTHENTICATE));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_api_key_unauthorized() {
+        let response = router()
+            .oneshot(
+                Request::get("/")
+                    .header("x-api-key", "other")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn test_api_key_attaches_principal() {
+        let response = router()
+            .oneshot(
+                Request::get("/")
+                    .header("x-api-key", "s3cr3t")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&body[..], b"batch");
+    }
+}
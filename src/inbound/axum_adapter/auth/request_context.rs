@@ -0,0 +1,41 @@
+use axum::{
+    async_trait,
+    extract::FromRequestParts,
+    http::request::Parts,
+    response::{IntoResponse, Response},
+};
+use uuid::Uuid;
+
+use crate::{
+    business::auth::{AuthError, Principal, RequestContext},
+    inbound::axum_adapter::{request_id::RequestId, tenant::RequestedTenant},
+};
+
+use super::auth_error::AxumAuthError;
+
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for RequestContext {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        let principal = parts
+            .extensions
+            .get::<Principal>()
+            .ok_or_else(|| AxumAuthError(AuthError::MissingCredentials).into_response())?;
+        let request_id = parts
+            .extensions
+            .get::<RequestId>()
+            .map_or_else(|| Uuid::new_v4().to_string(), |id| id.0.clone());
+        let tenant = match parts.extensions.get::<RequestedTenant>() {
+            Some(requested) => requested.0.clone(),
+            None => principal.get_tenant().cloned().unwrap_or_default(),
+        };
+        if !principal.can_act_for(&tenant) {
+            return Err(AxumAuthError(AuthError::TenantMismatch {
+                tenant: tenant.to_string(),
+            })
+            .into_response());
+        }
+        Ok(RequestContext::new(principal, &request_id).with_tenant(&tenant))
+    }
+}
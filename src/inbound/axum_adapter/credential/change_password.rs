@@ -0,0 +1,66 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        credential::{ChangePasswordRequest, CredentialServiceTrait},
+    },
+    inbound::axum_adapter::setup::CredentialState,
+};
+
+use super::credential_error::AxumCredentialError;
+
+#[utoipa::path(
+    post,
+    tag = "User",
+    path = "/user/{user_id}/password/change",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    params(
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    request_body = ChangePasswordRequest,
+    responses(
+        (
+            status = 204,
+            description = "Password changed"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden or current password mismatch"
+        ),
+        (
+            status = 422,
+            description = "Password does not satisfy the password policy"
+        )
+    ),
+)]
+pub async fn change_password<C: CredentialServiceTrait>(
+    State(credential_state): State<CredentialState<C>>,
+    context: RequestContext,
+    Path(user_id): Path<Uuid>,
+    Json(change_password_request): Json<ChangePasswordRequest>,
+) -> impl IntoResponse {
+    credential_state
+        .credential_service
+        .change_password(&context, &user_id, &change_password_request)
+        .await
+        .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
+        .unwrap_or_else(|e| AxumCredentialError(e).into_response())
+}
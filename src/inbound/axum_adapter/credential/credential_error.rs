@@ -0,0 +1,48 @@
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+use crate::business::credential::CredentialError;
+
+pub struct AxumCredentialError(pub CredentialError);
+
+impl IntoResponse for AxumCredentialError {
+    fn into_response(self) -> Response {
+        match self.0 {
+            ref e @ CredentialError::InvalidCredentials
+            | ref e @ CredentialError::InvalidRefreshToken => (
+                StatusCode::UNAUTHORIZED,
+                [(header::WWW_AUTHENTICATE, "Bearer")],
+                e.to_string(),
+            )
+                .into_response(),
+            ref e @ CredentialError::AccountLocked { retry_after } => (
+                StatusCode::LOCKED,
+                [(header::RETRY_AFTER, retry_after.to_string())],
+                e.to_string(),
+            )
+                .into_response(),
+            ref e @ CredentialError::CurrentPasswordMismatch
+            | ref e @ CredentialError::AccountDisabled { status: _ }
+            | ref e @ CredentialError::Forbidden { operation: _ } => {
+                (StatusCode::FORBIDDEN, e.to_string()).into_response()
+            }
+            ref e @ CredentialError::WeakPassword(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response()
+            }
+            ref _e @ CredentialError::UserNotExists { id: _ } => {
+                (StatusCode::NOT_FOUND, ()).into_response()
+            }
+            ref e @ CredentialError::CredentialNotExists { id: _ } => {
+                (StatusCode::NOT_FOUND, e.to_string()).into_response()
+            }
+            ref e @ CredentialError::CredentialAlreadyExists { id: _ }
+            | ref e @ CredentialError::Unknown(_) => {
+                error!(error = %e, "credential request failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            }
+        }
+    }
+}
@@ -0,0 +1,49 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+use crate::{
+    business::{
+        auth::TenantId,
+        credential::{AuthTokens, CredentialServiceTrait, LoginRequest},
+    },
+    inbound::axum_adapter::setup::CredentialState,
+};
+
+use super::credential_error::AxumCredentialError;
+
+#[utoipa::path(
+    post,
+    tag = "Auth",
+    path = "/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (
+            status = 200,
+            description = "Login succeed",
+            body = AuthTokens
+        ),
+        (
+            status = 401,
+            description = "Invalid email or password"
+        ),
+        (
+            status = 403,
+            description = "Account suspended or locked"
+        ),
+        (
+            status = 423,
+            description = "Account locked after repeated failures"
+        )
+    ),
+)]
+pub async fn login<C: CredentialServiceTrait>(
+    State(credential_state): State<CredentialState<C>>,
+    tenant: TenantId,
+    Json(login_request): Json<LoginRequest>,
+) -> impl IntoResponse {
+    credential_state
+        .credential_service
+        .login(&tenant, &login_request)
+        .await
+        .map(|t| (StatusCode::OK, Json(t)).into_response())
+        .unwrap_or_else(|e| AxumCredentialError(e).into_response())
+}
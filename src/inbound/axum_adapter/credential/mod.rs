@@ -0,0 +1,47 @@
+pub mod change_password;
+pub mod credential_error;
+pub mod login;
+pub mod refresh;
+pub mod set_password;
+pub mod set_roles;
+
+use axum::{
+    routing::{post, put},
+    Router,
+};
+use change_password::change_password;
+use login::login;
+use refresh::refresh;
+use set_password::set_password;
+use set_roles::set_roles;
+use utoipa::OpenApi;
+
+use crate::business::credential::CredentialServiceTrait;
+
+use super::setup::CredentialState;
+
+pub async fn init_auth_route<C: CredentialServiceTrait>() -> Router<CredentialState<C>> {
+    Router::new()
+        .route("/login", post(login))
+        .route("/refresh", post(refresh))
+}
+
+pub async fn init_password_route<C: CredentialServiceTrait>() -> Router<CredentialState<C>> {
+    Router::new()
+        .route("/:user_id/password", put(set_password))
+        .route("/:user_id/password/change", post(change_password))
+        .route("/:user_id/roles", put(set_roles))
+}
+
+pub fn api_docs() -> utoipa::openapi::OpenApi {
+    #[derive(OpenApi)]
+    #[openapi(paths(
+        crate::inbound::axum_adapter::credential::login::login,
+        crate::inbound::axum_adapter::credential::refresh::refresh,
+        crate::inbound::axum_adapter::credential::set_password::set_password,
+        crate::inbound::axum_adapter::credential::set_roles::set_roles,
+        crate::inbound::axum_adapter::credential::change_password::change_password
+    ))]
+    struct ApiDocs;
+    ApiDocs::openapi()
+}
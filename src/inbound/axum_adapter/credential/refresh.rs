@@ -0,0 +1,45 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+use crate::{
+    business::credential::{AuthTokens, CredentialServiceTrait, RefreshRequest},
+    inbound::axum_adapter::setup::CredentialState,
+};
+
+use super::credential_error::AxumCredentialError;
+
+#[utoipa::path(
+    post,
+    tag = "Auth",
+    path = "/auth/refresh",
+    request_body = RefreshRequest,
+    responses(
+        (
+            status = 200,
+            description = "Token refresh succeed",
+            body = AuthTokens
+        ),
+        (
+            status = 401,
+            description = "Invalid refresh token"
+        ),
+        (
+            status = 403,
+            description = "Account suspended or locked"
+        ),
+        (
+            status = 423,
+            description = "Account locked after repeated failures"
+        )
+    ),
+)]
+pub async fn refresh<C: CredentialServiceTrait>(
+    State(credential_state): State<CredentialState<C>>,
+    Json(refresh_request): Json<RefreshRequest>,
+) -> impl IntoResponse {
+    credential_state
+        .credential_service
+        .refresh(&refresh_request)
+        .await
+        .map(|t| (StatusCode::OK, Json(t)).into_response())
+        .unwrap_or_else(|e| AxumCredentialError(e).into_response())
+}
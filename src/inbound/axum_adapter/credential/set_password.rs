@@ -0,0 +1,66 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        credential::{CredentialServiceTrait, SetPasswordRequest},
+    },
+    inbound::axum_adapter::setup::CredentialState,
+};
+
+use super::credential_error::AxumCredentialError;
+
+#[utoipa::path(
+    put,
+    tag = "User",
+    path = "/user/{user_id}/password",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    params(
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    request_body = SetPasswordRequest,
+    responses(
+        (
+            status = 204,
+            description = "Password set"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 422,
+            description = "Password does not satisfy the password policy"
+        )
+    ),
+)]
+pub async fn set_password<C: CredentialServiceTrait>(
+    State(credential_state): State<CredentialState<C>>,
+    context: RequestContext,
+    Path(user_id): Path<Uuid>,
+    Json(set_password_request): Json<SetPasswordRequest>,
+) -> impl IntoResponse {
+    credential_state
+        .credential_service
+        .set_password(&context, &user_id, &set_password_request)
+        .await
+        .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
+        .unwrap_or_else(|e| AxumCredentialError(e).into_response())
+}
@@ -0,0 +1,66 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        credential::{CredentialServiceTrait, SetRolesRequest},
+    },
+    inbound::axum_adapter::setup::CredentialState,
+};
+
+use super::credential_error::AxumCredentialError;
+
+#[utoipa::path(
+    put,
+    tag = "User",
+    path = "/user/{user_id}/roles",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    params(
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    request_body = SetRolesRequest,
+    responses(
+        (
+            status = 204,
+            description = "Roles set"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "User or password not found"
+        )
+    ),
+)]
+pub async fn set_roles<C: CredentialServiceTrait>(
+    State(credential_state): State<CredentialState<C>>,
+    context: RequestContext,
+    Path(user_id): Path<Uuid>,
+    Json(set_roles_request): Json<SetRolesRequest>,
+) -> impl IntoResponse {
+    credential_state
+        .credential_service
+        .set_roles(&context, &user_id, &set_roles_request)
+        .await
+        .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
+        .unwrap_or_else(|e| AxumCredentialError(e).into_response())
+}
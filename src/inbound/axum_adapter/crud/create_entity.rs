@@ -0,0 +1,24 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        crud::{CrudEntityTrait, CrudServiceTrait},
+    },
+    inbound::axum_adapter::setup::CrudState,
+};
+
+use super::crud_error::AxumCrudError;
+
+pub async fn create_entity<S: CrudServiceTrait>(
+    State(crud_state): State<CrudState<S>>,
+    context: RequestContext,
+    Json(add_request): Json<<S::Entity as CrudEntityTrait>::AddRequest>,
+) -> impl IntoResponse {
+    crud_state
+        .service
+        .create(&context, &add_request)
+        .await
+        .map(|e| (StatusCode::CREATED, Json(e)).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
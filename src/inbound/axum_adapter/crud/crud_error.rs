@@ -0,0 +1,24 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+use crate::business::crud::{CrudErrorKind, CrudErrorTrait};
+
+pub struct AxumCrudError<E: CrudErrorTrait>(pub E);
+
+impl<E: CrudErrorTrait> IntoResponse for AxumCrudError<E> {
+    fn into_response(self) -> Response {
+        match self.0.kind() {
+            CrudErrorKind::NotFound => (StatusCode::NOT_FOUND, ()).into_response(),
+            CrudErrorKind::Conflict => (StatusCode::CONFLICT, self.0.to_string()).into_response(),
+            CrudErrorKind::Invalid => (StatusCode::BAD_REQUEST, self.0.to_string()).into_response(),
+            CrudErrorKind::Forbidden => (StatusCode::FORBIDDEN, self.0.to_string()).into_response(),
+            CrudErrorKind::Internal => {
+                error!(error = %self.0, "crud request failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, self.0.to_string()).into_response()
+            }
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, crud::CrudServiceTrait},
+    inbound::axum_adapter::setup::CrudState,
+};
+
+use super::crud_error::AxumCrudError;
+
+pub async fn delete_entity<S: CrudServiceTrait>(
+    State(crud_state): State<CrudState<S>>,
+    context: RequestContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    crud_state
+        .service
+        .delete(&context, &id)
+        .await
+        .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
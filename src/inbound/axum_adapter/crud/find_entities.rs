@@ -0,0 +1,29 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        crud::{CrudEntityTrait, CrudServiceTrait},
+    },
+    inbound::axum_adapter::setup::CrudState,
+};
+
+use super::crud_error::AxumCrudError;
+
+pub async fn find_entities<S: CrudServiceTrait>(
+    State(crud_state): State<CrudState<S>>,
+    context: RequestContext,
+    Query(find_request): Query<<S::Entity as CrudEntityTrait>::FindRequest>,
+) -> impl IntoResponse {
+    crud_state
+        .service
+        .find(&context, &find_request)
+        .await
+        .map(|page| (StatusCode::OK, Json(page)).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
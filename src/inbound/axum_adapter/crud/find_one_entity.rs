@@ -0,0 +1,27 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, crud::CrudServiceTrait},
+    inbound::axum_adapter::setup::CrudState,
+};
+
+use super::crud_error::AxumCrudError;
+
+pub async fn find_one_entity<S: CrudServiceTrait>(
+    State(crud_state): State<CrudState<S>>,
+    context: RequestContext,
+    Path(id): Path<Uuid>,
+) -> impl IntoResponse {
+    crud_state
+        .service
+        .find_one(&context, &id)
+        .await
+        .map(|e| (StatusCode::OK, Json(e)).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
@@ -0,0 +1,425 @@
+pub mod create_entity;
+pub mod crud_error;
+pub mod delete_entity;
+pub mod find_entities;
+pub mod find_one_entity;
+pub mod update_entity;
+
+use axum::{
+    routing::{delete, get, post, put},
+    Router,
+};
+use create_entity::create_entity;
+use delete_entity::delete_entity;
+use find_entities::find_entities;
+use find_one_entity::find_one_entity;
+use update_entity::update_entity;
+use utoipa::{
+    openapi::{
+        path::{HttpMethod, Operation, OperationBuilder, ParameterBuilder, ParameterIn, PathItem},
+        request_body::RequestBodyBuilder,
+        schema::{ArrayBuilder, KnownFormat, ObjectBuilder, SchemaFormat, Type},
+        security::SecurityRequirement,
+        ComponentsBuilder, ContentBuilder, OpenApi, OpenApiBuilder, PathsBuilder, Ref, RefOr,
+        Required, ResponseBuilder, Schema,
+    },
+    IntoParams, ToSchema,
+};
+
+use crate::business::crud::{CrudEntityTrait, CrudServiceTrait};
+
+use super::setup::CrudState;
+
+/// Create, list, read, update and delete routes of a CRUD entity, to nest
+/// under its path.
+pub async fn init_route<S: CrudServiceTrait>() -> Router<CrudState<S>> {
+    Router::new()
+        .route("/", post(create_entity))
+        .route("/", get(find_entities))
+        .route("/:id", put(update_entity))
+        .route("/:id", get(find_one_entity))
+        .route("/:id", delete(delete_entity))
+}
+
+fn json(schema: impl Into<RefOr<Schema>>) -> utoipa::openapi::Content {
+    ContentBuilder::new().schema(Some(schema)).build()
+}
+
+fn operation(
+    tag: &str,
+    operation_id: String,
+    responses: Vec<(&str, String, Option<RefOr<Schema>>)>,
+) -> OperationBuilder {
+    let mut operation = OperationBuilder::new()
+        .tag(tag)
+        .operation_id(Some(operation_id))
+        .security(SecurityRequirement::new(
+            "bearer_auth",
+            Vec::<String>::new(),
+        ))
+        .security(SecurityRequirement::new("api_key", Vec::<String>::new()));
+    let responses = responses.into_iter().chain([
+        ("401", String::from("Authentication required"), None),
+        ("403", String::from("Operation forbidden"), None),
+    ]);
+    for (status, description, schema) in responses {
+        let mut response = ResponseBuilder::new().description(description);
+        if let Some(schema) = schema {
+            response = response.content("application/json", json(schema));
+        }
+        operation = operation.response(status, response.build());
+    }
+    operation
+}
+
+fn id_parameter(tag: &str) -> utoipa::openapi::path::Parameter {
+    ParameterBuilder::new()
+        .name("id")
+        .parameter_in(ParameterIn::Path)
+        .required(Required::True)
+        .description(Some(format!("{tag} identifier")))
+        .schema(Some(
+            ObjectBuilder::new()
+                .schema_type(Type::String)
+                .format(Some(SchemaFormat::KnownFormat(KnownFormat::Uuid))),
+        ))
+        .build()
+}
+
+fn components<T: ToSchema>(components: ComponentsBuilder) -> ComponentsBuilder {
+    let mut schemas = Vec::new();
+    T::schemas(&mut schemas);
+    components
+        .schema(T::name(), T::schema())
+        .schemas_from_iter(schemas)
+}
+
+/// Documents the routes of `init_route` nested under `path`.
+pub fn api_docs<S: CrudServiceTrait>(path: &str, tag: &str) -> OpenApi {
+    type Entity<S> = <S as CrudServiceTrait>::Entity;
+    type AddRequest<S> = <Entity<S> as CrudEntityTrait>::AddRequest;
+    type UpdateRequest<S> = <Entity<S> as CrudEntityTrait>::UpdateRequest;
+    type FindParams<S> = <Entity<S> as CrudEntityTrait>::FindParams;
+
+    let name = tag.to_lowercase();
+    let entity: RefOr<Schema> = Ref::from_schema_name(Entity::<S>::name()).into();
+    let page: RefOr<Schema> = ObjectBuilder::new()
+        .property(
+            "items",
+            ArrayBuilder::new().items(Ref::from_schema_name(Entity::<S>::name())),
+        )
+        .required("items")
+        .property("num_pages", ObjectBuilder::new().schema_type(Type::Integer))
+        .required("num_pages")
+        .into();
+    let body = |schema: String| {
+        Some(
+            RequestBodyBuilder::new()
+                .content("application/json", json(Ref::from_schema_name(schema)))
+                .required(Some(Required::True))
+                .build(),
+        )
+    };
+    let create: Operation = operation(
+        tag,
+        format!("create_{name}"),
+        vec![
+            (
+                "201",
+                format!("{tag} creation succeed"),
+                Some(entity.clone()),
+            ),
+            ("400", String::from("Data sent not correct"), None),
+            ("409", format!("{tag} conflicts with another"), None),
+        ],
+    )
+    .request_body(body(AddRequest::<S>::name().to_string()))
+    .build();
+    let find: Operation = operation(
+        tag,
+        format!("find_{name}"),
+        vec![("200", format!("{tag} list succeed"), Some(page))],
+    )
+    .parameters(Some(FindParams::<S>::into_params(|| {
+        Some(ParameterIn::Query)
+    })))
+    .build();
+    let update: Operation = operation(
+        tag,
+        format!("update_{name}"),
+        vec![
+            ("200", format!("{tag} update succeed"), Some(entity.clone())),
+            ("400", String::from("Sent data not correct"), None),
+            ("404", format!("{tag} not found"), None),
+            ("409", format!("{tag} conflicts with another"), None),
+        ],
+    )
+    .parameter(id_parameter(tag))
+    .request_body(body(UpdateRequest::<S>::name().to_string()))
+    .build();
+    let find_one: Operation = operation(
+        tag,
+        format!("find_one_{name}"),
+        vec![
+            ("200", format!("{tag} found"), Some(entity)),
+            ("404", format!("{tag} not found"), None),
+        ],
+    )
+    .parameter(id_parameter(tag))
+    .build();
+    let delete: Operation = operation(
+        tag,
+        format!("delete_{name}"),
+        vec![
+            ("204", format!("{tag} deletion succeed"), None),
+            ("404", format!("{tag} not found"), None),
+        ],
+    )
+    .parameter(id_parameter(tag))
+    .build();
+
+    let item_path = format!("{path}/{{id}}");
+    let paths = PathsBuilder::new()
+        .path(path, PathItem::new(HttpMethod::Post, create))
+        .path(path, PathItem::new(HttpMethod::Get, find))
+        .path(&item_path, PathItem::new(HttpMethod::Put, update))
+        .path(&item_path, PathItem::new(HttpMethod::Get, find_one))
+        .path(&item_path, PathItem::new(HttpMethod::Delete, delete));
+    let schemas =
+        components::<UpdateRequest<S>>(components::<AddRequest<S>>(components::<Entity<S>>(
+            ComponentsBuilder::new(),
+        )));
+    OpenApiBuilder::new()
+        .paths(paths)
+        .components(Some(schemas.build()))
+        .build()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{cmp::Ordering, sync::Arc};
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        Extension, Router,
+    };
+    use http_body_util::BodyExt;
+    use serde::{Deserialize, Serialize};
+    use thiserror::Error;
+    use tower::ServiceExt;
+    use utoipa::{IntoParams, ToSchema};
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::{AuthenticationMethod, Principal, Role},
+            crud::{
+                service::crud_service::CrudService, CrudEntityTrait, CrudErrorKind, CrudErrorTrait,
+                CrudFindRequestTrait,
+            },
+            user::UserOperation,
+        },
+        inbound::axum_adapter::setup::CrudState,
+        outbound::{
+            in_memory_repository_adapter::in_memory_crud_repository::InMemoryCrudRepository,
+            repository_trait::FindOptionTrait,
+        },
+    };
+
+    #[derive(Clone, Serialize, ToSchema)]
+    struct Tag {
+        id: Uuid,
+        label: String,
+    }
+
+    #[derive(Deserialize, ToSchema)]
+    struct TagRequest {
+        label: String,
+    }
+
+    #[derive(Clone, Deserialize, IntoParams)]
+    struct TagFindRequest {
+        label: Option<String>,
+    }
+
+    #[derive(Debug, Error)]
+    enum TagError {
+        #[error("Tag {0} not found")]
+        NotFound(Uuid),
+        #[error("Tag ids {0} and {1} mismatch")]
+        Mismatch(Uuid, Uuid),
+        #[error("Tag label must not be empty")]
+        EmptyLabel,
+        #[error("Tag label {0} already used")]
+        Duplicated(String),
+        #[error("Operation {0} forbidden")]
+        Forbidden(UserOperation),
+    }
+
+    impl CrudErrorTrait for TagError {
+        fn kind(&self) -> CrudErrorKind {
+            match self {
+                TagError::NotFound(_) => CrudErrorKind::NotFound,
+                TagError::Mismatch(_, _) | TagError::EmptyLabel => CrudErrorKind::Invalid,
+                TagError::Duplicated(_) => CrudErrorKind::Conflict,
+                TagError::Forbidden(_) => CrudErrorKind::Forbidden,
+            }
+        }
+        fn not_exists(id: &Uuid) -> Self {
+            TagError::NotFound(*id)
+        }
+        fn mismatch_id(id1: &Uuid, id2: &Uuid) -> Self {
+            TagError::Mismatch(*id1, *id2)
+        }
+        fn forbidden(operation: UserOperation) -> Self {
+            TagError::Forbidden(operation)
+        }
+    }
+
+    impl FindOptionTrait for TagFindRequest {
+        type QueryFilter = Option<String>;
+        fn get_query(&self) -> Self::QueryFilter {
+            self.label.clone()
+        }
+        fn get_order_by(&self) -> String {
+            String::from("label")
+        }
+    }
+
+    impl CrudFindRequestTrait<Tag> for TagFindRequest {
+        fn matches(&self, entity: &Tag) -> bool {
+            self.label
+                .as_ref()
+                .map_or(true, |label| &entity.label == label)
+        }
+        fn compare(&self, a: &Tag, b: &Tag) -> Ordering {
+            a.label.cmp(&b.label)
+        }
+    }
+
+    impl CrudEntityTrait for Tag {
+        type AddRequest = TagRequest;
+        type UpdateRequest = TagRequest;
+        type FindRequest = TagFindRequest;
+        type FindParams = TagFindRequest;
+        type Error = TagError;
+
+        fn get_id(&self) -> &Uuid {
+            &self.id
+        }
+        fn with_id(&self, id: &Uuid) -> Self {
+            Tag {
+                id: *id,
+                label: self.label.clone(),
+            }
+        }
+        fn from_add_request(req: &TagRequest) -> Result<Self, TagError> {
+            Self::from_update_request(&Uuid::nil(), req)
+        }
+        fn from_update_request(id: &Uuid, req: &TagRequest) -> Result<Self, TagError> {
+            if req.label.trim().is_empty() {
+                return Err(TagError::EmptyLabel);
+            }
+            Ok(Tag {
+                id: *id,
+                label: req.label.clone(),
+            })
+        }
+        fn conflict_with(&self, other: &Self) -> Option<TagError> {
+            (self.label == other.label).then(|| TagError::Duplicated(self.label.clone()))
+        }
+    }
+
+    type TagService = CrudService<Tag, InMemoryCrudRepository<Tag>>;
+
+    async fn router(roles: &[Role]) -> Router {
+        let principal = Principal::new("tester", None, roles, AuthenticationMethod::ApiKey);
+        Router::new()
+            .nest(
+                "/tag",
+                super::init_route::<TagService>()
+                    .await
+                    .with_state(CrudState {
+                        service: Arc::new(CrudService::new(InMemoryCrudRepository::new())),
+                    }),
+            )
+            .layer(Extension(principal))
+    }
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    fn json_request(method: &str, uri: &str, label: &str) -> Request<Body> {
+        Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(format!("{{\"label\":\"{label}\"}}")))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_crud_round_trip() {
+        let app = router(&[Role::new("admin")]).await;
+
+        let (status, created) = send(&app, json_request("POST", "/tag", "rust")).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let id = created["id"].as_str().unwrap().to_string();
+        let (status, _) = send(&app, json_request("POST", "/tag", "axum")).await;
+        assert_eq!(status, StatusCode::CREATED);
+        let (status, _) = send(&app, json_request("POST", "/tag", "rust")).await;
+        assert_eq!(status, StatusCode::CONFLICT);
+        let (status, _) = send(&app, json_request("POST", "/tag", " ")).await;
+        assert_eq!(status, StatusCode::BAD_REQUEST);
+
+        let (status, page) = send(&app, Request::get("/tag").body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["items"][0]["label"], "axum");
+        assert_eq!(page["items"][1]["label"], "rust");
+
+        let uri = format!("/tag/{id}");
+        let (status, updated) = send(&app, json_request("PUT", &uri, "tokio")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(updated["label"], "tokio");
+        let (status, found) = send(&app, Request::get(&uri).body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(found["label"], "tokio");
+
+        let (status, page) = send(
+            &app,
+            Request::get("/tag?label=tokio")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(page["items"].as_array().unwrap().len(), 1);
+
+        let delete = || Request::delete(&uri).body(Body::empty()).unwrap();
+        assert_eq!(send(&app, delete()).await.0, StatusCode::NO_CONTENT);
+        assert_eq!(send(&app, delete()).await.0, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_policy_applies() {
+        let app = router(&[]).await;
+        let (status, _) = send(&app, json_request("POST", "/tag", "rust")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_api_docs() {
+        let docs = super::api_docs::<TagService>("/tag", "Tag");
+        let json = docs.to_json().unwrap();
+        assert!(docs.paths.paths.contains_key("/tag"));
+        assert!(docs.paths.paths.contains_key("/tag/{id}"));
+        assert!(json.contains("\"create_tag\""));
+        assert!(json.contains("#/components/schemas/TagRequest"));
+        assert!(docs.components.unwrap().schemas.contains_key("Tag"));
+    }
+}
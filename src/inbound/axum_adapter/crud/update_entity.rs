@@ -0,0 +1,31 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        crud::{CrudEntityTrait, CrudServiceTrait},
+    },
+    inbound::axum_adapter::setup::CrudState,
+};
+
+use super::crud_error::AxumCrudError;
+
+pub async fn update_entity<S: CrudServiceTrait>(
+    State(crud_state): State<CrudState<S>>,
+    context: RequestContext,
+    Path(id): Path<Uuid>,
+    Json(update_request): Json<<S::Entity as CrudEntityTrait>::UpdateRequest>,
+) -> impl IntoResponse {
+    crud_state
+        .service
+        .update(&context, &id, &update_request)
+        .await
+        .map(|e| (StatusCode::OK, Json(e)).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
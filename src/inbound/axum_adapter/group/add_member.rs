@@ -0,0 +1,63 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, group::GroupServiceTrait},
+    inbound::axum_adapter::{crud::crud_error::AxumCrudError, setup::CrudState},
+};
+
+#[utoipa::path(
+    put,
+    tag = "Group",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/group/{id}/members/{user_id}",
+    params(
+        (
+            "id" = Uuid,
+            Path,
+            description = "Group identifier"
+        ),
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    responses(
+        (
+            status = 204,
+            description = "Member addition succeed"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "Group or user not found"
+        )
+    ),
+)]
+pub async fn add_member<S: GroupServiceTrait>(
+    State(group_state): State<CrudState<S>>,
+    context: RequestContext,
+    Path((group_id, user_id)): Path<(Uuid, Uuid)>,
+) -> impl IntoResponse {
+    group_state
+        .service
+        .add_member(&context, &group_id, &user_id)
+        .await
+        .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
@@ -0,0 +1,59 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, group::GroupServiceTrait},
+    inbound::axum_adapter::{crud::crud_error::AxumCrudError, setup::CrudState},
+};
+
+#[utoipa::path(
+    get,
+    tag = "Group",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/group/{id}/members",
+    params(
+        (
+            "id" = Uuid,
+            Path,
+            description = "Group identifier"
+        )
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Group members list succeed"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "Group not found"
+        )
+    ),
+)]
+pub async fn find_members<S: GroupServiceTrait>(
+    State(group_state): State<CrudState<S>>,
+    context: RequestContext,
+    Path(group_id): Path<Uuid>,
+) -> impl IntoResponse {
+    group_state
+        .service
+        .find_members(&context, &group_id)
+        .await
+        .map(|members| (StatusCode::OK, Json(members)).into_response())
+        .unwrap_or_else(|e| AxumCrudError(e).into_response())
+}
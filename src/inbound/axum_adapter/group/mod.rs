@@ -0,0 +1,137 @@
+pub mod add_member;
+pub mod find_members;
+pub mod remove_member;
+
+use add_member::add_member;
+use axum::{
+    routing::{delete, get, put},
+    Router,
+};
+use find_members::find_members;
+use remove_member::remove_member;
+use utoipa::OpenApi;
+
+use crate::business::group::GroupServiceTrait;
+
+use super::{crud, setup::CrudState};
+
+pub async fn init_route<S: GroupServiceTrait>() -> Router<CrudState<S>> {
+    crud::init_route()
+        .await
+        .route("/:id/members", get(find_members))
+        .route("/:id/members/:user_id", put(add_member))
+        .route("/:id/members/:user_id", delete(remove_member))
+}
+
+pub fn api_docs<S: GroupServiceTrait>() -> utoipa::openapi::OpenApi {
+    #[derive(OpenApi)]
+    #[openapi(paths(
+        crate::inbound::axum_adapter::group::find_members::find_members,
+        crate::inbound::axum_adapter::group::add_member::add_member,
+        crate::inbound::axum_adapter::group::remove_member::remove_member
+    ))]
+    struct ApiDocs;
+    let mut api_docs = crud::api_docs::<S>("/group", "Group");
+    api_docs.merge(ApiDocs::openapi());
+    api_docs
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        Extension, Router,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::{AuthenticationMethod, Principal, Role},
+            group::service::group_service::GroupService,
+            user::{EmailAddress, Name, User},
+        },
+        inbound::axum_adapter::setup::CrudState,
+        outbound::{
+            in_memory_repository_adapter::{
+                in_memory_group_repository::InMemoryGroupRepository,
+                in_memory_user_repository::InMemoryUserRepository,
+            },
+            repository_trait::RepositoryTrait,
+        },
+    };
+
+    async fn send(app: &Router, request: Request<Body>) -> (StatusCode, serde_json::Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let bytes = response.into_body().collect().await.unwrap().to_bytes();
+        (status, serde_json::from_slice(&bytes).unwrap_or_default())
+    }
+
+    #[tokio::test]
+    async fn test_members() {
+        let users = InMemoryUserRepository::new();
+        let user = users
+            .save(&User::new(
+                &Uuid::new_v4(),
+                &Name::new("John").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new("john@example.com").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let admin = Principal::new(
+            "admin",
+            None,
+            &[Role::new("admin")],
+            AuthenticationMethod::ApiKey,
+        );
+        let app = Router::new()
+            .nest(
+                "/group",
+                super::init_route().await.with_state(CrudState {
+                    service: Arc::new(GroupService::new(InMemoryGroupRepository::new(), users)),
+                }),
+            )
+            .layer(Extension(admin));
+
+        let (status, group) = send(
+            &app,
+            Request::post("/group")
+                .header(header::CONTENT_TYPE, "application/json")
+                .body(Body::from("{\"name\":\"Core\"}"))
+                .unwrap(),
+        )
+        .await;
+        assert_eq!(status, StatusCode::CREATED);
+        let members = format!("/group/{}/members", group["id"].as_str().unwrap());
+        let member = format!("{members}/{}", user.get_id());
+
+        let put = |uri: &str| Request::put(uri).body(Body::empty()).unwrap();
+        assert_eq!(send(&app, put(&member)).await.0, StatusCode::NO_CONTENT);
+        let unknown_user = format!("{members}/{}", Uuid::new_v4());
+        assert_eq!(
+            send(&app, put(&unknown_user)).await.0,
+            StatusCode::NOT_FOUND
+        );
+        let unknown_group = format!("/group/{}/members/{}", Uuid::new_v4(), user.get_id());
+        assert_eq!(
+            send(&app, put(&unknown_group)).await.0,
+            StatusCode::NOT_FOUND
+        );
+
+        let (status, listed) =
+            send(&app, Request::get(&members).body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(listed[0]["email"], "john@example.com");
+
+        let (status, _) = send(&app, Request::delete(&member).body(Body::empty()).unwrap()).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        let (_, listed) = send(&app, Request::get(&members).body(Body::empty()).unwrap()).await;
+        assert_eq!(listed, serde_json::json!([]));
+    }
+}
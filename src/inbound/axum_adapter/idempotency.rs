@@ -0,0 +1,395 @@
+use std::{sync::Arc, time::Duration};
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+use tracing::error;
+
+use crate::business::{
+    auth::Principal,
+    idempotency::{IdempotencyStoreTrait, StoredResponse},
+};
+
+use super::{problem::Problem, tenant::RequestedTenant};
+
+pub const IDEMPOTENCY_KEY_HEADER: &str = "idempotency-key";
+pub const IDEMPOTENT_REPLAYED_HEADER: &str = "idempotent-replayed";
+
+const MAX_KEY_LEN: usize = 255;
+const MAX_REQUEST_BODY: usize = 2 * 1024 * 1024;
+const MAX_RESPONSE_BODY: u64 = 2 * 1024 * 1024;
+const STORED_HEADERS: [HeaderName; 2] = [header::CONTENT_TYPE, header::LOCATION];
+
+/// Keeps the first response to a `POST` or `PUT` carrying an
+/// `Idempotency-Key` header and replays it to retries with the same key, for
+/// `ttl`. Keys are scoped to the tenant and the principal. Server errors,
+/// rejected requests and responses over 2 MiB or of unknown length are not
+/// kept, so they can be retried. A request still running holds its key for
+/// `lease` at most, in case it is never completed nor released.
+#[derive(Debug, Clone)]
+pub struct IdempotencyCache<R: IdempotencyStoreTrait> {
+    store: R,
+    ttl: Duration,
+    lease: Duration,
+}
+
+impl<R: IdempotencyStoreTrait> IdempotencyCache<R> {
+    pub fn new(store: R) -> Self {
+        Self {
+            store,
+            ttl: Duration::from_secs(24 * 60 * 60),
+            lease: Duration::from_secs(60),
+        }
+    }
+
+    pub fn with_ttl(mut self, ttl: &Duration) -> Self {
+        self.ttl = *ttl;
+        self
+    }
+
+    pub fn with_lease(mut self, lease: &Duration) -> Self {
+        self.lease = *lease;
+        self
+    }
+
+    pub fn get_ttl(&self) -> &Duration {
+        &self.ttl
+    }
+
+    pub fn get_lease(&self) -> &Duration {
+        &self.lease
+    }
+}
+
+/// Releases the key of a request whose handling is dropped before it
+/// completes, such as when the client disconnects.
+struct Reservation<R: IdempotencyStoreTrait> {
+    store: R,
+    key: Option<String>,
+}
+
+impl<R: IdempotencyStoreTrait> Reservation<R> {
+    async fn release(mut self) {
+        if let Some(key) = self.key.take() {
+            self.store.release(&key).await.ok();
+        }
+    }
+
+    async fn complete(mut self, response: &StoredResponse, ttl: &Duration) {
+        if let Some(key) = self.key.take() {
+            if self.store.complete(&key, response, ttl).await.is_err() {
+                self.store.release(&key).await.ok();
+            }
+        }
+    }
+}
+
+impl<R: IdempotencyStoreTrait> Drop for Reservation<R> {
+    fn drop(&mut self) {
+        if let Some(key) = self.key.take() {
+            let store = self.store.clone();
+            tokio::spawn(async move { store.release(&key).await.ok() });
+        }
+    }
+}
+
+fn problem(status: StatusCode, detail: &str) -> Response {
+    Problem::new(status).with_detail(detail).into_response()
+}
+
+/// The tenant the request acts for, resolved as for its `RequestContext`, and
+/// its principal.
+fn scope(request: &Request) -> String {
+    let principal = request.extensions().get::<Principal>();
+    let tenant = match request.extensions().get::<RequestedTenant>() {
+        Some(requested) => requested.0.clone(),
+        None => principal
+            .and_then(Principal::get_tenant)
+            .cloned()
+            .unwrap_or_default(),
+    };
+    let subject = principal.map_or("", Principal::get_subject);
+    format!("{tenant}:{subject}")
+}
+
+fn replay(response: &StoredResponse) -> Response {
+    let status = StatusCode::from_u16(response.get_status()).unwrap_or(StatusCode::OK);
+    let mut replayed = (status, response.get_body().to_vec()).into_response();
+    let headers = replayed.headers_mut();
+    for (name, value) in response.get_headers() {
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::try_from(name.as_str()),
+            HeaderValue::from_str(value),
+        ) {
+            headers.insert(name, value);
+        }
+    }
+    headers.insert(IDEMPOTENT_REPLAYED_HEADER, HeaderValue::from_static("true"));
+    replayed
+}
+
+pub async fn idempotency<R: IdempotencyStoreTrait>(
+    State(cache): State<Arc<IdempotencyCache<R>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    if !matches!(*request.method(), Method::POST | Method::PUT) {
+        return next.run(request).await;
+    }
+    let Some(key) = request.headers().get(IDEMPOTENCY_KEY_HEADER) else {
+        return next.run(request).await;
+    };
+    let Some(key) = key
+        .to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= MAX_KEY_LEN)
+        .map(|key| format!("{}|{}", scope(&request), key))
+    else {
+        return problem(
+            StatusCode::BAD_REQUEST,
+            "Idempotency-Key must have 1 to 255 visible ASCII characters",
+        );
+    };
+    let (parts, body) = request.into_parts();
+    let Ok(body) = to_bytes(body, MAX_REQUEST_BODY).await else {
+        return problem(StatusCode::PAYLOAD_TOO_LARGE, "Request body is too large");
+    };
+    let mut hasher = Sha256::new();
+    hasher.update(parts.method.as_str());
+    hasher.update(b" ");
+    hasher.update(parts.uri.to_string());
+    hasher.update(b"\n");
+    hasher.update(&body);
+    let fingerprint = hex::encode(hasher.finalize());
+
+    match cache.store.begin(&key, &fingerprint, &cache.lease).await {
+        Err(error) => {
+            error!(%error, "idempotency store failed");
+            problem(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "Idempotency-Key could not be checked",
+            )
+        }
+        Ok(Some(record)) if record.get_fingerprint().ne(&fingerprint) => problem(
+            StatusCode::UNPROCESSABLE_ENTITY,
+            "Idempotency-Key was already used for a different request",
+        ),
+        Ok(Some(record)) => match record.get_response() {
+            Some(response) => replay(response),
+            None => problem(
+                StatusCode::CONFLICT,
+                "A request with this Idempotency-Key is still being processed",
+            ),
+        },
+        Ok(None) => {
+            let reservation = Reservation {
+                store: cache.store.clone(),
+                key: Some(key),
+            };
+            let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+            let status = response.status();
+            let length = response.body().size_hint().exact();
+            if status.is_server_error()
+                || status == StatusCode::TOO_MANY_REQUESTS
+                || length.map_or(true, |length| length > MAX_RESPONSE_BODY)
+            {
+                reservation.release().await;
+                return response;
+            }
+            let (parts, body) = response.into_parts();
+            let Ok(body) = to_bytes(body, MAX_RESPONSE_BODY as usize).await else {
+                reservation.release().await;
+                return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            };
+            let headers = STORED_HEADERS
+                .iter()
+                .filter_map(|name| {
+                    let value = parts.headers.get(name)?.to_str().ok()?;
+                    Some((name.to_string(), value.to_string()))
+                })
+                .collect::<Vec<_>>();
+            let stored = StoredResponse::new(status.as_u16(), &headers, &body);
+            reservation.complete(&stored, &cache.ttl).await;
+            Response::from_parts(parts, Body::from(body))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{
+            atomic::{AtomicUsize, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use axum::{
+        body::Body,
+        http::{header, Request, StatusCode},
+        middleware,
+        response::Response,
+        routing::{post, put},
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::{
+        business::auth::TenantId, inbound::axum_adapter::tenant::RequestedTenant,
+        outbound::in_memory_repository_adapter::in_memory_idempotency_store::InMemoryIdempotencyStore,
+    };
+
+    use super::{
+        idempotency, IdempotencyCache, IDEMPOTENCY_KEY_HEADER, IDEMPOTENT_REPLAYED_HEADER,
+        MAX_RESPONSE_BODY,
+    };
+
+    fn app() -> (Router, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let router = Router::new()
+            .route(
+                "/user",
+                post(move |body: String| async move {
+                    let call = counter.fetch_add(1, Ordering::SeqCst) + 1;
+                    if body == "slow" && call == 1 {
+                        std::future::pending::<()>().await;
+                    }
+                    let status = if body == "fail" {
+                        StatusCode::SERVICE_UNAVAILABLE
+                    } else {
+                        StatusCode::CREATED
+                    };
+                    (
+                        status,
+                        [(header::LOCATION, format!("/user/{call}"))],
+                        format!("{call}"),
+                    )
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(IdempotencyCache::new(InMemoryIdempotencyStore::new())),
+                idempotency::<InMemoryIdempotencyStore>,
+            ));
+        (router, calls)
+    }
+
+    async fn create(app: &Router, key: Option<&str>, body: &'static str) -> Response {
+        let mut request = Request::post("/user");
+        if let Some(key) = key {
+            request = request.header(IDEMPOTENCY_KEY_HEADER, key);
+        }
+        app.clone()
+            .oneshot(request.body(Body::from(body)).unwrap())
+            .await
+            .unwrap()
+    }
+
+    async fn text(response: Response) -> String {
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        String::from_utf8(body.to_vec()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_replay() {
+        let (app, calls) = app();
+        let first = create(&app, Some("k1"), "john").await;
+        assert_eq!(first.status(), StatusCode::CREATED);
+        assert!(!first.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+        assert_eq!(text(first).await, "1");
+
+        let retry = create(&app, Some("k1"), "john").await;
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert_eq!(retry.headers()[header::LOCATION], "/user/1");
+        assert_eq!(retry.headers()[IDEMPOTENT_REPLAYED_HEADER], "true");
+        assert_eq!(text(retry).await, "1");
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+
+        let reused = create(&app, Some("k1"), "jane").await;
+        assert_eq!(reused.status(), StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(text(create(&app, Some("k2"), "john").await).await, "2");
+        assert_eq!(text(create(&app, None, "john").await).await, "3");
+    }
+
+    #[tokio::test]
+    async fn test_put_replayed_and_large_responses_not_kept() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let (updates, exports) = (calls.clone(), calls.clone());
+        let app = Router::new()
+            .route(
+                "/user/1",
+                put(move || async move { format!("{}", updates.fetch_add(1, Ordering::SeqCst)) }),
+            )
+            .route(
+                "/user/export",
+                post(move || async move {
+                    exports.fetch_add(1, Ordering::SeqCst);
+                    "x".repeat(MAX_RESPONSE_BODY as usize + 1)
+                }),
+            )
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(IdempotencyCache::new(InMemoryIdempotencyStore::new())),
+                idempotency::<InMemoryIdempotencyStore>,
+            ));
+        for (request, key) in [
+            (Request::put("/user/1"), "k1"),
+            (Request::put("/user/1"), "k1"),
+            (Request::post("/user/export"), "k2"),
+            (Request::post("/user/export"), "k2"),
+        ] {
+            let request = request
+                .header(IDEMPOTENCY_KEY_HEADER, key)
+                .body(Body::empty())
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn test_server_errors_not_kept() {
+        let (app, calls) = app();
+        for _ in 0..2 {
+            let response = create(&app, Some("k1"), "fail").await;
+            assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        let invalid = create(&app, Some(""), "john").await;
+        assert_eq!(invalid.status(), StatusCode::BAD_REQUEST);
+    }
+
+    #[tokio::test]
+    async fn test_keys_scoped_to_tenant() {
+        let (app, calls) = app();
+        for tenant in ["acme", "globex"] {
+            let request = Request::post("/user")
+                .header(IDEMPOTENCY_KEY_HEADER, "k1")
+                .extension(RequestedTenant(TenantId::new(tenant).unwrap()))
+                .body(Body::from("john"))
+                .unwrap();
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert!(!response.headers().contains_key(IDEMPOTENT_REPLAYED_HEADER));
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_request_releases_key() {
+        let (app, calls) = app();
+        let abandoned =
+            tokio::time::timeout(Duration::from_millis(50), create(&app, Some("k1"), "slow")).await;
+        assert!(abandoned.is_err());
+        tokio::time::sleep(Duration::from_millis(10)).await;
+        let retry = create(&app, Some("k1"), "slow").await;
+        assert_eq!(retry.status(), StatusCode::CREATED);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
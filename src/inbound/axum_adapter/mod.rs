@@ -1,2 +1,13 @@
+pub mod auth;
+pub mod credential;
+pub mod crud;
+pub mod group;
+pub mod idempotency;
+pub mod problem;
+pub mod rate_limit;
+pub mod request_id;
 pub mod setup;
+pub mod tenant;
+pub mod trace;
 pub mod user;
+pub mod webhook;
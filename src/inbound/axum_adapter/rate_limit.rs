@@ -0,0 +1,492 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    path::Path,
+    sync::Arc,
+    time::Duration,
+};
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath, Request, State},
+    http::{header, HeaderMap, HeaderName, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+use tracing::warn;
+
+use crate::business::{
+    auth::Principal,
+    rate_limit::{RateLimit, RateLimitDecision, RateLimitError, RateLimitStoreTrait},
+};
+
+use super::{auth::API_KEY_HEADER, problem::Problem};
+
+pub const RATE_LIMIT_LIMIT_HEADER: &str = "ratelimit-limit";
+pub const RATE_LIMIT_REMAINING_HEADER: &str = "ratelimit-remaining";
+pub const RATE_LIMIT_RESET_HEADER: &str = "ratelimit-reset";
+pub const RATE_LIMIT_POLICY_HEADER: &str = "ratelimit-policy";
+
+/// What identifies a client, tried in order until one is present.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RateLimitKey {
+    Principal,
+    ApiKey,
+    Ip,
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RateLimiterConfig {
+    default: Option<RateLimit>,
+    per_ip: Option<RateLimit>,
+    #[serde(default)]
+    routes: BTreeMap<String, RateLimit>,
+    keys: Option<Vec<RateLimitKey>>,
+    forwarded_header: Option<String>,
+}
+
+/// Token-bucket rate limiting per client, with a bucket for each route that
+/// has its own limit and one shared by the others. Routes are named by method
+/// and matched path, such as `POST /user` or `GET /user/:user_id`. A bucket
+/// per IP address may also limit every request before it is authenticated.
+#[derive(Debug, Clone)]
+pub struct RateLimiter<R: RateLimitStoreTrait> {
+    store: R,
+    default_limit: Option<RateLimit>,
+    ip_limit: Option<RateLimit>,
+    route_limits: HashMap<String, RateLimit>,
+    keys: Vec<RateLimitKey>,
+    forwarded_header: Option<HeaderName>,
+}
+
+fn invalid_config(reason: impl Into<String>) -> RateLimitError {
+    RateLimitError::InvalidConfig {
+        reason: reason.into(),
+    }
+}
+
+impl<R: RateLimitStoreTrait> RateLimiter<R> {
+    /// Limits nothing until limits are added.
+    pub fn new(store: R) -> Self {
+        Self {
+            store,
+            default_limit: None,
+            ip_limit: None,
+            route_limits: HashMap::new(),
+            keys: vec![
+                RateLimitKey::Principal,
+                RateLimitKey::ApiKey,
+                RateLimitKey::Ip,
+            ],
+            forwarded_header: None,
+        }
+    }
+
+    pub fn with_default_limit(mut self, limit: &RateLimit) -> Self {
+        self.default_limit = Some(*limit);
+        self
+    }
+
+    /// Limits every request of an IP address, authenticated or not.
+    pub fn with_ip_limit(mut self, limit: &RateLimit) -> Self {
+        self.ip_limit = Some(*limit);
+        self
+    }
+
+    pub fn with_route_limit(mut self, method: &Method, path: &str, limit: &RateLimit) -> Self {
+        self.route_limits.insert(route(method, path), *limit);
+        self
+    }
+
+    pub fn with_keys(mut self, keys: &[RateLimitKey]) -> Self {
+        self.keys = keys.to_vec();
+        self
+    }
+
+    /// Reads the client address from a header set by a trusted proxy, such as
+    /// `x-forwarded-for`, instead of the connection.
+    pub fn with_forwarded_header(mut self, header: HeaderName) -> Self {
+        self.forwarded_header = Some(header);
+        self
+    }
+
+    pub fn from_json(store: R, content: &[u8]) -> Result<Self, RateLimitError> {
+        let config: RateLimiterConfig =
+            serde_json::from_slice(content).map_err(|e| invalid_config(e.to_string()))?;
+        let mut limiter = Self::new(store);
+        limiter.default_limit = config.default;
+        limiter.ip_limit = config.per_ip;
+        for (name, limit) in config.routes {
+            let (method, path) = name
+                .split_once(' ')
+                .ok_or_else(|| invalid_config(format!("{name} is not a method and a path")))?;
+            let method = Method::from_bytes(method.as_bytes())
+                .map_err(|_| invalid_config(format!("{method} is not a method")))?;
+            limiter = limiter.with_route_limit(&method, path.trim(), &limit);
+        }
+        if let Some(keys) = config.keys {
+            limiter = limiter.with_keys(&keys);
+        }
+        if let Some(forwarded_header) = config.forwarded_header {
+            let header = HeaderName::try_from(forwarded_header)
+                .map_err(|e| invalid_config(e.to_string()))?;
+            limiter = limiter.with_forwarded_header(header);
+        }
+        Ok(limiter)
+    }
+
+    pub fn from_file(store: R, path: &Path) -> Result<Self, RateLimitError> {
+        let content = std::fs::read(path)
+            .map_err(|e| invalid_config(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(store, &content)
+    }
+
+    fn client(&self, request: &Request) -> String {
+        self.keys
+            .iter()
+            .find_map(|key| match key {
+                RateLimitKey::Principal => request.extensions().get::<Principal>().map(|p| {
+                    let tenant = p.get_tenant().map(ToString::to_string);
+                    format!(
+                        "principal:{}:{}",
+                        tenant.unwrap_or_default(),
+                        p.get_subject()
+                    )
+                }),
+                RateLimitKey::ApiKey => request
+                    .headers()
+                    .get(API_KEY_HEADER)
+                    .map(|key| format!("api_key:{}", hex::encode(Sha256::digest(key.as_bytes())))),
+                RateLimitKey::Ip => self.client_ip(request).map(|ip| format!("ip:{ip}")),
+            })
+            .unwrap_or_else(|| String::from("anonymous"))
+    }
+
+    fn client_ip(&self, request: &Request) -> Option<String> {
+        if let Some(header) = &self.forwarded_header {
+            return request
+                .headers()
+                .get(header)
+                .and_then(|v| v.to_str().ok())
+                .and_then(|v| v.split(',').next())
+                .map(|ip| ip.trim().to_string())
+                .filter(|ip| !ip.is_empty());
+        }
+        request
+            .extensions()
+            .get::<ConnectInfo<SocketAddr>>()
+            .map(|ConnectInfo(address)| address.ip().to_string())
+    }
+
+    /// The bucket of the request and its limit, if it is limited at all.
+    fn bucket(&self, request: &Request) -> Option<(String, RateLimit)> {
+        let name = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map(|path| route(request.method(), path.as_str()));
+        match name.and_then(|name| self.route_limits.get(&name).map(|limit| (name, limit))) {
+            Some((name, limit)) => Some((format!("{}|{}", self.client(request), name), *limit)),
+            None => self
+                .default_limit
+                .map(|limit| (format!("{}|*", self.client(request)), limit)),
+        }
+    }
+}
+
+/// Runs the request if `bucket` has a token left, or answers it with `429`.
+/// Requests go through if the store fails.
+async fn acquire<R: RateLimitStoreTrait>(
+    limiter: &RateLimiter<R>,
+    bucket: &str,
+    limit: &RateLimit,
+    request: Request,
+    next: Next,
+) -> Response {
+    match limiter.store.acquire(bucket, limit).await {
+        Ok(decision) => {
+            let mut response = if decision.is_allowed() {
+                next.run(request).await
+            } else {
+                Problem::new(StatusCode::TOO_MANY_REQUESTS)
+                    .with_detail("Rate limit exceeded, retry later")
+                    .into_response()
+            };
+            insert_headers(response.headers_mut(), &decision);
+            response
+        }
+        Err(error) => {
+            warn!(%error, "rate limit store failed, letting the request through");
+            next.run(request).await
+        }
+    }
+}
+
+fn route(method: &Method, path: &str) -> String {
+    format!("{method} {path}")
+}
+
+fn seconds(duration: &Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+fn insert_headers(headers: &mut HeaderMap, decision: &RateLimitDecision) {
+    let limit = decision.get_limit();
+    for (name, value) in [
+        (RATE_LIMIT_LIMIT_HEADER, limit.get_requests().to_string()),
+        (
+            RATE_LIMIT_REMAINING_HEADER,
+            decision.get_remaining().to_string(),
+        ),
+        (
+            RATE_LIMIT_RESET_HEADER,
+            seconds(decision.get_reset()).to_string(),
+        ),
+        (
+            RATE_LIMIT_POLICY_HEADER,
+            format!("{};w={}", limit.get_requests(), seconds(limit.get_period())),
+        ),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(&value) {
+            headers.insert(name, value);
+        }
+    }
+    if let Some(retry_after) = decision.get_retry_after() {
+        headers.insert(header::RETRY_AFTER, HeaderValue::from(seconds(retry_after)));
+    }
+}
+
+/// Route layer, to be applied inside authentication so that requests can be
+/// keyed by principal.
+pub async fn rate_limit<R: RateLimitStoreTrait>(
+    State(limiter): State<Arc<RateLimiter<R>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let Some((bucket, limit)) = limiter.bucket(&request) else {
+        return next.run(request).await;
+    };
+    acquire(&limiter, &bucket, &limit, request, next).await
+}
+
+/// Layer of the whole router, applied outside authentication so that
+/// requests failing it, such as guesses of API keys, are limited too.
+pub async fn rate_limit_ip<R: RateLimitStoreTrait>(
+    State(limiter): State<Arc<RateLimiter<R>>>,
+    request: Request,
+    next: Next,
+) -> Response {
+    let (Some(limit), Some(ip)) = (limiter.ip_limit, limiter.client_ip(&request)) else {
+        return next.run(request).await;
+    };
+    acquire(&limiter, &format!("ip:{ip}|per_ip"), &limit, request, next).await
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use axum::{
+        body::Body,
+        extract::Request,
+        http::{header, Method, StatusCode},
+        middleware::{self, Next},
+        response::IntoResponse,
+        routing::get,
+        Router,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::{
+        business::{
+            auth::{AuthenticationMethod, Principal},
+            rate_limit::RateLimit,
+        },
+        outbound::in_memory_repository_adapter::in_memory_rate_limit_store::InMemoryRateLimitStore,
+    };
+
+    use super::{
+        rate_limit, rate_limit_ip, RateLimiter, RATE_LIMIT_LIMIT_HEADER,
+        RATE_LIMIT_REMAINING_HEADER,
+    };
+
+    async fn authenticate(mut request: Request, next: Next) -> axum::response::Response {
+        if let Some(subject) = request.headers().get("x-subject").cloned() {
+            request.extensions_mut().insert(Principal::new(
+                subject.to_str().unwrap(),
+                None,
+                &[],
+                AuthenticationMethod::ApiKey,
+            ));
+        }
+        next.run(request).await
+    }
+
+    fn app() -> Router {
+        let limit = |requests| RateLimit::new(requests, Duration::from_secs(60)).unwrap();
+        let limiter = RateLimiter::new(InMemoryRateLimitStore::new())
+            .with_default_limit(&limit(3))
+            .with_route_limit(&Method::POST, "/user", &limit(1))
+            .with_forwarded_header(header::FORWARDED);
+        let user = Router::new()
+            .route("/", get(|| async { "list" }).post(|| async { "create" }))
+            .route("/:user_id", get(|| async { "one" }));
+        Router::new()
+            .nest("/user", user)
+            .route_layer(middleware::from_fn_with_state(
+                Arc::new(limiter),
+                rate_limit::<InMemoryRateLimitStore>,
+            ))
+            .route_layer(middleware::from_fn(authenticate))
+    }
+
+    async fn call(app: &Router, method: Method, uri: &str, client: (&str, &str)) -> StatusCode {
+        let request = Request::builder()
+            .method(method)
+            .uri(uri)
+            .header(client.0, client.1)
+            .body(Body::empty())
+            .unwrap();
+        app.clone().oneshot(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn test_route_limit() {
+        let app = app();
+        let alice = ("x-subject", "alice");
+        assert_eq!(
+            call(&app, Method::POST, "/user", alice).await,
+            StatusCode::OK
+        );
+        let response = app
+            .clone()
+            .oneshot(
+                Request::post("/user")
+                    .header("x-subject", "alice")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(response.headers()[RATE_LIMIT_LIMIT_HEADER], "1");
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING_HEADER], "0");
+        assert_eq!(response.headers()[header::RETRY_AFTER], "60");
+        assert_eq!(
+            response.headers()[header::CONTENT_TYPE],
+            "application/problem+json"
+        );
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        let problem: serde_json::Value = serde_json::from_slice(&body).unwrap();
+        assert_eq!(problem["status"], 429);
+
+        assert_eq!(
+            call(&app, Method::GET, "/user", alice).await,
+            StatusCode::OK
+        );
+        let bob = ("x-subject", "bob");
+        assert_eq!(call(&app, Method::POST, "/user", bob).await, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_default_limit_shared() {
+        let app = app();
+        let client = (header::FORWARDED.as_str(), "192.0.2.1");
+        assert_eq!(
+            call(&app, Method::GET, "/user", client).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            call(&app, Method::GET, "/user/1", client).await,
+            StatusCode::OK
+        );
+        let response = app
+            .clone()
+            .oneshot(
+                Request::get("/user/2")
+                    .header(client.0, client.1)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[RATE_LIMIT_REMAINING_HEADER], "0");
+        assert_eq!(
+            call(&app, Method::GET, "/user", client).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        let other = (header::FORWARDED.as_str(), "192.0.2.2");
+        assert_eq!(
+            call(&app, Method::GET, "/user", other).await,
+            StatusCode::OK
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ip_limit_before_authentication() {
+        let limiter = Arc::new(
+            RateLimiter::new(InMemoryRateLimitStore::new())
+                .with_ip_limit(&RateLimit::new(2, Duration::from_secs(60)).unwrap())
+                .with_forwarded_header(header::FORWARDED),
+        );
+        let app = Router::new()
+            .route("/user", get(|| async { "list" }))
+            .route_layer(middleware::from_fn(
+                |request: Request, next: Next| async move {
+                    match request.headers().contains_key("x-subject") {
+                        true => next.run(request).await,
+                        false => StatusCode::UNAUTHORIZED.into_response(),
+                    }
+                },
+            ))
+            .layer(middleware::from_fn_with_state(
+                limiter,
+                rate_limit_ip::<InMemoryRateLimitStore>,
+            ));
+        let client = (header::FORWARDED.as_str(), "192.0.2.1");
+        for _ in 0..2 {
+            assert_eq!(
+                call(&app, Method::GET, "/user", client).await,
+                StatusCode::UNAUTHORIZED
+            );
+        }
+        assert_eq!(
+            call(&app, Method::GET, "/user", client).await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+        let other = (header::FORWARDED.as_str(), "192.0.2.2");
+        assert_eq!(
+            call(&app, Method::GET, "/user", other).await,
+            StatusCode::UNAUTHORIZED
+        );
+    }
+
+    #[test]
+    fn test_from_json() {
+        let store = InMemoryRateLimitStore::new();
+        let limiter = RateLimiter::from_json(
+            store.clone(),
+            br#"{"default": {"requests": 100, "period_secs": 60},
+                 "per_ip": {"requests": 600, "period_secs": 60},
+                 "routes": {"POST /user": {"requests": 10, "period_secs": 60}},
+                 "keys": ["api_key", "ip"], "forwarded_header": "x-forwarded-for"}"#,
+        )
+        .unwrap();
+        assert_eq!(limiter.route_limits.len(), 1);
+        assert!(limiter.ip_limit.is_some());
+        assert!(RateLimiter::from_json(
+            store.clone(),
+            br#"{"routes": {"/user": {"requests": 10, "period_secs": 60}}}"#
+        )
+        .is_err());
+        assert!(RateLimiter::from_json(
+            store,
+            br#"{"default": {"requests": 0, "period_secs": 60}}"#
+        )
+        .is_err());
+    }
+}
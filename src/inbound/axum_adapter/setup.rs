@@ -1,50 +1,142 @@
 use std::sync::Arc;
 
-use axum::Router;
+use axum::{middleware, Router};
 use utoipa::OpenApi;
 use utoipa_swagger_ui::SwaggerUi;
-use uuid::Uuid;
 
-use crate::business::user::{
-    dtos::{UserFindRequest, UserFindResponse},
-    model::user::UserError,
-    service::user_service::UserService,
-    User, UserRepositoryTrait,
+use crate::business::{
+    auth::AuthenticatorTrait, credential::CredentialServiceTrait, crud::CrudServiceTrait,
+    group::GroupServiceTrait, idempotency::IdempotencyStoreTrait, rate_limit::RateLimitStoreTrait,
+    user::UserServiceTrait, webhook::WebhookServiceTrait,
 };
 
-use super::user;
+use super::{
+    auth::{authenticate::authenticate, SecurityAddon},
+    credential, group,
+    idempotency::{idempotency, IdempotencyCache},
+    rate_limit::{rate_limit, rate_limit_ip, RateLimiter},
+    request_id::request_id,
+    tenant::{resolve_tenant, TenantResolver},
+    trace::trace_request,
+    user, webhook,
+};
+
+#[derive(Debug, Clone)]
+pub struct AppState<S: UserServiceTrait> {
+    pub user_service: Arc<S>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CredentialState<C: CredentialServiceTrait> {
+    pub credential_service: Arc<C>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CrudState<S: CrudServiceTrait> {
+    pub service: Arc<S>,
+}
+
+#[derive(Debug, Clone)]
+pub struct WebhookState<W: WebhookServiceTrait> {
+    pub webhook_service: Arc<W>,
+}
 
+/// Per-client limits and request replay, applied to every route.
 #[derive(Debug, Clone)]
-pub struct AppState<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
-> {
-    pub user_service: Arc<UserService<U>>,
+pub struct TrafficState<L: RateLimitStoreTrait, I: IdempotencyStoreTrait> {
+    pub rate_limiter: RateLimiter<L>,
+    pub idempotency_cache: IdempotencyCache<I>,
 }
 
 pub async fn setup<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
+    S: UserServiceTrait,
+    C: CredentialServiceTrait,
+    W: WebhookServiceTrait,
+    G: GroupServiceTrait,
+    A: AuthenticatorTrait,
+    L: RateLimitStoreTrait,
+    I: IdempotencyStoreTrait,
 >(
-    app_state: AppState<U>,
+    app_state: AppState<S>,
+    credential_state: CredentialState<C>,
+    webhook_state: WebhookState<W>,
+    group_state: CrudState<G>,
+    tenant_resolver: TenantResolver,
+    authenticator: A,
+    traffic_state: TrafficState<L, I>,
 ) -> Router<()> {
     #[derive(OpenApi)]
-    #[openapi(info(title = "Api documentation"))]
+    #[openapi(info(title = "Api documentation"), modifiers(&SecurityAddon))]
     struct ApiDocs;
     let mut api_docs = ApiDocs::openapi();
-    api_docs.merge(user::api_docs());
+    api_docs.merge(user::api_docs(
+        app_state.user_service.get_attribute_schema(),
+    ));
+    api_docs.merge(credential::api_docs());
+    api_docs.merge(webhook::api_docs());
+    api_docs.merge(group::api_docs::<G>());
+    let authentication = middleware::from_fn_with_state(Arc::new(authenticator), authenticate::<A>);
+    let rate_limiter = Arc::new(traffic_state.rate_limiter);
+    let rate_limit = middleware::from_fn_with_state(rate_limiter.clone(), rate_limit::<L>);
+    let idempotency =
+        middleware::from_fn_with_state(Arc::new(traffic_state.idempotency_cache), idempotency::<I>);
     Router::new()
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api_docs))
-        .nest("/user", user::init_route().await)
-        .with_state(app_state)
+        .nest(
+            "/user",
+            user::init_route()
+                .await
+                .with_state(app_state.clone())
+                .merge(
+                    credential::init_password_route()
+                        .await
+                        .with_state(credential_state.clone()),
+                )
+                .route_layer(idempotency.clone())
+                .route_layer(rate_limit.clone())
+                .route_layer(authentication.clone())
+                .merge(
+                    user::init_public_route()
+                        .await
+                        .with_state(app_state)
+                        .route_layer(idempotency.clone())
+                        .route_layer(rate_limit.clone()),
+                ),
+        )
+        .nest(
+            "/webhook",
+            webhook::init_route()
+                .await
+                .with_state(webhook_state)
+                .route_layer(idempotency.clone())
+                .route_layer(rate_limit.clone())
+                .route_layer(authentication.clone()),
+        )
+        .nest(
+            "/group",
+            group::init_route()
+                .await
+                .with_state(group_state)
+                .route_layer(idempotency)
+                .route_layer(rate_limit.clone())
+                .route_layer(authentication),
+        )
+        // Tokens are not kept for replay, so `/auth` has no idempotency keys.
+        .nest(
+            "/auth",
+            credential::init_auth_route()
+                .await
+                .with_state(credential_state)
+                .route_layer(rate_limit),
+        )
+        .layer(middleware::from_fn_with_state(
+            rate_limiter,
+            rate_limit_ip::<L>,
+        ))
+        .layer(middleware::from_fn_with_state(
+            Arc::new(tenant_resolver),
+            resolve_tenant,
+        ))
+        .layer(middleware::from_fn(trace_request))
+        .layer(middleware::from_fn(request_id))
 }
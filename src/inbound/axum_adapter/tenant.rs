@@ -0,0 +1,257 @@
+use std::sync::Arc;
+
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Request, State},
+    http::{header, request::Parts, HeaderMap, HeaderName, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+
+use crate::business::auth::{TenantId, TenantIdError};
+
+pub const TENANT_HEADER: &str = "x-tenant-id";
+
+/// Resolves the tenant of a request from a header, then from the subdomain of
+/// `base_domain`. Tenants claimed by a token take precedence, see
+/// `RequestContext`.
+#[derive(Debug, Clone)]
+pub struct TenantResolver {
+    header: HeaderName,
+    base_domain: Option<String>,
+}
+
+impl Default for TenantResolver {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantResolver {
+    pub fn new() -> Self {
+        Self {
+            header: HeaderName::from_static(TENANT_HEADER),
+            base_domain: None,
+        }
+    }
+
+    pub fn with_header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Resolves `acme` from `acme.example.com` when `base_domain` is `example.com`.
+    pub fn with_base_domain(mut self, base_domain: &str) -> Self {
+        self.base_domain = Some(base_domain.trim_start_matches('.').to_lowercase());
+        self
+    }
+
+    pub fn resolve(&self, headers: &HeaderMap) -> Result<Option<TenantId>, TenantIdError> {
+        if let Some(value) = headers.get(&self.header) {
+            let raw = value.to_str().map_err(|_| TenantIdError {
+                invalid_tenant: String::from_utf8_lossy(value.as_bytes()).to_string(),
+            })?;
+            return TenantId::new(raw).map(Some);
+        }
+        let Some(base_domain) = &self.base_domain else {
+            return Ok(None);
+        };
+        let host = headers
+            .get(header::HOST)
+            .and_then(|v| v.to_str().ok())
+            .map(|host| host.split(':').next().unwrap_or_default().to_lowercase());
+        match host
+            .as_deref()
+            .and_then(|host| host.strip_suffix(base_domain.as_str()))
+            .and_then(|prefix| prefix.strip_suffix('.'))
+        {
+            Some(subdomain) => TenantId::new(subdomain).map(Some),
+            None => Ok(None),
+        }
+    }
+}
+
+/// Tenant requested by the client, before checking it against the principal.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestedTenant(pub TenantId);
+
+pub async fn resolve_tenant(
+    State(resolver): State<Arc<TenantResolver>>,
+    mut request: Request,
+    next: Next,
+) -> Response {
+    match resolver.resolve(request.headers()) {
+        Ok(tenant) => {
+            if let Some(tenant) = tenant {
+                request.extensions_mut().insert(RequestedTenant(tenant));
+            }
+            next.run(request).await
+        }
+        Err(e) => (StatusCode::BAD_REQUEST, e.to_string()).into_response(),
+    }
+}
+
+/// The requested tenant, or the default one, for routes without a principal.
+#[async_trait]
+impl<S: Send + Sync> FromRequestParts<S> for TenantId {
+    type Rejection = Response;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(parts
+            .extensions
+            .get::<RequestedTenant>()
+            .map_or_else(TenantId::default, |requested| requested.0.clone()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{
+        body::Body,
+        http::{header, HeaderMap, HeaderName, HeaderValue, Request, StatusCode},
+        middleware,
+        routing::get,
+        Extension, Router,
+    };
+    use http_body_util::BodyExt;
+    use tower::ServiceExt;
+
+    use crate::business::auth::{
+        AuthenticationMethod, Principal, RequestContext, Role, TenantId, CROSS_TENANT_ROLE,
+    };
+
+    use super::{resolve_tenant, TenantResolver, TENANT_HEADER};
+
+    fn headers(pairs: &[(HeaderName, &str)]) -> HeaderMap {
+        pairs
+            .iter()
+            .map(|(name, value)| (name.clone(), HeaderValue::from_str(value).unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn test_resolve() {
+        let resolver = TenantResolver::new().with_base_domain("example.com");
+        let tenant_header = HeaderName::from_static("x-tenant-id");
+        let acme = Some(TenantId::new("acme").unwrap());
+
+        assert_eq!(
+            resolver
+                .resolve(&headers(&[(tenant_header.clone(), "ACME")]))
+                .unwrap(),
+            acme
+        );
+        assert_eq!(
+            resolver
+                .resolve(&headers(&[(header::HOST, "acme.example.com:8080")]))
+                .unwrap(),
+            acme
+        );
+        assert_eq!(
+            resolver
+                .resolve(&headers(&[
+                    (tenant_header.clone(), "globex"),
+                    (header::HOST, "acme.example.com"),
+                ]))
+                .unwrap(),
+            Some(TenantId::new("globex").unwrap())
+        );
+        assert_eq!(
+            resolver
+                .resolve(&headers(&[(header::HOST, "example.com")]))
+                .unwrap(),
+            None
+        );
+        assert_eq!(
+            resolver
+                .resolve(&headers(&[(header::HOST, "acme.other.com")]))
+                .unwrap(),
+            None
+        );
+        assert!(resolver
+            .resolve(&headers(&[(tenant_header, "not a tenant")]))
+            .is_err());
+    }
+
+    async fn context_tenant(principal: Principal, requested: Option<&str>) -> (StatusCode, String) {
+        let app = Router::new()
+            .route(
+                "/",
+                get(|context: RequestContext| async move { context.get_tenant().to_string() }),
+            )
+            .layer(Extension(principal))
+            .layer(middleware::from_fn_with_state(
+                Arc::new(TenantResolver::new()),
+                resolve_tenant,
+            ));
+        let mut request = Request::get("/");
+        if let Some(tenant) = requested {
+            request = request.header(TENANT_HEADER, tenant);
+        }
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = response.into_body().collect().await.unwrap().to_bytes();
+        (status, String::from_utf8_lossy(&body).to_string())
+    }
+
+    #[tokio::test]
+    async fn test_request_context_tenant() {
+        let anonymous = Principal::new("batch", None, &[], AuthenticationMethod::ApiKey);
+        let claimed = anonymous
+            .clone()
+            .with_tenant(&TenantId::new("acme").unwrap());
+
+        assert_eq!(
+            context_tenant(anonymous.clone(), None).await,
+            (StatusCode::OK, String::from("default"))
+        );
+        assert_eq!(
+            context_tenant(anonymous.clone(), Some("default")).await,
+            (StatusCode::OK, String::from("default"))
+        );
+        assert_eq!(
+            context_tenant(anonymous, Some("globex")).await.0,
+            StatusCode::FORBIDDEN
+        );
+        assert_eq!(
+            context_tenant(claimed.clone(), None).await,
+            (StatusCode::OK, String::from("acme"))
+        );
+        assert_eq!(
+            context_tenant(claimed.clone(), Some("acme")).await.0,
+            StatusCode::OK
+        );
+        assert_eq!(
+            context_tenant(claimed, Some("globex")).await.0,
+            StatusCode::FORBIDDEN
+        );
+    }
+
+    #[tokio::test]
+    async fn test_cross_tenant_role() {
+        let operator = Principal::new(
+            "support",
+            None,
+            &[Role::new("admin"), Role::new(CROSS_TENANT_ROLE)],
+            AuthenticationMethod::ApiKey,
+        );
+        assert_eq!(
+            context_tenant(operator.clone(), Some("globex")).await,
+            (StatusCode::OK, String::from("globex"))
+        );
+        assert_eq!(
+            context_tenant(operator.clone(), None).await,
+            (StatusCode::OK, String::from("default"))
+        );
+        let bound = operator.with_tenant(&TenantId::new("acme").unwrap());
+        assert_eq!(
+            context_tenant(bound, Some("globex")).await.0,
+            StatusCode::FORBIDDEN
+        );
+    }
+}
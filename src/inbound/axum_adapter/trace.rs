@@ -0,0 +1,139 @@
+use std::time::Instant;
+
+use axum::{
+    extract::Request,
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::Response,
+};
+use opentelemetry::{
+    propagation::{Extractor, Injector, TextMapPropagator},
+    trace::TraceContextExt,
+};
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use tracing::{error, field::Empty, info, info_span, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use super::request_id::RequestId;
+
+pub const TRACEPARENT_HEADER: &str = "traceparent";
+
+struct HeaderExtractor<'a>(&'a HeaderMap);
+
+impl Extractor for HeaderExtractor<'_> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|v| v.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(HeaderName::as_str).collect()
+    }
+}
+
+struct HeaderInjector<'a>(&'a mut HeaderMap);
+
+impl Injector for HeaderInjector<'_> {
+    fn set(&mut self, key: &str, value: String) {
+        if value.is_empty() {
+            return;
+        }
+        if let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(key.as_bytes()),
+            HeaderValue::from_str(&value),
+        ) {
+            self.0.insert(name, value);
+        }
+    }
+}
+
+/// Runs the request in a span joined to the caller's W3C trace context, logs
+/// its outcome and returns the `traceparent` of the span. Expects the
+/// [`RequestId`] to be set by an outer layer.
+pub async fn trace_request(request: Request, next: Next) -> Response {
+    let propagator = TraceContextPropagator::new();
+    let parent = propagator.extract(&HeaderExtractor(request.headers()));
+    let request_id = request
+        .extensions()
+        .get::<RequestId>()
+        .map(|id| id.0.clone())
+        .unwrap_or_default();
+    let span = info_span!(
+        "http_request",
+        method = %request.method(),
+        path = request.uri().path(),
+        request_id,
+        trace_id = Empty,
+        status = Empty,
+        latency_ms = Empty,
+    );
+    span.set_parent(parent.clone());
+    let context = span.context();
+    let span_context = match context.span().span_context().is_valid() {
+        true => context.span().span_context().clone(),
+        false => parent.span().span_context().clone(),
+    };
+    if span_context.is_valid() {
+        span.record("trace_id", span_context.trace_id().to_string());
+    }
+    let started = Instant::now();
+    let mut response = next.run(request).instrument(span.clone()).await;
+    span.record("status", response.status().as_u16());
+    span.record("latency_ms", started.elapsed().as_millis() as u64);
+    span.in_scope(|| match response.status().is_server_error() {
+        true => error!("request failed"),
+        false => info!("request completed"),
+    });
+    if context.span().span_context().is_valid() {
+        propagator.inject_context(&context, &mut HeaderInjector(response.headers_mut()));
+    }
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{body::Body, http::Request, middleware, routing::get, Router};
+    use opentelemetry::trace::TracerProvider as _;
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tower::ServiceExt;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::{trace_request, TRACEPARENT_HEADER};
+
+    const TRACE_ID: &str = "4bf92f3577b34da6a3ce929d0e0e4736";
+
+    fn app() -> Router {
+        Router::new()
+            .route("/", get(|| async { "ok" }))
+            .layer(middleware::from_fn(trace_request))
+    }
+
+    fn request() -> Request<Body> {
+        Request::get("/")
+            .header(
+                TRACEPARENT_HEADER,
+                format!("00-{TRACE_ID}-00f067aa0ba902b7-01"),
+            )
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_continues_caller_trace() {
+        let provider = TracerProvider::builder().build();
+        let subscriber = tracing_subscriber::registry()
+            .with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+        let response = app().oneshot(request()).await.unwrap();
+        let traceparent = response.headers()[TRACEPARENT_HEADER].to_str().unwrap();
+        let parts: Vec<&str> = traceparent.split('-').collect();
+        assert_eq!(parts[1], TRACE_ID);
+        assert_ne!(parts[2], "00f067aa0ba902b7");
+        assert!(response.headers().get("tracestate").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_no_traceparent_without_exporter() {
+        let response = app().oneshot(request()).await.unwrap();
+        assert!(response.headers().get(TRACEPARENT_HEADER).is_none());
+    }
+}
@@ -1,11 +1,9 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use uuid::Uuid;
 
 use crate::{
-    business::user::{
-        dtos::{UserFindRequest, UserFindResponse},
-        model::user::UserError,
-        User, UserAddRequest, UserRepositoryTrait, UserServiceTrait,
+    business::{
+        auth::RequestContext,
+        user::{UserAddRequest, UserServiceTrait},
     },
     inbound::axum_adapter::setup::AppState,
 };
@@ -15,7 +13,18 @@ use super::user_error::AxumUserError;
 #[utoipa::path(
     post,
     tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
     path = "/user",
+    params(
+        (
+            "Idempotency-Key" = Option<String>,
+            Header,
+            description = "Replays the first response to retries with the same key"
+        )
+    ),
     request_body = UserAddRequest,
     responses(
         (
@@ -33,24 +42,25 @@ use super::user_error::AxumUserError;
         (
             status = 403,
             description = "Operation forbidden"
+        ),
+        (
+            status = 409,
+            description = "Email already used, or request with the same key in progress"
+        ),
+        (
+            status = 422,
+            description = "Idempotency key already used for a different request"
         )
     ),
 )]
-pub async fn create_user<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
->(
-    State(app_state): State<AppState<U>>,
+pub async fn create_user<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
     Json(user_add_request): Json<UserAddRequest>,
 ) -> impl IntoResponse {
     app_state
         .user_service
-        .create_user(&user_add_request)
+        .create_user(&context, &user_add_request)
         .await
         .map(|u| (StatusCode::CREATED, Json(u)).into_response())
         .unwrap_or_else(|e| AxumUserError(e).into_response())
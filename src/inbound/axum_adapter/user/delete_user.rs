@@ -3,13 +3,11 @@ use axum::{
     http::StatusCode,
     response::IntoResponse,
 };
-use uuid::Uuid;
 
 use crate::{
-    business::user::{
-        dtos::{UserFindRequest, UserFindResponse},
-        model::user::UserError,
-        User, UserDeleteRequest, UserRepositoryTrait, UserServiceTrait,
+    business::{
+        auth::RequestContext,
+        user::{UserDeleteRequest, UserServiceTrait},
     },
     inbound::axum_adapter::setup::AppState,
 };
@@ -19,6 +17,10 @@ use super::user_error::AxumUserError;
 #[utoipa::path(
     delete,
     tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
     path = "/user/{user_id}",
     params(
         (
@@ -42,21 +44,14 @@ use super::user_error::AxumUserError;
         )
     ),
 )]
-pub async fn delete_user<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
->(
-    State(app_state): State<AppState<U>>,
+pub async fn delete_user<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
     Path(user_delete_request): Path<UserDeleteRequest>,
 ) -> impl IntoResponse {
     app_state
         .user_service
-        .delete_user(&user_delete_request)
+        .delete_user(&context, &user_delete_request)
         .await
         .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
         .unwrap_or_else(|e| AxumUserError(e).into_response())
@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, user::UserServiceTrait},
+    inbound::axum_adapter::setup::AppState,
+};
+
+use super::user_error::AxumUserError;
+
+#[utoipa::path(
+    post,
+    tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/user/{user_id}/erasure",
+    params(
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Personal data anonymised"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "User not found"
+        )
+    ),
+)]
+pub async fn erase_user<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    app_state
+        .user_service
+        .erase_user(&context, &user_id)
+        .await
+        .map(|u| (StatusCode::OK, Json(u)).into_response())
+        .unwrap_or_else(|e| AxumUserError(e).into_response())
+}
@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Path, State},
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, user::UserServiceTrait},
+    inbound::axum_adapter::setup::AppState,
+};
+
+use super::user_error::AxumUserError;
+
+#[utoipa::path(
+    get,
+    tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/user/{user_id}/data-export",
+    params(
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    responses(
+        (
+            status = 200,
+            description = "JSON archive of the profile, audit entries and group memberships",
+            content_type = "application/json"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "User not found"
+        )
+    ),
+)]
+pub async fn export_user_data<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    app_state
+        .user_service
+        .export_user_data(&context, &user_id)
+        .await
+        .map(|export| {
+            (
+                StatusCode::OK,
+                [(
+                    header::CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"user-{}.json\"", user_id),
+                )],
+                Json(export),
+            )
+                .into_response()
+        })
+        .unwrap_or_else(|e| AxumUserError(e).into_response())
+}
@@ -7,11 +7,7 @@ use axum::{
 use uuid::Uuid;
 
 use crate::{
-    business::user::{
-        dtos::{UserFindRequest, UserFindResponse},
-        model::user::UserError,
-        User, UserRepositoryTrait, UserServiceTrait,
-    },
+    business::{auth::RequestContext, user::UserServiceTrait},
     inbound::axum_adapter::setup::AppState,
 };
 
@@ -20,6 +16,10 @@ use super::user_error::AxumUserError;
 #[utoipa::path(
     get,
     tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
     path = "/user/{user_id}",
     params(
         (
@@ -43,21 +43,14 @@ use super::user_error::AxumUserError;
         )
     ),
 )]
-pub async fn find_one_user<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
->(
-    State(app_state): State<AppState<U>>,
+pub async fn find_one_user<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
     Path(user_id): Path<Uuid>,
 ) -> impl IntoResponse {
     app_state
         .user_service
-        .find_one_user(&user_id)
+        .find_one_user(&context, &user_id)
         .await
         .map(|u| (StatusCode::OK, Json(u)).into_response())
         .unwrap_or_else(|e| AxumUserError(e).into_response())
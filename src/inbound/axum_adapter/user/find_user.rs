@@ -4,13 +4,14 @@ use axum::{
     response::IntoResponse,
     Json,
 };
-use uuid::Uuid;
 
 use crate::{
-    business::user::{
-        dtos::{UserFindRequest, UserFindResponse},
-        model::user::UserError,
-        User, UserRepositoryTrait, UserServiceTrait,
+    business::{
+        auth::RequestContext,
+        user::{
+            dtos::{user_find_request::UserFindRequestQuery, UserFindRequest},
+            UserServiceTrait,
+        },
     },
     inbound::axum_adapter::setup::AppState,
 };
@@ -20,9 +21,13 @@ use super::user_error::AxumUserError;
 #[utoipa::path(
     get,
     tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
     path = "/user",
     params(
-        UserFindRequest
+        UserFindRequestQuery
     ),
     responses(
         (
@@ -39,21 +44,14 @@ use super::user_error::AxumUserError;
         )
     ),
 )]
-pub async fn find_user<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
->(
-    State(app_state): State<AppState<U>>,
+pub async fn find_user<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
     Query(user_find_request): Query<UserFindRequest>,
 ) -> impl IntoResponse {
     app_state
         .user_service
-        .find_user(&user_find_request)
+        .find_user(&context, &user_find_request)
         .await
         .map(|u| (StatusCode::OK, Json(u)).into_response())
         .unwrap_or_else(|e| AxumUserError(e).into_response())
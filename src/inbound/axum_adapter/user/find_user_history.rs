@@ -0,0 +1,58 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{audit::AuditEntry, auth::RequestContext, user::UserServiceTrait},
+    inbound::axum_adapter::setup::AppState,
+};
+
+use super::user_error::AxumUserError;
+
+#[utoipa::path(
+    get,
+    tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/user/{user_id}/history",
+    params(
+        (
+            "user_id" = Uuid,
+            Path,
+            description = "User identifier"
+        )
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Audit entries of the user, oldest first",
+            body = Vec<AuditEntry>
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        )
+    ),
+)]
+pub async fn find_user_history<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
+    Path(user_id): Path<Uuid>,
+) -> impl IntoResponse {
+    app_state
+        .user_service
+        .find_user_history(&context, &user_id)
+        .await
+        .map(|h| (StatusCode::OK, Json(h)).into_response())
+        .unwrap_or_else(|e| AxumUserError(e).into_response())
+}
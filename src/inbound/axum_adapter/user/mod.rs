@@ -1,56 +1,131 @@
+pub mod activate_user;
 pub mod create_user;
 pub mod delete_user;
+pub mod erase_user;
+pub mod export_user_data;
 pub mod find_one_user;
 pub mod find_user;
+pub mod find_user_history;
+pub mod lock_user;
+pub mod send_verification;
+pub mod suspend_user;
+pub mod unlock_user;
 pub mod update_user;
 pub mod user_error;
+pub mod verify_email;
+pub mod watch_users;
 
+use activate_user::activate_user;
 use axum::{
     routing::{delete, get, post, put},
     Router,
 };
 use create_user::create_user;
 use delete_user::delete_user;
+use erase_user::erase_user;
+use export_user_data::export_user_data;
 use find_one_user::find_one_user;
 use find_user::find_user;
+use find_user_history::find_user_history;
+use lock_user::lock_user;
+use send_verification::send_verification;
+use suspend_user::suspend_user;
+use unlock_user::unlock_user;
 use update_user::update_user;
-use utoipa::OpenApi;
-use uuid::Uuid;
-
-use crate::business::user::{
-    dtos::{UserFindRequest, UserFindResponse},
-    model::user::UserError,
-    User, UserRepositoryTrait,
+use utoipa::{
+    openapi::{
+        schema::{AdditionalProperties, ObjectBuilder, Schema, Type},
+        RefOr,
+    },
+    OpenApi,
 };
+use verify_email::verify_email;
+use watch_users::watch_users;
+
+use crate::business::user::{AttributeSchema, AttributeType, UserServiceTrait};
 
 use super::setup::AppState;
 
-pub async fn init_route<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
->() -> Router<AppState<U>> {
+pub async fn init_route<S: UserServiceTrait>() -> Router<AppState<S>> {
     Router::new()
         .route("/", post(create_user))
         .route("/", get(find_user))
+        .route("/events", get(watch_users))
         .route("/:user_id", put(update_user))
         .route("/:user_id", get(find_one_user))
         .route("/:user_id", delete(delete_user))
+        .route("/:user_id/history", get(find_user_history))
+        .route("/:user_id/verification", post(send_verification))
+        .route("/:user_id/activate", post(activate_user))
+        .route("/:user_id/suspend", post(suspend_user))
+        .route("/:user_id/lock", post(lock_user))
+        .route("/:user_id/unlock", post(unlock_user))
+        .route("/:user_id/data-export", get(export_user_data))
+        .route("/:user_id/erasure", post(erase_user))
+}
+
+pub async fn init_public_route<S: UserServiceTrait>() -> Router<AppState<S>> {
+    Router::new().route("/verify-email", post(verify_email))
 }
 
-pub fn api_docs() -> utoipa::openapi::OpenApi {
+pub fn api_docs(attribute_schema: &AttributeSchema) -> utoipa::openapi::OpenApi {
     #[derive(OpenApi)]
     #[openapi(paths(
         crate::inbound::axum_adapter::user::create_user::create_user,
         crate::inbound::axum_adapter::user::update_user::update_user,
         crate::inbound::axum_adapter::user::delete_user::delete_user,
         crate::inbound::axum_adapter::user::find_one_user::find_one_user,
-        crate::inbound::axum_adapter::user::find_user::find_user
+        crate::inbound::axum_adapter::user::find_user::find_user,
+        crate::inbound::axum_adapter::user::find_user_history::find_user_history,
+        crate::inbound::axum_adapter::user::send_verification::send_verification,
+        crate::inbound::axum_adapter::user::verify_email::verify_email,
+        crate::inbound::axum_adapter::user::activate_user::activate_user,
+        crate::inbound::axum_adapter::user::suspend_user::suspend_user,
+        crate::inbound::axum_adapter::user::lock_user::lock_user,
+        crate::inbound::axum_adapter::user::unlock_user::unlock_user,
+        crate::inbound::axum_adapter::user::export_user_data::export_user_data,
+        crate::inbound::axum_adapter::user::erase_user::erase_user,
+        crate::inbound::axum_adapter::user::watch_users::watch_users
     ))]
     struct ApiDocs;
-    ApiDocs::openapi()
+    let mut api_docs = ApiDocs::openapi();
+    if let Some(components) = api_docs.components.as_mut() {
+        components.schemas.insert(
+            String::from("CustomAttributes"),
+            custom_attributes_schema(attribute_schema),
+        );
+    }
+    api_docs
+}
+
+/// Describes the configured custom attributes in place of a free-form map.
+fn custom_attributes_schema(attribute_schema: &AttributeSchema) -> RefOr<Schema> {
+    let object = attribute_schema.get_definitions().iter().fold(
+        ObjectBuilder::new()
+            .schema_type(Type::Object)
+            .additional_properties(Some(AdditionalProperties::<Schema>::FreeForm(false))),
+        |object, (name, definition)| {
+            let property = ObjectBuilder::new()
+                .description(definition.get_description())
+                .schema_type(match definition.get_type() {
+                    AttributeType::Boolean => Type::Boolean,
+                    AttributeType::Integer => Type::Integer,
+                    AttributeType::String => Type::String,
+                })
+                .minimum(definition.get_min())
+                .maximum(definition.get_max())
+                .max_length(definition.get_max_length())
+                .pattern(definition.get_pattern())
+                .enum_values(
+                    Some(definition.get_values().iter().map(String::as_str))
+                        .filter(|_| !definition.get_values().is_empty()),
+                );
+            let object = object.property(name, property);
+            match definition.is_required() {
+                true => object.required(name),
+                false => object,
+            }
+        },
+    );
+    Schema::Object(object.build()).into()
 }
@@ -7,10 +7,9 @@ use axum::{
 use uuid::Uuid;
 
 use crate::{
-    business::user::{
-        dtos::{UserFindRequest, UserFindResponse},
-        model::user::UserError,
-        User, UserRepositoryTrait, UserServiceTrait, UserUpdateRequest,
+    business::{
+        auth::RequestContext,
+        user::{UserServiceTrait, UserUpdateRequest},
     },
     inbound::axum_adapter::setup::AppState,
 };
@@ -20,6 +19,10 @@ use super::user_error::AxumUserError;
 #[utoipa::path(
     put,
     tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
     path = "/user/{user_id}",
     params(
         (
@@ -48,22 +51,15 @@ use super::user_error::AxumUserError;
         )
     ),
 )]
-pub async fn update_user<
-    U: UserRepositoryTrait<
-        Id = Uuid,
-        Entity = User,
-        Error = UserError,
-        FindOptions = UserFindRequest,
-        FindResult = UserFindResponse,
-    >,
->(
-    State(app_state): State<AppState<U>>,
+pub async fn update_user<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
     Path(user_id): Path<Uuid>,
     Json(user_update_request): Json<UserUpdateRequest>,
 ) -> impl IntoResponse {
     app_state
         .user_service
-        .update_user(&user_id, &user_update_request)
+        .update_user(&context, &user_id, &user_update_request)
         .await
         .map(|u| (StatusCode::OK, Json(u)).into_response())
         .unwrap_or_else(|e| AxumUserError(e).into_response())
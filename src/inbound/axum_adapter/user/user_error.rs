@@ -2,8 +2,11 @@ use axum::{
     http::StatusCode,
     response::{IntoResponse, Response},
 };
+use tracing::{error, warn};
 
-use crate::business::user::model::user::UserError;
+use crate::{
+    business::user::model::user::UserError, inbound::axum_adapter::crud::crud_error::AxumCrudError,
+};
 
 pub struct AxumUserError(pub UserError);
 
@@ -14,10 +17,12 @@ impl IntoResponse for AxumUserError {
                 (StatusCode::NOT_FOUND, ()).into_response()
             }
             ref e @ UserError::EmailAlreadyUsed { email: _ }
-            | ref e @ UserError::EmailAlreadyUsedByOther { email: _ } => {
+            | ref e @ UserError::EmailAlreadyUsedByOther { email: _ }
+            | ref e @ UserError::IdAlreadyUsed { id: _ } => {
                 (StatusCode::CONFLICT, e.to_string()).into_response()
             }
-            ref e @ UserError::MismatchUserId { id1: _, id2: _ } => {
+            ref e @ UserError::MismatchUserId { id1: _, id2: _ }
+            | ref e @ UserError::MissingUserId => {
                 (StatusCode::BAD_REQUEST, e.to_string()).into_response()
             }
             ref e @ UserError::PerPageValueTooHigh { per_page: _ }
@@ -27,7 +32,32 @@ impl IntoResponse for AxumUserError {
             ref e @ UserError::PerPageOffsetTooLow { offset: _ } => {
                 (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response()
             }
-            ref e @ UserError::Unknown(ref _error) => {
+            ref e @ UserError::InvalidAttribute(_) => {
+                (StatusCode::UNPROCESSABLE_ENTITY, e.to_string()).into_response()
+            }
+            ref e @ UserError::Forbidden { operation: _ } => {
+                (StatusCode::FORBIDDEN, e.to_string()).into_response()
+            }
+            ref e @ UserError::InvalidVerificationToken
+            | ref e @ UserError::ExpiredVerificationToken => {
+                (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            }
+            ref e @ UserError::EmailAlreadyVerified { email: _ }
+            | ref e @ UserError::InvalidStatusTransition { from: _, to: _ } => {
+                (StatusCode::CONFLICT, e.to_string()).into_response()
+            }
+            ref e @ UserError::MailDelivery(ref _error) => {
+                warn!(error = %e, "mail delivery failed");
+                (StatusCode::BAD_GATEWAY, e.to_string()).into_response()
+            }
+            UserError::Group(e) => AxumCrudError(e).into_response(),
+            ref e @ UserError::Audit(_)
+            | ref e @ UserError::EventPublishing {
+                event_id: _,
+                reason: _,
+            }
+            | ref e @ UserError::Unknown(_) => {
+                error!(error = %e, "user request failed");
                 (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
             }
         }
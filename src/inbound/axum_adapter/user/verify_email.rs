@@ -0,0 +1,39 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Extension, Json};
+use uuid::Uuid;
+
+use crate::{
+    business::user::{UserServiceTrait, UserVerifyEmailRequest},
+    inbound::axum_adapter::{request_id::RequestId, setup::AppState},
+};
+
+use super::user_error::AxumUserError;
+
+#[utoipa::path(
+    post,
+    tag = "User",
+    path = "/user/verify-email",
+    request_body = UserVerifyEmailRequest,
+    responses(
+        (
+            status = 200,
+            description = "Email verified"
+        ),
+        (
+            status = 400,
+            description = "Verification token invalid, already used or expired"
+        )
+    ),
+)]
+pub async fn verify_email<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    request_id: Option<Extension<RequestId>>,
+    Json(user_verify_email_request): Json<UserVerifyEmailRequest>,
+) -> impl IntoResponse {
+    let request_id = request_id.map_or_else(|| Uuid::new_v4().to_string(), |Extension(id)| id.0);
+    app_state
+        .user_service
+        .verify_email(&request_id, &user_verify_email_request)
+        .await
+        .map(|u| (StatusCode::OK, Json(u)).into_response())
+        .unwrap_or_else(|e| AxumUserError(e).into_response())
+}
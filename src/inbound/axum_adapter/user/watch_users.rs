@@ -0,0 +1,180 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::{
+        sse::{Event, KeepAlive},
+        IntoResponse, Sse,
+    },
+};
+use futures_util::stream;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        user::{dtos::UserFindRequestFilter, UserServiceTrait},
+    },
+    inbound::axum_adapter::setup::AppState,
+};
+
+use super::user_error::AxumUserError;
+
+const LAST_EVENT_ID_HEADER: &str = "last-event-id";
+
+#[utoipa::path(
+    get,
+    tag = "User",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/user/events",
+    params(
+        UserFindRequestFilter,
+        (
+            "Last-Event-ID" = Option<u64>,
+            Header,
+            description = "Sequence of the last change received, to resume the stream"
+        )
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Stream of user changes",
+            content_type = "text/event-stream"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        )
+    ),
+)]
+pub async fn watch_users<S: UserServiceTrait>(
+    State(app_state): State<AppState<S>>,
+    context: RequestContext,
+    headers: HeaderMap,
+    Query(filter): Query<UserFindRequestFilter>,
+) -> impl IntoResponse {
+    let last_sequence = headers
+        .get(LAST_EVENT_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.trim().parse::<u64>().ok());
+    match app_state
+        .user_service
+        .watch_users(&context, &filter, last_sequence)
+        .await
+    {
+        Ok(subscription) => {
+            let changes = stream::unfold(subscription, |mut subscription| async move {
+                let change = subscription.next().await?;
+                let event = Event::default()
+                    .id(change.get_sequence().to_string())
+                    .event(change.get_event().get_event_type().to_string())
+                    .json_data(change.get_event());
+                Some((event, subscription))
+            });
+            Sse::new(changes)
+                .keep_alive(KeepAlive::default())
+                .into_response()
+        }
+        Err(e) => AxumUserError(e).into_response(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{
+        body::Body,
+        http::{Request, StatusCode},
+        routing::get,
+        Extension, Router,
+    };
+    use http_body_util::BodyExt;
+    use std::sync::Arc;
+    use tower::ServiceExt;
+
+    use crate::{
+        business::{
+            auth::{AuthenticationMethod, Principal, RequestContext, Role},
+            user::{
+                service::user_service::UserService, EmailAddress, Name, UserAddRequest,
+                UserServiceTrait,
+            },
+        },
+        inbound::axum_adapter::setup::AppState,
+        outbound::{
+            event_publisher_adapter::broadcast_event_publisher::BroadcastEventPublisher,
+            id_generator_adapter::uuid_v7_generator::UuidV7Generator,
+            in_memory_repository_adapter::{
+                in_memory_audit_trail::InMemoryAuditTrail,
+                in_memory_group_repository::InMemoryGroupRepository,
+                in_memory_user_repository::InMemoryUserRepository,
+                in_memory_verification_token_store::InMemoryVerificationTokenStore,
+            },
+            mailer_adapter::file_mailer::FileMailer,
+        },
+    };
+
+    use super::watch_users;
+
+    #[tokio::test]
+    async fn test_resume_with_last_event_id() {
+        let service = UserService::new(
+            InMemoryUserRepository::new(),
+            FileMailer::file(
+                &EmailAddress::new("no-reply@example.com").unwrap(),
+                &std::env::temp_dir().join(format!("watch-{}.eml", uuid::Uuid::new_v4())),
+            ),
+            InMemoryVerificationTokenStore::new(),
+            BroadcastEventPublisher::default(),
+            InMemoryAuditTrail::new(),
+            InMemoryGroupRepository::new(),
+            UuidV7Generator,
+        );
+        let admin = Principal::new(
+            "admin",
+            None,
+            &[Role::new("admin")],
+            AuthenticationMethod::ApiKey,
+        );
+        for (firstname, email) in [("Anna", "anna@example.com"), ("Bob", "bob@example.com")] {
+            service
+                .create_user(
+                    &RequestContext::new(&admin, "test-request"),
+                    &UserAddRequest::new(
+                        &Name::new(firstname).unwrap(),
+                        &Name::new("Doe").unwrap(),
+                        &EmailAddress::new(email).unwrap(),
+                    ),
+                )
+                .await
+                .unwrap();
+        }
+        let app = Router::new()
+            .route("/events", get(watch_users))
+            .with_state(AppState {
+                user_service: Arc::new(service),
+            })
+            .layer(Extension(admin));
+
+        let response = app
+            .oneshot(
+                Request::get("/events?firstname=Bob")
+                    .header("Last-Event-ID", "0")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut body = response.into_body();
+        let frame = body.frame().await.unwrap().unwrap().into_data().unwrap();
+        let text = String::from_utf8(frame.to_vec()).unwrap();
+        assert!(text.contains("event: UserCreated"));
+        assert!(text.contains("id: 2"));
+        assert!(text.contains("\"firstname\":\"Bob\""));
+    }
+}
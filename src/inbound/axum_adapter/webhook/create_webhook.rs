@@ -0,0 +1,53 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        webhook::{WebhookAddRequest, WebhookServiceTrait, WebhookSubscription},
+    },
+    inbound::axum_adapter::setup::WebhookState,
+};
+
+use super::webhook_error::AxumWebhookError;
+
+#[utoipa::path(
+    post,
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/webhook",
+    request_body = WebhookAddRequest,
+    responses(
+        (
+            status = 201,
+            description = "Webhook subscription created",
+            body = WebhookSubscription
+        ),
+        (
+            status = 400,
+            description = "Data sent not correct"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        )
+    ),
+)]
+pub async fn create_webhook<W: WebhookServiceTrait>(
+    State(webhook_state): State<WebhookState<W>>,
+    context: RequestContext,
+    Json(webhook_add_request): Json<WebhookAddRequest>,
+) -> impl IntoResponse {
+    webhook_state
+        .webhook_service
+        .create_subscription(&context, &webhook_add_request)
+        .await
+        .map(|s| (StatusCode::CREATED, Json(s)).into_response())
+        .unwrap_or_else(|e| AxumWebhookError(e).into_response())
+}
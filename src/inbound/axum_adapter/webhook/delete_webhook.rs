@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{auth::RequestContext, webhook::WebhookServiceTrait},
+    inbound::axum_adapter::setup::WebhookState,
+};
+
+use super::webhook_error::AxumWebhookError;
+
+#[utoipa::path(
+    delete,
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/webhook/{webhook_id}",
+    params(
+        (
+            "webhook_id" = Uuid,
+            Path,
+            description = "Webhook subscription identifier"
+        )
+    ),
+    responses(
+        (
+            status = 204,
+            description = "Webhook subscription deleted"
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "Webhook subscription not found"
+        )
+    ),
+)]
+pub async fn delete_webhook<W: WebhookServiceTrait>(
+    State(webhook_state): State<WebhookState<W>>,
+    context: RequestContext,
+    Path(webhook_id): Path<Uuid>,
+) -> impl IntoResponse {
+    webhook_state
+        .webhook_service
+        .delete_subscription(&context, &webhook_id)
+        .await
+        .map(|_| (StatusCode::NO_CONTENT, ()).into_response())
+        .unwrap_or_else(|e| AxumWebhookError(e).into_response())
+}
@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        webhook::{WebhookServiceTrait, WebhookSubscription},
+    },
+    inbound::axum_adapter::setup::WebhookState,
+};
+
+use super::webhook_error::AxumWebhookError;
+
+#[utoipa::path(
+    get,
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/webhook/{webhook_id}",
+    params(
+        (
+            "webhook_id" = Uuid,
+            Path,
+            description = "Webhook subscription identifier"
+        )
+    ),
+    responses(
+        (
+            status = 200,
+            description = "Webhook subscription",
+            body = WebhookSubscription
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "Webhook subscription not found"
+        )
+    ),
+)]
+pub async fn find_one_webhook<W: WebhookServiceTrait>(
+    State(webhook_state): State<WebhookState<W>>,
+    context: RequestContext,
+    Path(webhook_id): Path<Uuid>,
+) -> impl IntoResponse {
+    webhook_state
+        .webhook_service
+        .find_one_subscription(&context, &webhook_id)
+        .await
+        .map(|s| (StatusCode::OK, Json(s)).into_response())
+        .unwrap_or_else(|e| AxumWebhookError(e).into_response())
+}
@@ -0,0 +1,47 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        webhook::{WebhookServiceTrait, WebhookSubscription},
+    },
+    inbound::axum_adapter::setup::WebhookState,
+};
+
+use super::webhook_error::AxumWebhookError;
+
+#[utoipa::path(
+    get,
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/webhook",
+    responses(
+        (
+            status = 200,
+            description = "Webhook subscriptions list",
+            body = Vec<WebhookSubscription>
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        )
+    ),
+)]
+pub async fn find_webhooks<W: WebhookServiceTrait>(
+    State(webhook_state): State<WebhookState<W>>,
+    context: RequestContext,
+) -> impl IntoResponse {
+    webhook_state
+        .webhook_service
+        .find_subscriptions(&context)
+        .await
+        .map(|s| (StatusCode::OK, Json(s)).into_response())
+        .unwrap_or_else(|e| AxumWebhookError(e).into_response())
+}
@@ -0,0 +1,51 @@
+pub mod create_webhook;
+pub mod delete_webhook;
+pub mod find_dead_letters;
+pub mod find_deliveries;
+pub mod find_one_webhook;
+pub mod find_webhooks;
+pub mod retry_delivery;
+pub mod webhook_error;
+
+use axum::{
+    routing::{delete, get, post},
+    Router,
+};
+use create_webhook::create_webhook;
+use delete_webhook::delete_webhook;
+use find_dead_letters::find_dead_letters;
+use find_deliveries::find_deliveries;
+use find_one_webhook::find_one_webhook;
+use find_webhooks::find_webhooks;
+use retry_delivery::retry_delivery;
+use utoipa::OpenApi;
+
+use crate::business::webhook::WebhookServiceTrait;
+
+use super::setup::WebhookState;
+
+pub async fn init_route<W: WebhookServiceTrait>() -> Router<WebhookState<W>> {
+    Router::new()
+        .route("/", post(create_webhook))
+        .route("/", get(find_webhooks))
+        .route("/dead-letters", get(find_dead_letters))
+        .route("/dead-letters/:delivery_id/retry", post(retry_delivery))
+        .route("/:webhook_id", get(find_one_webhook))
+        .route("/:webhook_id", delete(delete_webhook))
+        .route("/:webhook_id/deliveries", get(find_deliveries))
+}
+
+pub fn api_docs() -> utoipa::openapi::OpenApi {
+    #[derive(OpenApi)]
+    #[openapi(paths(
+        crate::inbound::axum_adapter::webhook::create_webhook::create_webhook,
+        crate::inbound::axum_adapter::webhook::find_webhooks::find_webhooks,
+        crate::inbound::axum_adapter::webhook::find_one_webhook::find_one_webhook,
+        crate::inbound::axum_adapter::webhook::delete_webhook::delete_webhook,
+        crate::inbound::axum_adapter::webhook::find_deliveries::find_deliveries,
+        crate::inbound::axum_adapter::webhook::find_dead_letters::find_dead_letters,
+        crate::inbound::axum_adapter::webhook::retry_delivery::retry_delivery
+    ))]
+    struct ApiDocs;
+    ApiDocs::openapi()
+}
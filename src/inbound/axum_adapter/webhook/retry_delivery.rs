@@ -0,0 +1,69 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::RequestContext,
+        webhook::{WebhookDelivery, WebhookServiceTrait},
+    },
+    inbound::axum_adapter::setup::WebhookState,
+};
+
+use super::webhook_error::AxumWebhookError;
+
+#[utoipa::path(
+    post,
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = []),
+        ("api_key" = [])
+    ),
+    path = "/webhook/dead-letters/{delivery_id}/retry",
+    params(
+        (
+            "delivery_id" = Uuid,
+            Path,
+            description = "Delivery identifier"
+        )
+    ),
+    responses(
+        (
+            status = 202,
+            description = "Delivery scheduled again",
+            body = WebhookDelivery
+        ),
+        (
+            status = 401,
+            description = "Authentication required"
+        ),
+        (
+            status = 403,
+            description = "Operation forbidden"
+        ),
+        (
+            status = 404,
+            description = "Delivery not found"
+        ),
+        (
+            status = 409,
+            description = "Delivery is not dead-lettered"
+        )
+    ),
+)]
+pub async fn retry_delivery<W: WebhookServiceTrait>(
+    State(webhook_state): State<WebhookState<W>>,
+    context: RequestContext,
+    Path(delivery_id): Path<Uuid>,
+) -> impl IntoResponse {
+    webhook_state
+        .webhook_service
+        .retry_delivery(&context, &delivery_id)
+        .await
+        .map(|d| (StatusCode::ACCEPTED, Json(d)).into_response())
+        .unwrap_or_else(|e| AxumWebhookError(e).into_response())
+}
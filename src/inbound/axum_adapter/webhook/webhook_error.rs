@@ -0,0 +1,34 @@
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use tracing::error;
+
+use crate::business::webhook::WebhookError;
+
+pub struct AxumWebhookError(pub WebhookError);
+
+impl IntoResponse for AxumWebhookError {
+    fn into_response(self) -> Response {
+        match self.0 {
+            ref _e @ WebhookError::SubscriptionNotExists { id: _ }
+            | ref _e @ WebhookError::DeliveryNotExists { id: _ } => {
+                (StatusCode::NOT_FOUND, ()).into_response()
+            }
+            ref e @ WebhookError::DeliveryNotDeadLettered { id: _ } => {
+                (StatusCode::CONFLICT, e.to_string()).into_response()
+            }
+            ref e @ WebhookError::EmptySecret | ref e @ WebhookError::InternalTarget { url: _ } => {
+                (StatusCode::BAD_REQUEST, e.to_string()).into_response()
+            }
+            ref e @ WebhookError::Forbidden => {
+                (StatusCode::FORBIDDEN, e.to_string()).into_response()
+            }
+            ref e @ WebhookError::Transport { url: _, reason: _ }
+            | ref e @ WebhookError::Unknown(_) => {
+                error!(error = %e, "webhook request failed");
+                (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()).into_response()
+            }
+        }
+    }
+}
@@ -0,0 +1 @@
+pub mod static_api_key_authenticator;
@@ -0,0 +1,129 @@
+use std::{collections::HashMap, future::Future, path::Path, sync::Arc};
+
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::business::auth::{
+    AuthError, AuthToken, AuthenticationMethod, AuthenticatorTrait, Principal, Role, TenantId,
+};
+
+#[derive(Debug, Deserialize)]
+struct ApiKeyEntry {
+    key: String,
+    name: String,
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    tenant: Option<TenantId>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StaticApiKeyAuthenticator {
+    keys: Arc<HashMap<[u8; 32], Principal>>,
+}
+
+impl StaticApiKeyAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_key(mut self, key: &str, name: &str, roles: &[Role]) -> Self {
+        let mut keys = self.keys.as_ref().clone();
+        keys.insert(
+            Self::digest(key),
+            Principal::new(name, None, roles, AuthenticationMethod::ApiKey),
+        );
+        self.keys = Arc::new(keys);
+        self
+    }
+
+    /// Restricts a previously added key to a single tenant.
+    pub fn with_key_tenant(mut self, key: &str, tenant: &TenantId) -> Self {
+        let mut keys = self.keys.as_ref().clone();
+        if let Some(principal) = keys.remove(&Self::digest(key)) {
+            keys.insert(Self::digest(key), principal.with_tenant(tenant));
+        }
+        self.keys = Arc::new(keys);
+        self
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, AuthError> {
+        let invalid_key = |reason: String| AuthError::InvalidKey {
+            source_name: path.display().to_string(),
+            reason,
+        };
+        let content = std::fs::read(path).map_err(|e| invalid_key(e.to_string()))?;
+        let entries: Vec<ApiKeyEntry> =
+            serde_json::from_slice(&content).map_err(|e| invalid_key(e.to_string()))?;
+        Ok(entries.iter().fold(Self::new(), |authenticator, entry| {
+            let authenticator = authenticator.with_key(&entry.key, &entry.name, &entry.roles);
+            match &entry.tenant {
+                Some(tenant) => authenticator.with_key_tenant(&entry.key, tenant),
+                None => authenticator,
+            }
+        }))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn digest(key: &str) -> [u8; 32] {
+        Sha256::digest(key.as_bytes()).into()
+    }
+}
+
+impl AuthenticatorTrait for StaticApiKeyAuthenticator {
+    fn authenticate(
+        &self,
+        token: &AuthToken,
+    ) -> impl Future<Output = Result<Principal, AuthError>> + Send {
+        Box::pin(async move {
+            match token {
+                AuthToken::ApiKey(key) => self
+                    .keys
+                    .get(&Self::digest(key))
+                    .cloned()
+                    .ok_or(AuthError::UnknownApiKey),
+                _ => Err(AuthError::UnsupportedCredentials),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::business::auth::{AuthError, AuthToken, AuthenticatorTrait, Role};
+
+    use super::StaticApiKeyAuthenticator;
+
+    #[tokio::test]
+    async fn test_known_key_ok() {
+        let authenticator =
+            StaticApiKeyAuthenticator::new().with_key("s3cr3t", "batch", &[Role::new("admin")]);
+        let principal = authenticator
+            .authenticate(&AuthToken::ApiKey(String::from("s3cr3t")))
+            .await
+            .unwrap();
+        assert_eq!(principal.get_subject(), "batch");
+        assert!(principal.has_role(&Role::new("admin")));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_key_ko() {
+        let authenticator = StaticApiKeyAuthenticator::new().with_key("s3cr3t", "batch", &[]);
+        let result = authenticator
+            .authenticate(&AuthToken::ApiKey(String::from("other")))
+            .await;
+        assert!(matches!(result, Err(AuthError::UnknownApiKey)));
+    }
+
+    #[tokio::test]
+    async fn test_bearer_unsupported() {
+        let authenticator = StaticApiKeyAuthenticator::new().with_key("s3cr3t", "batch", &[]);
+        let result = authenticator
+            .authenticate(&AuthToken::Bearer(String::from("s3cr3t")))
+            .await;
+        assert!(matches!(result, Err(AuthError::UnsupportedCredentials)));
+    }
+}
@@ -0,0 +1,83 @@
+use argon2::{
+    password_hash::{rand_core::OsRng, PasswordHash, SaltString},
+    Algorithm, Argon2, Params, PasswordHasher, PasswordVerifier, Version,
+};
+
+use crate::business::credential::{CredentialError, PasswordHasherTrait};
+
+#[derive(Debug, Clone)]
+pub struct Argon2PasswordHasher {
+    params: Params,
+}
+
+impl Default for Argon2PasswordHasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Argon2PasswordHasher {
+    pub fn new() -> Self {
+        Self {
+            params: Params::default(),
+        }
+    }
+
+    pub fn with_params(
+        memory_cost: u32,
+        time_cost: u32,
+        parallelism: u32,
+    ) -> Result<Self, CredentialError> {
+        let params = Params::new(memory_cost, time_cost, parallelism, None)
+            .map_err(|e| CredentialError::Unknown(anyhow::anyhow!(e)))?;
+        Ok(Self { params })
+    }
+
+    fn argon2(&self) -> Argon2<'static> {
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, self.params.clone())
+    }
+}
+
+impl PasswordHasherTrait for Argon2PasswordHasher {
+    fn hash(&self, password: &str) -> Result<String, CredentialError> {
+        let salt = SaltString::generate(&mut OsRng);
+        self.argon2()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| CredentialError::Unknown(anyhow::anyhow!(e)))
+    }
+
+    fn verify(&self, password: &str, password_hash: &str) -> Result<bool, CredentialError> {
+        let parsed = PasswordHash::new(password_hash)
+            .map_err(|e| CredentialError::Unknown(anyhow::anyhow!(e)))?;
+        match self.argon2().verify_password(password.as_bytes(), &parsed) {
+            Ok(()) => Ok(true),
+            Err(argon2::password_hash::Error::Password) => Ok(false),
+            Err(e) => Err(CredentialError::Unknown(anyhow::anyhow!(e))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::business::credential::PasswordHasherTrait;
+
+    use super::Argon2PasswordHasher;
+
+    #[test]
+    fn test_hash_and_verify() {
+        let hasher = Argon2PasswordHasher::with_params(1024, 1, 1).unwrap();
+        let hash = hasher.hash("Correct-Horse-42").unwrap();
+        assert!(hash.starts_with("$argon2id$"));
+        assert!(hasher.verify("Correct-Horse-42", &hash).unwrap());
+        assert!(!hasher.verify("Wrong-Horse-42", &hash).unwrap());
+    }
+
+    #[test]
+    fn test_hash_is_salted() {
+        let hasher = Argon2PasswordHasher::with_params(1024, 1, 1).unwrap();
+        let hash_1 = hasher.hash("Correct-Horse-42").unwrap();
+        let hash_2 = hasher.hash("Correct-Horse-42").unwrap();
+        assert_ne!(hash_1, hash_2);
+    }
+}
@@ -0,0 +1,255 @@
+use std::{
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::Mutex,
+};
+use uuid::Uuid;
+
+use crate::business::audit::{AuditEntry, AuditError, AuditRecord, AuditTrailTrait, GENESIS_HASH};
+
+#[derive(Debug)]
+struct FileAuditState {
+    file: File,
+    entries: Vec<AuditEntry>,
+}
+
+/// Append-only JSON lines file, one hash-chained entry per line.
+#[derive(Debug, Clone)]
+pub struct FileAuditTrail {
+    path: PathBuf,
+    state: Arc<Mutex<FileAuditState>>,
+}
+
+fn storage_error(e: impl ToString) -> AuditError {
+    AuditError::Storage {
+        reason: e.to_string(),
+    }
+}
+
+impl FileAuditTrail {
+    /// Opens the trail, refusing to start on a file whose chain is broken.
+    pub async fn open(path: &Path) -> Result<Self, AuditError> {
+        let entries = Self::read_entries(path).await?;
+        AuditEntry::verify_chain(entries.iter())?;
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .await
+            .map_err(storage_error)?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            state: Arc::new(Mutex::new(FileAuditState { file, entries })),
+        })
+    }
+
+    async fn read_entries(path: &Path) -> Result<Vec<AuditEntry>, AuditError> {
+        let content = match tokio::fs::read_to_string(path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(storage_error(e)),
+        };
+        content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty())
+            .map(|(index, line)| {
+                serde_json::from_str::<AuditEntry>(line).map_err(|_| AuditError::TamperedEntry {
+                    sequence: index as u64 + 1,
+                })
+            })
+            .collect()
+    }
+}
+
+impl AuditTrailTrait for FileAuditTrail {
+    fn append(
+        &self,
+        record: &AuditRecord,
+    ) -> impl Future<Output = Result<AuditEntry, AuditError>> + Send {
+        Box::pin(async move {
+            let mut state = self.state.lock().await;
+            if let Some(entry) = AuditEntry::find_event(&state.entries, record) {
+                return Ok(entry.clone());
+            }
+            let previous_hash = state
+                .entries
+                .last()
+                .map_or(GENESIS_HASH.to_string(), |entry| {
+                    entry.get_hash().to_string()
+                });
+            let entry = AuditEntry::seal(record, &(state.entries.len() as u64 + 1), &previous_hash);
+            let mut line = serde_json::to_vec(&entry).map_err(storage_error)?;
+            line.push(b'\n');
+            state.file.write_all(&line).await.map_err(storage_error)?;
+            state.file.sync_data().await.map_err(storage_error)?;
+            state.entries.push(entry.clone());
+            Ok(entry)
+        })
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<AuditEntry>, AuditError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .state
+                .lock()
+                .await
+                .entries
+                .iter()
+                .filter(|entry| entry.get_user_id().eq(user_id))
+                .cloned()
+                .collect())
+        })
+    }
+
+    /// Rewrites the whole file next to it, then swaps it in.
+    fn redact_user(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<usize, AuditError>> + Send {
+        Box::pin(async move {
+            let mut state = self.state.lock().await;
+            let mut entries = state.entries.clone();
+            let redacted = entries
+                .iter_mut()
+                .filter(|entry| entry.get_user_id().eq(user_id))
+                .filter_map(|entry| entry.redact().then_some(()))
+                .count();
+            if redacted == 0 {
+                return Ok(0);
+            }
+            let mut content = Vec::new();
+            for entry in entries.iter() {
+                content.extend(serde_json::to_vec(entry).map_err(storage_error)?);
+                content.push(b'\n');
+            }
+            let temporary = self.path.with_extension("tmp");
+            let mut file = File::create(&temporary).await.map_err(storage_error)?;
+            file.write_all(&content).await.map_err(storage_error)?;
+            file.sync_all().await.map_err(storage_error)?;
+            tokio::fs::rename(&temporary, &self.path)
+                .await
+                .map_err(storage_error)?;
+            state.file = OpenOptions::new()
+                .append(true)
+                .open(&self.path)
+                .await
+                .map_err(storage_error)?;
+            state.entries = entries;
+            Ok(redacted)
+        })
+    }
+
+    fn verify_chain(&self) -> impl Future<Output = Result<u64, AuditError>> + Send {
+        Box::pin(async move {
+            let _state = self.state.lock().await;
+            let entries = Self::read_entries(&self.path).await?;
+            AuditEntry::verify_chain(entries.iter())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::{
+        audit::{AuditError, AuditRecord, AuditTrailTrait},
+        auth::{AuthenticationMethod, Principal, RequestContext, Role},
+        user::{EmailAddress, Name, User, UserOperation},
+    };
+
+    use super::FileAuditTrail;
+
+    fn record(user: &User, operation: UserOperation) -> AuditRecord {
+        let context = RequestContext::new(
+            &Principal::new(
+                "admin",
+                None,
+                &[Role::new("admin")],
+                AuthenticationMethod::ApiKey,
+            ),
+            "req-1",
+        );
+        AuditRecord::new(&context, operation, None, Some(user))
+    }
+
+    #[tokio::test]
+    async fn test_reopen_and_detect_tampering() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", Uuid::new_v4()));
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let trail = FileAuditTrail::open(&path).await.unwrap();
+        trail
+            .append(&record(&user, UserOperation::Create))
+            .await
+            .unwrap();
+        trail
+            .append(&record(&user, UserOperation::Update))
+            .await
+            .unwrap();
+
+        let reopened = FileAuditTrail::open(&path).await.unwrap();
+        let third = reopened
+            .append(&record(&user, UserOperation::Update))
+            .await
+            .unwrap();
+        assert_eq!(third.get_sequence(), 3);
+        assert_eq!(reopened.find_by_user(user.get_id()).await.unwrap().len(), 3);
+        assert_eq!(reopened.verify_chain().await.unwrap(), 3);
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        tokio::fs::write(
+            &path,
+            content.replacen("\"actor\":\"admin\"", "\"actor\":\"intruder\"", 1),
+        )
+        .await
+        .unwrap();
+        assert!(matches!(
+            reopened.verify_chain().await,
+            Err(AuditError::TamperedEntry { sequence: 1 })
+        ));
+        assert!(FileAuditTrail::open(&path).await.is_err());
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_redaction_survives_reopen() {
+        let path = std::env::temp_dir().join(format!("audit-{}.jsonl", Uuid::new_v4()));
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let trail = FileAuditTrail::open(&path).await.unwrap();
+        trail
+            .append(&record(&user, UserOperation::Create))
+            .await
+            .unwrap();
+        assert_eq!(trail.redact_user(user.get_id()).await.unwrap(), 1);
+        trail
+            .append(&record(&user, UserOperation::Update))
+            .await
+            .unwrap();
+
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        assert_eq!(content.matches("john@example.com").count(), 1);
+        let reopened = FileAuditTrail::open(&path).await.unwrap();
+        assert_eq!(reopened.verify_chain().await.unwrap(), 2);
+        tokio::fs::remove_file(&path).await.unwrap();
+    }
+}
@@ -0,0 +1,258 @@
+use std::{collections::BTreeMap, fmt::Debug, path::Path, sync::Arc};
+
+use hmac::{Hmac, Mac};
+use lazy_static::lazy_static;
+use rand::RngCore;
+use ring::aead::{Aad, LessSafeKey, Nonce, UnboundKey, AES_256_GCM, NONCE_LEN};
+use serde::Deserialize;
+use sha2::Sha256;
+
+use crate::business::encryption::{EncryptionError, FieldEncryptorTrait};
+
+const CIPHERTEXT_PREFIX: &str = "enc:v1:";
+const KEY_LEN: usize = 32;
+
+lazy_static! {
+    static ref KEY_ID_REGEX: regex::Regex = regex::Regex::new(r"^[A-Za-z0-9_.-]+$").unwrap();
+}
+
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct KeyFile {
+    active_key: String,
+    keys: BTreeMap<String, String>,
+    index_key: String,
+}
+
+/// AES-256-GCM encryption with keys read from a local JSON file:
+///
+/// ```json
+/// {
+///   "active_key": "2024-10",
+///   "keys": {"2024-10": "<64 hex digits>", "2024-01": "<64 hex digits>"},
+///   "index_key": "<64 hex digits>"
+/// }
+/// ```
+///
+/// Values are encrypted under `active_key` and written as
+/// `enc:v1:<key id>:<hex nonce and ciphertext>`, so the other keys are only
+/// used to decrypt what they encrypted before a rotation. The blind index is
+/// an HMAC-SHA256 under `index_key`, which is not rotated.
+#[derive(Clone)]
+pub struct LocalKeyFileEncryptor {
+    keys: BTreeMap<String, Arc<LessSafeKey>>,
+    active_key: String,
+    index_key: Arc<Vec<u8>>,
+}
+
+fn invalid_key_file(reason: impl Into<String>) -> EncryptionError {
+    EncryptionError::InvalidKeyFile {
+        reason: reason.into(),
+    }
+}
+
+impl LocalKeyFileEncryptor {
+    pub fn new(index_key: &[u8], active_key: &str, key: &[u8]) -> Result<Self, EncryptionError> {
+        if index_key.len() < KEY_LEN {
+            return Err(invalid_key_file(format!(
+                "index key must be at least {KEY_LEN} bytes"
+            )));
+        }
+        Self {
+            keys: BTreeMap::new(),
+            active_key: active_key.to_string(),
+            index_key: Arc::new(index_key.to_vec()),
+        }
+        .with_key(active_key, key)
+    }
+
+    /// Adds a key that only decrypts, typically one replaced by a rotation.
+    pub fn with_key(mut self, key_id: &str, key: &[u8]) -> Result<Self, EncryptionError> {
+        if !KEY_ID_REGEX.is_match(key_id) {
+            return Err(invalid_key_file(format!("{key_id} is not a valid key id")));
+        }
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| invalid_key_file(format!("key {key_id} must be {KEY_LEN} bytes")))?;
+        self.keys
+            .insert(key_id.to_string(), Arc::new(LessSafeKey::new(key)));
+        Ok(self)
+    }
+
+    pub fn from_json(content: &[u8]) -> Result<Self, EncryptionError> {
+        let key_file: KeyFile =
+            serde_json::from_slice(content).map_err(|e| invalid_key_file(e.to_string()))?;
+        let decode = |name: &str, value: &str| {
+            hex::decode(value.trim()).map_err(|e| invalid_key_file(format!("{name}: {e}")))
+        };
+        let active = key_file
+            .keys
+            .get(&key_file.active_key)
+            .ok_or_else(|| invalid_key_file(format!("no key {}", key_file.active_key)))?;
+        key_file.keys.iter().try_fold(
+            Self::new(
+                &decode("index_key", &key_file.index_key)?,
+                &key_file.active_key,
+                &decode(&key_file.active_key, active)?,
+            )?,
+            |encryptor, (key_id, key)| encryptor.with_key(key_id, &decode(key_id, key)?),
+        )
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, EncryptionError> {
+        let content = std::fs::read(path)
+            .map_err(|e| invalid_key_file(format!("cannot read {}: {}", path.display(), e)))?;
+        Self::from_json(&content)
+    }
+
+    pub fn get_active_key(&self) -> &str {
+        &self.active_key
+    }
+}
+
+impl Debug for LocalKeyFileEncryptor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("LocalKeyFileEncryptor")
+            .field("keys", &self.keys.keys().collect::<Vec<_>>())
+            .field("active_key", &self.active_key)
+            .finish_non_exhaustive()
+    }
+}
+
+impl FieldEncryptorTrait for LocalKeyFileEncryptor {
+    fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError> {
+        let key = self
+            .keys
+            .get(&self.active_key)
+            .ok_or_else(|| EncryptionError::UnknownKey {
+                key_id: self.active_key.clone(),
+            })?;
+        let mut nonce = [0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let mut sealed = plaintext.as_bytes().to_vec();
+        key.seal_in_place_append_tag(
+            Nonce::assume_unique_for_key(nonce),
+            Aad::from(self.active_key.as_bytes()),
+            &mut sealed,
+        )
+        .map_err(|_| EncryptionError::EncryptionFailed)?;
+        Ok(format!(
+            "{}{}:{}{}",
+            CIPHERTEXT_PREFIX,
+            self.active_key,
+            hex::encode(nonce),
+            hex::encode(sealed)
+        ))
+    }
+
+    /// Values stored before encryption was enabled are returned as they are.
+    fn decrypt(&self, ciphertext: &str) -> Result<String, EncryptionError> {
+        let Some(encrypted) = ciphertext.strip_prefix(CIPHERTEXT_PREFIX) else {
+            return Ok(ciphertext.to_string());
+        };
+        let (key_id, payload) = encrypted
+            .split_once(':')
+            .ok_or(EncryptionError::MalformedCiphertext)?;
+        let key = self
+            .keys
+            .get(key_id)
+            .ok_or_else(|| EncryptionError::UnknownKey {
+                key_id: key_id.to_string(),
+            })?;
+        let payload = hex::decode(payload).map_err(|_| EncryptionError::MalformedCiphertext)?;
+        if payload.len() < NONCE_LEN + AES_256_GCM.tag_len() {
+            return Err(EncryptionError::MalformedCiphertext);
+        }
+        let (nonce, sealed) = payload.split_at(NONCE_LEN);
+        let nonce = Nonce::try_assume_unique_for_key(nonce)
+            .map_err(|_| EncryptionError::MalformedCiphertext)?;
+        let mut sealed = sealed.to_vec();
+        let plaintext = key
+            .open_in_place(nonce, Aad::from(key_id.as_bytes()), &mut sealed)
+            .map_err(|_| EncryptionError::DecryptionFailed)?;
+        String::from_utf8(plaintext.to_vec()).map_err(|_| EncryptionError::DecryptionFailed)
+    }
+
+    fn blind_index(&self, value: &str) -> String {
+        let mut mac = Hmac::<Sha256>::new_from_slice(&self.index_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(value.as_bytes());
+        hex::encode(mac.finalize().into_bytes())
+    }
+
+    fn needs_rotation(&self, stored: &str) -> bool {
+        !stored
+            .strip_prefix(CIPHERTEXT_PREFIX)
+            .and_then(|encrypted| encrypted.split_once(':'))
+            .is_some_and(|(key_id, _)| key_id == self.active_key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::business::encryption::{EncryptionError, FieldEncryptorTrait};
+
+    use super::LocalKeyFileEncryptor;
+
+    fn encryptor(active_key: &str) -> LocalKeyFileEncryptor {
+        LocalKeyFileEncryptor::new(&[7; 32], active_key, &[active_key.len() as u8; 32]).unwrap()
+    }
+
+    #[test]
+    fn test_encrypt_decrypt() {
+        let encryptor = encryptor("k1");
+        let first = encryptor.encrypt("john@example.com").unwrap();
+        let second = encryptor.encrypt("john@example.com").unwrap();
+        assert!(first.starts_with("enc:v1:k1:"));
+        assert!(!first.contains("john"));
+        assert_ne!(first, second);
+        assert_eq!(encryptor.decrypt(&first).unwrap(), "john@example.com");
+        assert_eq!(
+            encryptor.blind_index("john@example.com"),
+            encryptor.blind_index("john@example.com")
+        );
+        assert_ne!(
+            encryptor.blind_index("john@example.com"),
+            encryptor.blind_index("jane@example.com")
+        );
+
+        let mut tampered = first.clone();
+        tampered.replace_range(tampered.len() - 2.., "00");
+        assert!(matches!(
+            encryptor.decrypt(&tampered),
+            Err(EncryptionError::DecryptionFailed)
+        ));
+    }
+
+    #[test]
+    fn test_rotation() {
+        let old = encryptor("k1");
+        let stored = old.encrypt("John").unwrap();
+        let rotated = encryptor("k22").with_key("k1", &[2; 32]).unwrap();
+        assert!(rotated.needs_rotation(&stored));
+        assert!(rotated.needs_rotation("John"));
+        assert_eq!(rotated.decrypt(&stored).unwrap(), "John");
+        assert_eq!(rotated.decrypt("John").unwrap(), "John");
+        let reencrypted = rotated.encrypt("John").unwrap();
+        assert!(!rotated.needs_rotation(&reencrypted));
+        assert_eq!(old.blind_index("John"), rotated.blind_index("John"));
+        assert!(matches!(
+            encryptor("k3").decrypt(&stored),
+            Err(EncryptionError::UnknownKey { .. })
+        ));
+    }
+
+    #[test]
+    fn test_from_json() {
+        let key = "11".repeat(32);
+        let content = format!(
+            r#"{{"active_key": "2024-10", "keys": {{"2024-10": "{key}", "2024-01": "{}"}}, "index_key": "{key}"}}"#,
+            "22".repeat(32)
+        );
+        let encryptor = LocalKeyFileEncryptor::from_json(content.as_bytes()).unwrap();
+        assert_eq!(encryptor.get_active_key(), "2024-10");
+        let missing = content.replace(r#""active_key": "2024-10""#, r#""active_key": "2025""#);
+        assert!(LocalKeyFileEncryptor::from_json(missing.as_bytes()).is_err());
+        let short = content.replace(&"22".repeat(32), "22");
+        assert!(LocalKeyFileEncryptor::from_json(short.as_bytes()).is_err());
+    }
+}
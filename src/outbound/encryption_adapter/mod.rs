@@ -0,0 +1,2 @@
+pub mod local_key_file_encryptor;
+pub mod plaintext_field_encryptor;
@@ -0,0 +1,23 @@
+use crate::business::encryption::{EncryptionError, FieldEncryptorTrait};
+
+/// Stores values as they are, for repositories without encryption.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PlaintextFieldEncryptor;
+
+impl FieldEncryptorTrait for PlaintextFieldEncryptor {
+    fn encrypt(&self, plaintext: &str) -> Result<String, EncryptionError> {
+        Ok(plaintext.to_string())
+    }
+
+    fn decrypt(&self, ciphertext: &str) -> Result<String, EncryptionError> {
+        Ok(ciphertext.to_string())
+    }
+
+    fn blind_index(&self, value: &str) -> String {
+        value.to_string()
+    }
+
+    fn needs_rotation(&self, _stored: &str) -> bool {
+        false
+    }
+}
@@ -0,0 +1,69 @@
+use std::future::Future;
+
+use tokio::sync::broadcast;
+
+use crate::business::user::{model::user::UserError, EventPublisherTrait, UserEvent};
+
+#[derive(Debug, Clone)]
+pub struct BroadcastEventPublisher {
+    sender: broadcast::Sender<UserEvent>,
+}
+
+impl Default for BroadcastEventPublisher {
+    fn default() -> Self {
+        Self::new(1024)
+    }
+}
+
+impl BroadcastEventPublisher {
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _) = broadcast::channel(capacity);
+        Self { sender }
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<UserEvent> {
+        self.sender.subscribe()
+    }
+}
+
+impl EventPublisherTrait for BroadcastEventPublisher {
+    fn publish(&self, event: &UserEvent) -> impl Future<Output = Result<(), UserError>> + Send {
+        // Having no subscriber is not a failure: the event is delivered to nobody.
+        let _ = self.sender.send(event.clone());
+        Box::pin(async { Ok(()) })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::user::{EmailAddress, EventPublisherTrait, Name, User, UserEvent};
+
+    use super::BroadcastEventPublisher;
+
+    #[tokio::test]
+    async fn test_subscribers_receive_events() {
+        let publisher = BroadcastEventPublisher::new(8);
+        publisher
+            .publish(&UserEvent::created(&User::new(
+                &Uuid::new_v4(),
+                &Name::new("Lost").unwrap(),
+                &Name::new("Event").unwrap(),
+                &EmailAddress::new("lost@example.com").unwrap(),
+            )))
+            .await
+            .unwrap();
+        let mut first = publisher.subscribe();
+        let mut second = publisher.subscribe();
+        let event = UserEvent::created(&User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        ));
+        publisher.publish(&event).await.unwrap();
+        assert_eq!(first.recv().await.unwrap(), event);
+        assert_eq!(second.recv().await.unwrap(), event);
+    }
+}
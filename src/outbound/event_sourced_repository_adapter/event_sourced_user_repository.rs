@@ -0,0 +1,558 @@
+use std::{
+    collections::{BTreeSet, HashMap},
+    future::Future,
+    sync::Arc,
+};
+
+use tokio::sync::{OwnedRwLockWriteGuard, RwLock};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        audit::AuditContext,
+        auth::TenantId,
+        user::{
+            dtos::{UserFindRequest, UserFindResponse},
+            model::user::UserError,
+            EmailAddress, User, UserChangeFeedTrait, UserChangeSubscription, UserEvent,
+            UserRepositoryTrait,
+        },
+    },
+    outbound::{
+        in_memory_repository_adapter::{
+            in_memory_user_repository::find_page,
+            user_change_log::UserChangeLog,
+            user_change_set::{UserChangeSet, UserLookupTrait},
+            user_outbox::UserOutbox,
+        },
+        repository_trait::{OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait},
+    },
+};
+
+#[derive(Debug, Clone)]
+struct UserSnapshot {
+    version: usize,
+    taken_at: u64,
+    state: Option<User>,
+}
+
+#[derive(Debug, Default)]
+struct UserStream {
+    events: Vec<UserEvent>,
+    snapshots: Vec<UserSnapshot>,
+}
+
+impl UserStream {
+    /// Rebuilds the state from the latest usable snapshot, replaying only the
+    /// events that occurred up to `as_of` when given.
+    fn replay(&self, as_of: Option<u64>) -> Option<User> {
+        let visible = |occurred_at: u64| as_of.map_or(true, |as_of| occurred_at <= as_of);
+        let snapshot = self
+            .snapshots
+            .iter()
+            .rev()
+            .find(|snapshot| visible(snapshot.taken_at));
+        let (version, state) = snapshot.map_or((0, None), |snapshot| {
+            (snapshot.version, snapshot.state.clone())
+        });
+        self.events[version..]
+            .iter()
+            .take_while(|event| visible(event.get_occurred_at()))
+            .fold(state, |_, event| event.apply())
+    }
+
+    fn current(&self) -> Option<User> {
+        self.replay(None)
+    }
+
+    /// Rewrites the history with the erased user, so that no past state can
+    /// be replayed anymore.
+    fn redact(&mut self, erased: &User) {
+        self.events
+            .iter_mut()
+            .for_each(|event| event.redact(erased));
+        self.snapshots
+            .iter_mut()
+            .filter(|snapshot| snapshot.state.is_some())
+            .for_each(|snapshot| snapshot.state = Some(erased.clone()));
+    }
+}
+
+#[derive(Debug, Default)]
+struct EventStore {
+    streams: HashMap<Uuid, UserStream>,
+    /// Ids of the current users by email, kept as events are appended so the
+    /// uniqueness check does not replay every stream.
+    emails: HashMap<EmailAddress, BTreeSet<Uuid>>,
+    outbox: UserOutbox,
+    changes: UserChangeLog,
+    snapshot_interval: usize,
+}
+
+impl UserLookupTrait for EventStore {
+    fn find_user(&self, user_id: &Uuid) -> Option<User> {
+        self.streams.get(user_id).and_then(UserStream::current)
+    }
+
+    fn find_ids_by_email(&self, email: &EmailAddress) -> Vec<Uuid> {
+        self.emails
+            .get(email)
+            .map_or(Vec::new(), |ids| ids.iter().copied().collect())
+    }
+}
+
+impl EventStore {
+    fn append(&mut self, user_id: &Uuid, event: UserEvent) {
+        let interval = self.snapshot_interval;
+        let stream = self.streams.entry(*user_id).or_default();
+        if let Some(before) = stream.current() {
+            if let Some(ids) = self.emails.get_mut(before.get_email()) {
+                ids.remove(user_id);
+                if ids.is_empty() {
+                    self.emails.remove(before.get_email());
+                }
+            }
+        }
+        if let Some(after) = event.apply() {
+            self.emails
+                .entry(after.get_email().clone())
+                .or_default()
+                .insert(*user_id);
+        }
+        if let Some(erased) = event.get_erased_user() {
+            stream.redact(erased);
+            self.changes.redact(erased);
+        }
+        stream.events.push(event.clone());
+        let version = stream.events.len();
+        if interval > 0 && version % interval == 0 {
+            let state = stream.current();
+            stream.snapshots.push(UserSnapshot {
+                version,
+                taken_at: event.get_occurred_at(),
+                state,
+            });
+        }
+        self.changes.record(&event);
+        self.outbox.push(event);
+    }
+
+    fn current_users(&self) -> Vec<User> {
+        self.streams
+            .values()
+            .filter_map(UserStream::current)
+            .collect()
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct EventSourcedUserRepository {
+    data: Arc<RwLock<EventStore>>,
+    tenant: TenantId,
+    audit: Option<AuditContext>,
+}
+
+impl Default for EventSourcedUserRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventSourcedUserRepository {
+    pub fn new() -> Self {
+        Self::with_snapshot_interval(50)
+    }
+
+    /// Takes a snapshot of a user every `snapshot_interval` events, 0 disables them.
+    pub fn with_snapshot_interval(snapshot_interval: usize) -> Self {
+        EventSourcedUserRepository {
+            data: Arc::new(RwLock::new(EventStore {
+                snapshot_interval,
+                ..Default::default()
+            })),
+            tenant: TenantId::default(),
+            audit: None,
+        }
+    }
+
+    /// Returns the user as it was at `timestamp`, in milliseconds since the Unix epoch.
+    pub async fn find_by_id_as_of(
+        &self,
+        user_id: &Uuid,
+        timestamp: u64,
+    ) -> Result<User, UserError> {
+        self.data
+            .read()
+            .await
+            .streams
+            .get(user_id)
+            .and_then(|stream| stream.replay(Some(timestamp)))
+            .filter(|user| user.get_tenant().eq(&self.tenant))
+            .ok_or(UserError::UserNotExists { id: *user_id })
+    }
+
+    pub async fn find_events(&self, user_id: &Uuid) -> Vec<UserEvent> {
+        self.data
+            .read()
+            .await
+            .streams
+            .get(user_id)
+            .filter(|stream| {
+                stream
+                    .events
+                    .first()
+                    .is_some_and(|event| event.get_tenant().eq(&self.tenant))
+            })
+            .map_or(Vec::new(), |stream| stream.events.clone())
+    }
+}
+
+impl RepositoryTrait for EventSourcedUserRepository {
+    type Id = Uuid;
+    type Entity = User;
+    type Error = UserError;
+    type FindOptions = UserFindRequest;
+    type FindResult = UserFindResponse;
+
+    fn save(
+        &self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let mut transaction = self.begin().await?;
+            let user = transaction.save(entity).await?;
+            transaction.commit().await?;
+            Ok(user)
+        })
+    }
+
+    fn update(
+        &self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let mut transaction = self.begin().await?;
+            let user = transaction.update(entity_id, entity).await?;
+            transaction.commit().await?;
+            Ok(user)
+        })
+    }
+
+    fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            let mut transaction = self.begin().await?;
+            transaction.delete(entity_id).await?;
+            transaction.commit().await
+        })
+    }
+
+    fn find_all(
+        &self,
+        options: &Self::FindOptions,
+    ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
+        Box::pin(async {
+            let users = self.data.read().await.current_users();
+            Ok(find_page(
+                users
+                    .iter()
+                    .filter(|user| user.get_tenant().eq(&self.tenant)),
+                options,
+            ))
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async {
+            self.data
+                .read()
+                .await
+                .streams
+                .get(entity_id)
+                .and_then(UserStream::current)
+                .filter(|user| user.get_tenant().eq(&self.tenant))
+                .ok_or(UserError::UserNotExists { id: *entity_id })
+        })
+    }
+}
+
+impl UnitOfWorkTrait for EventSourcedUserRepository {
+    type Transaction = EventSourcedUserTransaction;
+
+    fn begin(&self) -> impl Future<Output = Result<Self::Transaction, Self::Error>> + Send {
+        Box::pin(async move {
+            Ok(EventSourcedUserTransaction {
+                store: self.data.clone().write_owned().await,
+                changes: UserChangeSet::new(&self.tenant, self.audit.as_ref()),
+            })
+        })
+    }
+}
+
+#[derive(Debug)]
+pub struct EventSourcedUserTransaction {
+    store: OwnedRwLockWriteGuard<EventStore>,
+    changes: UserChangeSet,
+}
+
+impl TransactionTrait for EventSourcedUserTransaction {
+    type Id = Uuid;
+    type Entity = User;
+    type Error = UserError;
+
+    fn save(
+        &mut self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.save(&*self.store, entity) })
+    }
+
+    fn update(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.update(&*self.store, entity_id, entity) })
+    }
+
+    fn erase(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.erase(&*self.store, entity_id, entity) })
+    }
+
+    fn delete(
+        &mut self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move { self.changes.delete(&*self.store, entity_id) })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.changes
+                .find(&*self.store, entity_id)
+                .ok_or(UserError::UserNotExists { id: *entity_id })
+        })
+    }
+
+    fn commit(mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            let (_, events) = self.changes.into_parts();
+            for event in events {
+                let user_id = *event.get_user_id();
+                self.store.append(&user_id, event);
+            }
+            Ok(())
+        })
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl OutboxTrait for EventSourcedUserRepository {
+    type Event = UserEvent;
+
+    fn pending_events(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<Self::Event>, Self::Error>> + Send {
+        Box::pin(async move { Ok(self.data.read().await.outbox.pending(limit)) })
+    }
+
+    fn acknowledge_event(
+        &self,
+        event: &Self::Event,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            self.data.write().await.outbox.acknowledge(event.get_id());
+            Ok(())
+        })
+    }
+}
+
+impl UserChangeFeedTrait for EventSourcedUserRepository {
+    fn subscribe_changes(
+        &self,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .data
+                .read()
+                .await
+                .changes
+                .subscribe(last_sequence)
+                .with_tenant(&self.tenant))
+        })
+    }
+}
+
+impl UserRepositoryTrait for EventSourcedUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+
+    fn audited(&self, audit: &AuditContext) -> Self {
+        Self {
+            audit: Some(audit.clone()),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use crate::{
+        business::user::{model::user::UserError, EmailAddress, Name, User},
+        outbound::repository_trait::{
+            OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait,
+        },
+    };
+
+    use super::EventSourcedUserRepository;
+
+    fn user(firstname: &str) -> User {
+        User::new(
+            &Uuid::new_v4(),
+            &Name::new(firstname).unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        )
+    }
+
+    fn renamed(user: &User, firstname: &str) -> User {
+        User::new(
+            user.get_id(),
+            &Name::new(firstname).unwrap(),
+            user.get_lastname(),
+            user.get_email(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_replay_with_snapshots() {
+        let repository = EventSourcedUserRepository::with_snapshot_interval(2);
+        let created = repository.save(&user("Anna")).await.unwrap();
+        for firstname in ["Bella", "Carla", "Dana", "Emma"] {
+            repository
+                .update(created.get_id(), &renamed(&created, firstname))
+                .await
+                .unwrap();
+        }
+        let current = repository.find_by_id(created.get_id()).await.unwrap();
+        assert_eq!(current.get_firstname().to_string(), "Emma");
+        assert_eq!(repository.find_events(created.get_id()).await.len(), 5);
+        let data = repository.data.read().await;
+        let stream = data.streams.get(created.get_id()).unwrap();
+        assert_eq!(stream.snapshots.len(), 2);
+        assert_eq!(stream.snapshots[1].version, 4);
+        assert_eq!(repository.pending_events(10).await.unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_find_as_of() {
+        let repository = EventSourcedUserRepository::with_snapshot_interval(1);
+        let created = repository.save(&user("Anna")).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        repository
+            .update(created.get_id(), &renamed(&created, "Bella"))
+            .await
+            .unwrap();
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        repository.delete(created.get_id()).await.unwrap();
+
+        let events = repository.find_events(created.get_id()).await;
+        let as_of = |index: usize| events[index].get_occurred_at();
+        let first = repository
+            .find_by_id_as_of(created.get_id(), as_of(0))
+            .await
+            .unwrap();
+        assert_eq!(first.get_firstname().to_string(), "Anna");
+        let second = repository
+            .find_by_id_as_of(created.get_id(), as_of(1))
+            .await
+            .unwrap();
+        assert_eq!(second.get_firstname().to_string(), "Bella");
+        assert!(matches!(
+            repository
+                .find_by_id_as_of(created.get_id(), as_of(2))
+                .await,
+            Err(UserError::UserNotExists { id: _ })
+        ));
+        assert!(repository
+            .find_by_id_as_of(created.get_id(), as_of(0) - 1)
+            .await
+            .is_err());
+        assert!(repository.find_by_id(created.get_id()).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_email_projection_follows_events() {
+        let repository = EventSourcedUserRepository::new();
+        let created = repository.save(&user("Anna")).await.unwrap();
+        assert!(matches!(
+            repository.save(&user("Bella")).await,
+            Err(UserError::EmailAlreadyUsed { email: _ })
+        ));
+        let moved = User::new(
+            created.get_id(),
+            created.get_firstname(),
+            created.get_lastname(),
+            &EmailAddress::new("anna@example.com").unwrap(),
+        );
+        repository.update(created.get_id(), &moved).await.unwrap();
+        let other = repository.save(&user("Bella")).await.unwrap();
+        repository.delete(other.get_id()).await.unwrap();
+        repository.save(&user("Carla")).await.unwrap();
+        let data = repository.data.read().await;
+        assert_eq!(data.emails.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_erase_rewrites_history() {
+        let repository = EventSourcedUserRepository::with_snapshot_interval(2);
+        let created = repository.save(&user("Anna")).await.unwrap();
+        repository
+            .update(created.get_id(), &renamed(&created, "Bella"))
+            .await
+            .unwrap();
+        let mut erased = created.clone();
+        erased.anonymize();
+        let mut transaction = repository.begin().await.unwrap();
+        let erased = transaction.erase(created.get_id(), &erased).await.unwrap();
+        transaction.commit().await.unwrap();
+
+        let events = repository.find_events(created.get_id()).await;
+        assert_eq!(events.len(), 3);
+        let pending = repository.pending_events(10).await.unwrap();
+        for event in events.iter().chain(pending.iter()) {
+            let serialized = serde_json::to_string(event).unwrap();
+            assert!(!serialized.contains("example.com") && !serialized.contains("Doe"));
+        }
+        assert_eq!(
+            repository
+                .find_by_id_as_of(created.get_id(), events[0].get_occurred_at())
+                .await
+                .unwrap(),
+            erased
+        );
+    }
+}
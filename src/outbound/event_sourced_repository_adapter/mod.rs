@@ -0,0 +1 @@
+pub mod event_sourced_user_repository;
@@ -0,0 +1,192 @@
+use std::{collections::HashMap, future::Future, path::Path, sync::Arc, time::SystemTime};
+
+use uuid::Uuid;
+
+use crate::{
+    business::credential::{
+        CredentialError, CredentialFindRequest, CredentialFindResponse, CredentialRepositoryTrait,
+        LockoutPolicy, PasswordCredential,
+    },
+    outbound::{
+        in_memory_repository_adapter::in_memory_credential_repository::find_page,
+        repository_trait::RepositoryTrait,
+    },
+};
+
+use super::json_file::JsonFile;
+
+const CREDENTIALS_FILE: &str = "credentials.json";
+
+/// Password credentials kept next to the users, in `credentials.json`.
+#[derive(Debug, Clone)]
+pub struct FileCredentialRepository {
+    data: Arc<JsonFile<HashMap<Uuid, PasswordCredential>>>,
+}
+
+impl FileCredentialRepository {
+    /// Opens the credentials stored in `directory`.
+    pub async fn open(directory: &Path) -> Result<Self, CredentialError> {
+        Ok(Self {
+            data: Arc::new(JsonFile::open(&directory.join(CREDENTIALS_FILE)).await?),
+        })
+    }
+
+    async fn change<F>(
+        &self,
+        user_id: &Uuid,
+        change: F,
+    ) -> Result<PasswordCredential, CredentialError>
+    where
+        F: FnOnce(&mut PasswordCredential),
+    {
+        self.data
+            .update(|data| {
+                let credential = data
+                    .get_mut(user_id)
+                    .ok_or(CredentialError::CredentialNotExists { id: *user_id })?;
+                change(credential);
+                Ok(credential.clone())
+            })
+            .await
+    }
+}
+
+impl RepositoryTrait for FileCredentialRepository {
+    type Id = Uuid;
+    type Entity = PasswordCredential;
+    type Error = CredentialError;
+    type FindOptions = CredentialFindRequest;
+    type FindResult = CredentialFindResponse;
+
+    fn save(
+        &self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| {
+                    if data.contains_key(entity.get_user_id()) {
+                        return Err(CredentialError::CredentialAlreadyExists {
+                            id: *entity.get_user_id(),
+                        });
+                    }
+                    data.insert(*entity.get_user_id(), entity.clone());
+                    Ok(entity.clone())
+                })
+                .await
+        })
+    }
+
+    fn update(
+        &self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            if entity.get_user_id().ne(entity_id) {
+                return Err(CredentialError::CredentialNotExists { id: *entity_id });
+            }
+            self.change(entity_id, |credential| *credential = entity.clone())
+                .await
+        })
+    }
+
+    fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| match data.remove(entity_id) {
+                    Some(_) => Ok(()),
+                    None => Err(CredentialError::CredentialNotExists { id: *entity_id }),
+                })
+                .await
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async {
+            match self.data.read().await.get(entity_id) {
+                Some(c) => Ok(c.clone()),
+                None => Err(CredentialError::CredentialNotExists { id: *entity_id }),
+            }
+        })
+    }
+
+    fn find_all(
+        &self,
+        options: &Self::FindOptions,
+    ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
+        Box::pin(async { Ok(find_page(self.data.read().await.values(), options)) })
+    }
+}
+
+impl CredentialRepositoryTrait for FileCredentialRepository {
+    fn record_failure(
+        &self,
+        user_id: &Uuid,
+        policy: &LockoutPolicy,
+        now: &SystemTime,
+    ) -> impl Future<Output = Result<PasswordCredential, CredentialError>> + Send {
+        Box::pin(async move {
+            self.change(user_id, |credential| credential.record_failure(policy, now))
+                .await
+        })
+    }
+
+    fn record_success(
+        &self,
+        user_id: &Uuid,
+        now: &SystemTime,
+    ) -> impl Future<Output = Result<PasswordCredential, CredentialError>> + Send {
+        Box::pin(async move {
+            self.change(user_id, |credential| {
+                if !credential.is_locked(now) {
+                    credential.record_success();
+                }
+            })
+            .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::Role,
+            credential::{CredentialRepositoryTrait, LockoutPolicy, PasswordCredential},
+        },
+        outbound::repository_trait::RepositoryTrait,
+    };
+
+    use super::FileCredentialRepository;
+
+    #[tokio::test]
+    async fn test_credentials_survive_restart() {
+        let directory = std::env::temp_dir().join(format!("credentials-{}", Uuid::new_v4()));
+        let user_id = Uuid::new_v4();
+        let repository = FileCredentialRepository::open(&directory).await.unwrap();
+        let mut credential = PasswordCredential::new(&user_id, "hash", &[]);
+        repository.save(&credential).await.unwrap();
+        credential.set_roles(&[Role::new("admin")]);
+        repository.update(&user_id, &credential).await.unwrap();
+        let policy = LockoutPolicy::new(1, Duration::from_secs(60));
+        let now = SystemTime::now();
+        repository
+            .record_failure(&user_id, &policy, &now)
+            .await
+            .unwrap();
+
+        let reopened = FileCredentialRepository::open(&directory).await.unwrap();
+        let stored = reopened.find_by_id(&user_id).await.unwrap();
+        assert_eq!(stored.get_roles(), &[Role::new("admin")]);
+        assert!(stored.is_locked(&now));
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+}
@@ -0,0 +1,1132 @@
+use std::{
+    collections::{BTreeSet, HashMap, HashSet},
+    future::Future,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tokio::{
+    fs::{File, OpenOptions},
+    io::AsyncWriteExt,
+    sync::{OwnedRwLockWriteGuard, RwLock},
+};
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        audit::AuditContext,
+        auth::TenantId,
+        encryption::FieldEncryptorTrait,
+        user::{
+            dtos::{UserFindRequest, UserFindResponse},
+            model::user::UserError,
+            EmailAddress, User, UserChangeFeedTrait, UserChangeSubscription, UserEvent,
+            UserEventKind, UserRepositoryTrait,
+        },
+    },
+    outbound::{
+        encryption_adapter::plaintext_field_encryptor::PlaintextFieldEncryptor,
+        in_memory_repository_adapter::{
+            in_memory_user_repository::find_page,
+            user_change_log::UserChangeLog,
+            user_change_set::{UserChangeSet, UserLookupTrait},
+            user_outbox::UserOutbox,
+        },
+        repository_trait::{
+            FindOptionTrait, OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait,
+        },
+    },
+};
+
+const LOG_FILE: &str = "users.log";
+const SNAPSHOT_FILE: &str = "users.snapshot.json";
+
+const ENCRYPTED_FIELDS: [&str; 3] = ["firstname", "lastname", "email"];
+const EMAIL_INDEX_FIELD: &str = "email_index";
+const TENANT_FIELD: &str = "tenant";
+
+/// A user as written to disk: its names and email go through the encryptor,
+/// the email is also kept as a blind index, and the tenant is added.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(transparent)]
+struct StoredUser(serde_json::Map<String, Value>);
+
+impl StoredUser {
+    fn seal(user: &User, encryptor: &impl FieldEncryptorTrait) -> Result<Self, UserError> {
+        let mut fields = match serde_json::to_value(user).map_err(storage_error)? {
+            Value::Object(fields) => fields,
+            _ => return Err(storage_error(anyhow::anyhow!("user is not an object"))),
+        };
+        for field in ENCRYPTED_FIELDS {
+            if let Some(Value::String(value)) = fields.get_mut(field) {
+                *value = encryptor.encrypt(value).map_err(storage_error)?;
+            }
+        }
+        let email_index = encryptor.blind_index(user.get_email());
+        // Without encryption the email is its own index.
+        if email_index.ne(&**user.get_email()) {
+            fields.insert(EMAIL_INDEX_FIELD.to_string(), Value::String(email_index));
+        }
+        fields.insert(
+            TENANT_FIELD.to_string(),
+            Value::String(user.get_tenant().to_string()),
+        );
+        Ok(Self(fields))
+    }
+
+    fn unseal(mut self, encryptor: &impl FieldEncryptorTrait) -> Result<User, UserError> {
+        self.0.remove(EMAIL_INDEX_FIELD);
+        for field in ENCRYPTED_FIELDS {
+            if let Some(Value::String(value)) = self.0.get_mut(field) {
+                *value = encryptor.decrypt(value).map_err(storage_error)?;
+            }
+        }
+        // The model's value objects borrow their input, which a `Value` cannot lend.
+        let content = serde_json::to_string(&self.0).map_err(storage_error)?;
+        serde_json::from_str(&content).map_err(storage_error)
+    }
+
+    fn needs_rotation(&self, encryptor: &impl FieldEncryptorTrait) -> bool {
+        ENCRYPTED_FIELDS.iter().any(|field| {
+            matches!(self.0.get(*field), Some(Value::String(value)) if encryptor.needs_rotation(value))
+        })
+    }
+}
+
+/// A pending event as written to disk, its snapshots sealed like users and
+/// its audit record, which holds the changed values, encrypted whole.
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredEvent {
+    id: Uuid,
+    occurred_at: u64,
+    #[serde(flatten)]
+    kind: StoredEventKind,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    audit: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "type")]
+enum StoredEventKind {
+    #[serde(rename = "UserCreated")]
+    Created { after: Box<StoredUser> },
+    #[serde(rename = "UserUpdated")]
+    Updated {
+        before: Box<StoredUser>,
+        after: Box<StoredUser>,
+    },
+    #[serde(rename = "UserDeleted")]
+    Deleted { before: Box<StoredUser> },
+    #[serde(rename = "UserErased")]
+    Erased { after: Box<StoredUser> },
+}
+
+impl StoredEvent {
+    fn seal(event: &UserEvent, encryptor: &impl FieldEncryptorTrait) -> Result<Self, UserError> {
+        let seal = |user: &User| StoredUser::seal(user, encryptor).map(Box::new);
+        let kind = match event.get_kind() {
+            UserEventKind::UserCreated { after } => StoredEventKind::Created {
+                after: seal(after)?,
+            },
+            UserEventKind::UserUpdated { before, after } => StoredEventKind::Updated {
+                before: seal(before)?,
+                after: seal(after)?,
+            },
+            UserEventKind::UserDeleted { before } => StoredEventKind::Deleted {
+                before: seal(before)?,
+            },
+            UserEventKind::UserErased { after } => StoredEventKind::Erased {
+                after: seal(after)?,
+            },
+        };
+        let audit = match event.get_audit() {
+            Some(audit) => {
+                let content = serde_json::to_string(audit).map_err(storage_error)?;
+                Some(encryptor.encrypt(&content).map_err(storage_error)?)
+            }
+            None => None,
+        };
+        Ok(Self {
+            id: *event.get_id(),
+            occurred_at: event.get_occurred_at(),
+            kind,
+            audit,
+        })
+    }
+
+    fn unseal(self, encryptor: &impl FieldEncryptorTrait) -> Result<UserEvent, UserError> {
+        let unseal = |user: Box<StoredUser>| user.unseal(encryptor).map(Box::new);
+        let kind = match self.kind {
+            StoredEventKind::Created { after } => UserEventKind::UserCreated {
+                after: unseal(after)?,
+            },
+            StoredEventKind::Updated { before, after } => UserEventKind::UserUpdated {
+                before: unseal(before)?,
+                after: unseal(after)?,
+            },
+            StoredEventKind::Deleted { before } => UserEventKind::UserDeleted {
+                before: unseal(before)?,
+            },
+            StoredEventKind::Erased { after } => UserEventKind::UserErased {
+                after: unseal(after)?,
+            },
+        };
+        let event = UserEvent::new(kind)
+            .with_id(&self.id)
+            .with_occurred_at(self.occurred_at);
+        match self.audit {
+            Some(audit) => {
+                let content = encryptor.decrypt(&audit).map_err(storage_error)?;
+                let audit = serde_json::from_str(&content).map_err(storage_error)?;
+                Ok(event.with_audit(&audit))
+            }
+            None => Ok(event),
+        }
+    }
+}
+
+/// `Commit` holds the changes of a transaction with the events they raised,
+/// so the outbox survives a restart; `Acknowledge` removes a published event.
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "op")]
+enum LogRecord {
+    Put {
+        user: Box<StoredUser>,
+    },
+    Delete {
+        id: Uuid,
+    },
+    Commit {
+        records: Vec<LogRecord>,
+        events: Vec<StoredEvent>,
+    },
+    Acknowledge {
+        id: Uuid,
+    },
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct Snapshot {
+    users: Vec<StoredUser>,
+    outbox: Vec<StoredEvent>,
+}
+
+impl<E: FieldEncryptorTrait> UserLookupTrait for FileUserStore<E> {
+    fn find_user(&self, user_id: &Uuid) -> Option<User> {
+        self.users.get(user_id).cloned()
+    }
+
+    fn find_ids_by_email(&self, email: &EmailAddress) -> Vec<Uuid> {
+        self.find_by_email(email)
+            .map(|user| *user.get_id())
+            .collect()
+    }
+}
+
+#[derive(Debug)]
+struct FileUserStore<E: FieldEncryptorTrait> {
+    users: HashMap<Uuid, User>,
+    /// Ids of the users by blind index of their email.
+    emails: HashMap<String, BTreeSet<Uuid>>,
+    /// Users whose stored values are not encrypted under the active key.
+    stale: HashSet<Uuid>,
+    encryptor: E,
+    outbox: UserOutbox,
+    changes: UserChangeLog,
+    log: File,
+    /// Length of the complete records of the log.
+    log_len: u64,
+    log_records: usize,
+    /// Set when a failed append could not be cut off the log.
+    poisoned: bool,
+}
+
+impl<E: FieldEncryptorTrait> FileUserStore<E> {
+    fn apply(&mut self, record: LogRecord) -> Result<(), UserError> {
+        match record {
+            LogRecord::Put { user } => {
+                let stale = user.needs_rotation(&self.encryptor);
+                let user = user.unseal(&self.encryptor)?;
+                let user_id = *user.get_id();
+                self.put(user);
+                if stale {
+                    self.stale.insert(user_id);
+                }
+            }
+            LogRecord::Delete { id } => self.remove(&id),
+            LogRecord::Commit { records, events } => {
+                for record in records {
+                    self.apply(record)?;
+                }
+                for event in events {
+                    let event = event.unseal(&self.encryptor)?;
+                    self.outbox.push(event);
+                }
+            }
+            LogRecord::Acknowledge { id } => {
+                self.outbox.acknowledge(&id);
+            }
+        }
+        Ok(())
+    }
+
+    fn put(&mut self, user: User) {
+        self.remove(user.get_id());
+        self.emails
+            .entry(self.encryptor.blind_index(user.get_email()))
+            .or_default()
+            .insert(*user.get_id());
+        self.users.insert(*user.get_id(), user);
+    }
+
+    fn remove(&mut self, user_id: &Uuid) {
+        self.stale.remove(user_id);
+        if let Some(user) = self.users.remove(user_id) {
+            let email_index = self.encryptor.blind_index(user.get_email());
+            if let Some(ids) = self.emails.get_mut(&email_index) {
+                ids.remove(user_id);
+                if ids.is_empty() {
+                    self.emails.remove(&email_index);
+                }
+            }
+        }
+    }
+
+    /// Users with exactly this email, found through its blind index.
+    fn find_by_email<'a>(&'a self, email: &str) -> impl Iterator<Item = &'a User> + 'a {
+        self.emails
+            .get(&self.encryptor.blind_index(email))
+            .into_iter()
+            .flatten()
+            .filter_map(|id| self.users.get(id))
+    }
+}
+
+/// Keeps users in memory and persists every mutation to an append-only log,
+/// synced before the mutation is applied, and compacted into a snapshot.
+/// Names and emails are encrypted on disk by `E`.
+#[derive(Debug, Clone)]
+pub struct FileUserRepository<E: FieldEncryptorTrait = PlaintextFieldEncryptor> {
+    directory: PathBuf,
+    compaction_threshold: usize,
+    data: Arc<RwLock<FileUserStore<E>>>,
+    tenant: TenantId,
+    audit: Option<AuditContext>,
+}
+
+fn storage_error(e: impl Into<anyhow::Error>) -> UserError {
+    UserError::Unknown(e.into())
+}
+
+impl FileUserRepository {
+    /// Opens the repository stored in `directory` without encryption.
+    pub async fn open(directory: &Path) -> Result<Self, UserError> {
+        Self::open_with_encryptor(directory, PlaintextFieldEncryptor).await
+    }
+}
+
+impl<E: FieldEncryptorTrait> FileUserRepository<E> {
+    /// Opens the repository stored in `directory`, replaying the log over the
+    /// latest snapshot. A record torn by a crash at the end of the log is
+    /// discarded; a corrupted record anywhere else is an error.
+    pub async fn open_with_encryptor(directory: &Path, encryptor: E) -> Result<Self, UserError> {
+        tokio::fs::create_dir_all(directory)
+            .await
+            .map_err(storage_error)?;
+        let log_path = directory.join(LOG_FILE);
+        let (records, valid_len) = Self::read_log(&log_path).await?;
+        let log = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&log_path)
+            .await
+            .map_err(storage_error)?;
+        let mut store = FileUserStore {
+            users: HashMap::new(),
+            emails: HashMap::new(),
+            stale: HashSet::new(),
+            encryptor,
+            outbox: UserOutbox::default(),
+            changes: UserChangeLog::default(),
+            log,
+            log_len: valid_len,
+            log_records: records.len(),
+            poisoned: false,
+        };
+        let snapshot = Self::read_snapshot(&directory.join(SNAPSHOT_FILE)).await?;
+        for user in snapshot.users {
+            store.apply(LogRecord::Put {
+                user: Box::new(user),
+            })?;
+        }
+        for event in snapshot.outbox {
+            let event = event.unseal(&store.encryptor)?;
+            store.outbox.push(event);
+        }
+        for record in records {
+            store.apply(record)?;
+        }
+        if store.log.metadata().await.map_err(storage_error)?.len() > valid_len {
+            store.log.set_len(valid_len).await.map_err(storage_error)?;
+            store.log.sync_all().await.map_err(storage_error)?;
+        }
+        Ok(Self {
+            directory: directory.to_path_buf(),
+            compaction_threshold: 1000,
+            data: Arc::new(RwLock::new(store)),
+            tenant: TenantId::default(),
+            audit: None,
+        })
+    }
+
+    /// Compacts the log into a snapshot once it holds `threshold` records, 0 disables it.
+    pub fn with_compaction_threshold(mut self, threshold: usize) -> Self {
+        self.compaction_threshold = threshold;
+        self
+    }
+
+    async fn read_snapshot(path: &Path) -> Result<Snapshot, UserError> {
+        let content = match tokio::fs::read(path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Snapshot::default()),
+            Err(e) => return Err(storage_error(e)),
+        };
+        serde_json::from_slice(&content).map_err(storage_error)
+    }
+
+    /// Returns the records of the log and the length of its valid prefix.
+    async fn read_log(path: &Path) -> Result<(Vec<LogRecord>, u64), UserError> {
+        let content = match tokio::fs::read(path).await {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(storage_error(e)),
+        };
+        let mut records = Vec::new();
+        let mut valid_len = 0;
+        let mut lines = content.split_inclusive(|byte| *byte == b'\n').peekable();
+        while let Some(line) = lines.next() {
+            let is_last = lines.peek().is_none();
+            let record = match line.strip_suffix(b"\n") {
+                Some(line) => serde_json::from_slice::<LogRecord>(line),
+                None if is_last => break,
+                None => unreachable!(),
+            };
+            match record {
+                Ok(record) => {
+                    records.push(record);
+                    valid_len += line.len() as u64;
+                }
+                Err(_) if is_last => break,
+                Err(e) => {
+                    return Err(storage_error(anyhow::anyhow!(
+                        "corrupted record at byte {valid_len} of {}: {e}",
+                        path.display()
+                    )))
+                }
+            }
+        }
+        Ok((records, valid_len))
+    }
+
+    /// A failed write may leave part of the record behind, so the log is cut
+    /// back to its last complete record before anything else is appended. If
+    /// that fails too, the repository refuses every later write.
+    async fn append(
+        &self,
+        data: &mut FileUserStore<E>,
+        record: &LogRecord,
+    ) -> Result<(), UserError> {
+        if data.poisoned {
+            return Err(storage_error(anyhow::anyhow!(
+                "{} may hold a partial record, reopen the repository",
+                self.directory.join(LOG_FILE).display()
+            )));
+        }
+        let mut line = serde_json::to_vec(record).map_err(storage_error)?;
+        line.push(b'\n');
+        // Tokio reports a failed write on the next flush, not on sync.
+        let written = match data.log.write_all(&line).await {
+            Ok(()) => match data.log.flush().await {
+                Ok(()) => data.log.sync_data().await,
+                Err(e) => Err(e),
+            },
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
+            if self.truncate_log(data).await.is_err() {
+                data.poisoned = true;
+            }
+            return Err(storage_error(e));
+        }
+        data.log_len += line.len() as u64;
+        data.log_records += 1;
+        Ok(())
+    }
+
+    /// Reopens the log, whose handle may be unusable, and cuts it back.
+    async fn truncate_log(&self, data: &mut FileUserStore<E>) -> Result<(), UserError> {
+        let log = OpenOptions::new()
+            .append(true)
+            .open(self.directory.join(LOG_FILE))
+            .await
+            .map_err(storage_error)?;
+        log.set_len(data.log_len).await.map_err(storage_error)?;
+        log.sync_all().await.map_err(storage_error)?;
+        data.log = log;
+        Ok(())
+    }
+
+    /// Applies the compaction after the mutation so a failure leaves it durable.
+    async fn compact_if_needed(&self, data: &mut FileUserStore<E>) {
+        if self.compaction_threshold > 0 && data.log_records >= self.compaction_threshold {
+            self.compact_locked(data).await.ok();
+        }
+    }
+
+    /// Writes all users and pending events to a new snapshot, then truncates the log. Replaying a
+    /// log over a snapshot that already contains it is harmless, so a crash
+    /// between the two steps loses nothing.
+    pub async fn compact(&self) -> Result<(), UserError> {
+        let mut data = self.data.write().await;
+        self.compact_locked(&mut data).await
+    }
+
+    async fn compact_locked(&self, data: &mut FileUserStore<E>) -> Result<(), UserError> {
+        let mut users: Vec<&User> = data.users.values().collect();
+        users.sort_by_key(|user| user.get_id());
+        let users = users
+            .into_iter()
+            .map(|user| StoredUser::seal(user, &data.encryptor))
+            .collect::<Result<Vec<_>, _>>()?;
+        let outbox = data
+            .outbox
+            .iter()
+            .map(|event| StoredEvent::seal(event, &data.encryptor))
+            .collect::<Result<Vec<_>, _>>()?;
+        let content = serde_json::to_vec(&Snapshot { users, outbox }).map_err(storage_error)?;
+        let path = self.directory.join(SNAPSHOT_FILE);
+        let temporary = path.with_extension("json.tmp");
+        let mut snapshot = File::create(&temporary).await.map_err(storage_error)?;
+        snapshot.write_all(&content).await.map_err(storage_error)?;
+        snapshot.sync_all().await.map_err(storage_error)?;
+        tokio::fs::rename(&temporary, &path)
+            .await
+            .map_err(storage_error)?;
+        data.log.set_len(0).await.map_err(storage_error)?;
+        data.log.sync_all().await.map_err(storage_error)?;
+        data.log_len = 0;
+        data.log_records = 0;
+        data.stale.clear();
+        Ok(())
+    }
+
+    /// Re-encrypts under the active key the users still stored under another
+    /// one, or in clear, by compacting the log. Returns how many there were.
+    pub async fn rotate_keys(&self) -> Result<usize, UserError> {
+        let mut data = self.data.write().await;
+        let stale = data.stale.len();
+        if stale > 0 {
+            self.compact_locked(&mut data).await?;
+        }
+        Ok(stale)
+    }
+
+    fn record(data: &mut FileUserStore<E>, event: UserEvent) {
+        if let Some(erased) = event.get_erased_user() {
+            data.changes.redact(erased);
+        }
+        data.changes.record(&event);
+        data.outbox.push(event);
+    }
+}
+
+impl<E: FieldEncryptorTrait> RepositoryTrait for FileUserRepository<E> {
+    type Id = Uuid;
+    type Entity = User;
+    type Error = UserError;
+    type FindOptions = UserFindRequest;
+    type FindResult = UserFindResponse;
+
+    fn save(
+        &self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let mut transaction = self.begin().await?;
+            let user = transaction.save(entity).await?;
+            transaction.commit().await?;
+            Ok(user)
+        })
+    }
+
+    fn update(
+        &self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let mut transaction = self.begin().await?;
+            let user = transaction.update(entity_id, entity).await?;
+            transaction.commit().await?;
+            Ok(user)
+        })
+    }
+
+    fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            let mut transaction = self.begin().await?;
+            transaction.delete(entity_id).await?;
+            transaction.commit().await
+        })
+    }
+
+    fn find_all(
+        &self,
+        options: &Self::FindOptions,
+    ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
+        Box::pin(async {
+            let data = self.data.read().await;
+            let candidates: Vec<&User> = match options.get_query().email.as_deref() {
+                Some(email) if !email.ends_with('*') => data.find_by_email(email).collect(),
+                _ => data.users.values().collect(),
+            };
+            Ok(find_page(
+                candidates
+                    .into_iter()
+                    .filter(|user| user.get_tenant().eq(&self.tenant)),
+                options,
+            ))
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async {
+            match self.data.read().await.users.get(entity_id) {
+                Some(u) if u.get_tenant().eq(&self.tenant) => Ok(u.clone()),
+                _ => Err(UserError::UserNotExists { id: *entity_id }),
+            }
+        })
+    }
+}
+
+impl<E: FieldEncryptorTrait> UnitOfWorkTrait for FileUserRepository<E> {
+    type Transaction = FileUserTransaction<E>;
+
+    fn begin(&self) -> impl Future<Output = Result<Self::Transaction, Self::Error>> + Send {
+        Box::pin(async move {
+            Ok(FileUserTransaction {
+                repository: self.clone(),
+                store: self.data.clone().write_owned().await,
+                changes: UserChangeSet::new(&self.tenant, self.audit.as_ref()),
+            })
+        })
+    }
+}
+
+/// Staged changes are written as a single log record on commit, so a crash
+/// never leaves part of a transaction behind.
+#[derive(Debug)]
+pub struct FileUserTransaction<E: FieldEncryptorTrait> {
+    repository: FileUserRepository<E>,
+    store: OwnedRwLockWriteGuard<FileUserStore<E>>,
+    changes: UserChangeSet,
+}
+
+impl<E: FieldEncryptorTrait> TransactionTrait for FileUserTransaction<E> {
+    type Id = Uuid;
+    type Entity = User;
+    type Error = UserError;
+
+    fn save(
+        &mut self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.save(&*self.store, entity) })
+    }
+
+    fn update(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.update(&*self.store, entity_id, entity) })
+    }
+
+    fn erase(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.erase(&*self.store, entity_id, entity) })
+    }
+
+    fn delete(
+        &mut self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move { self.changes.delete(&*self.store, entity_id) })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.changes
+                .find(&*self.store, entity_id)
+                .ok_or(UserError::UserNotExists { id: *entity_id })
+        })
+    }
+
+    fn commit(mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            let (staged, events) = self.changes.into_parts();
+            if staged.is_empty() {
+                return Ok(());
+            }
+            let records = staged
+                .iter()
+                .map(|(id, user)| match user {
+                    Some(user) => Ok(LogRecord::Put {
+                        user: Box::new(StoredUser::seal(user, &self.store.encryptor)?),
+                    }),
+                    None => Ok(LogRecord::Delete { id: *id }),
+                })
+                .collect::<Result<Vec<_>, UserError>>()?;
+            let stored_events = events
+                .iter()
+                .map(|event| StoredEvent::seal(event, &self.store.encryptor))
+                .collect::<Result<Vec<_>, UserError>>()?;
+            let record = LogRecord::Commit {
+                records,
+                events: stored_events,
+            };
+            self.repository.append(&mut self.store, &record).await?;
+            staged.into_iter().for_each(|(id, user)| match user {
+                Some(user) => self.store.put(user),
+                None => self.store.remove(&id),
+            });
+            let erasure = events.iter().any(|event| event.get_erased_user().is_some());
+            events
+                .into_iter()
+                .for_each(|event| FileUserRepository::<E>::record(&mut self.store, event));
+            // The log still holds the erased values until it is compacted.
+            if erasure {
+                return self.repository.compact_locked(&mut self.store).await;
+            }
+            self.repository.compact_if_needed(&mut self.store).await;
+            Ok(())
+        })
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl<E: FieldEncryptorTrait> OutboxTrait for FileUserRepository<E> {
+    type Event = UserEvent;
+
+    fn pending_events(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<Self::Event>, Self::Error>> + Send {
+        Box::pin(async move { Ok(self.data.read().await.outbox.pending(limit)) })
+    }
+
+    fn acknowledge_event(
+        &self,
+        event: &Self::Event,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            if !data.outbox.contains(event.get_id()) {
+                return Ok(());
+            }
+            let record = LogRecord::Acknowledge {
+                id: *event.get_id(),
+            };
+            self.append(&mut data, &record).await?;
+            data.outbox.acknowledge(event.get_id());
+            self.compact_if_needed(&mut data).await;
+            Ok(())
+        })
+    }
+}
+
+impl<E: FieldEncryptorTrait> UserChangeFeedTrait for FileUserRepository<E> {
+    fn subscribe_changes(
+        &self,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .data
+                .read()
+                .await
+                .changes
+                .subscribe(last_sequence)
+                .with_tenant(&self.tenant))
+        })
+    }
+}
+
+impl<E: FieldEncryptorTrait> UserRepositoryTrait for FileUserRepository<E> {
+    fn for_tenant(&self, tenant: &TenantId) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+
+    fn audited(&self, audit: &AuditContext) -> Self {
+        Self {
+            audit: Some(audit.clone()),
+            ..self.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::{Path, PathBuf};
+
+    use tokio::io::AsyncWriteExt;
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            audit::AuditContext,
+            auth::{AuthenticationMethod, Principal, RequestContext, TenantId},
+            user::{
+                dtos::{UserFindRequest, UserFindRequestFilter},
+                model::user::UserError,
+                EmailAddress, Name, User, UserOperation, UserRepositoryTrait,
+            },
+        },
+        outbound::{
+            encryption_adapter::local_key_file_encryptor::LocalKeyFileEncryptor,
+            repository_trait::{
+                FindResultTrait, OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait,
+            },
+        },
+    };
+
+    use super::{FileUserRepository, LOG_FILE, SNAPSHOT_FILE};
+
+    fn encryptor(active_key: &str) -> LocalKeyFileEncryptor {
+        LocalKeyFileEncryptor::new(&[9; 32], active_key, &[active_key.len() as u8; 32]).unwrap()
+    }
+
+    fn directory() -> PathBuf {
+        std::env::temp_dir().join(format!("users-{}", Uuid::new_v4()))
+    }
+
+    fn user(email: &str) -> User {
+        User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new(email).unwrap(),
+        )
+    }
+
+    fn audit() -> AuditContext {
+        let principal = Principal::new("admin", None, &[], AuthenticationMethod::ApiKey);
+        AuditContext::new(
+            &RequestContext::new(&principal, "req-1"),
+            UserOperation::Create,
+        )
+    }
+
+    async fn count(repository: &FileUserRepository) -> usize {
+        repository
+            .find_all(
+                &UserFindRequest::new(&UserFindRequestFilter::default(), "id", &1000, &1).unwrap(),
+            )
+            .await
+            .unwrap()
+            .get_result()
+            .count()
+    }
+
+    async fn truncate(path: &Path, removed: u64) {
+        let file = tokio::fs::OpenOptions::new()
+            .write(true)
+            .open(path)
+            .await
+            .unwrap();
+        let len = file.metadata().await.unwrap().len();
+        file.set_len(len - removed).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recover_after_restart() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        let john = repository.save(&user("john@example.com")).await.unwrap();
+        let jane = repository.save(&user("jane@example.com")).await.unwrap();
+        let mut verified = john.clone();
+        verified.set_verified(&true);
+        repository.update(john.get_id(), &verified).await.unwrap();
+        repository.delete(jane.get_id()).await.unwrap();
+        drop(repository);
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(reopened.find_by_id(john.get_id()).await.unwrap(), verified);
+        assert!(matches!(
+            reopened.find_by_id(jane.get_id()).await,
+            Err(UserError::UserNotExists { id: _ })
+        ));
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_tenant_survives_restart() {
+        let directory = directory();
+        let acme = TenantId::new("acme").unwrap();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        let john = repository
+            .for_tenant(&acme)
+            .save(&user("john@example.com"))
+            .await
+            .unwrap();
+        assert!(!serde_json::to_string(&john).unwrap().contains("acme"));
+        drop(repository);
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert!(reopened.find_by_id(john.get_id()).await.is_err());
+        let stored = reopened
+            .for_tenant(&acme)
+            .find_by_id(john.get_id())
+            .await
+            .unwrap();
+        assert_eq!(stored.get_tenant(), &acme);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_recover_from_torn_record() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        repository.save(&user("john@example.com")).await.unwrap();
+        repository.save(&user("jane@example.com")).await.unwrap();
+        drop(repository);
+        truncate(&directory.join(LOG_FILE), 10).await;
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(count(&reopened).await, 1);
+        reopened.save(&user("jack@example.com")).await.unwrap();
+        drop(reopened);
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(count(&reopened).await, 2);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_refuse_corrupted_log() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        repository.save(&user("john@example.com")).await.unwrap();
+        repository.save(&user("jane@example.com")).await.unwrap();
+        drop(repository);
+        let path = directory.join(LOG_FILE);
+        let content = tokio::fs::read_to_string(&path).await.unwrap();
+        tokio::fs::write(&path, content.replacen("{", "[", 1))
+            .await
+            .unwrap();
+
+        assert!(FileUserRepository::open(&directory).await.is_err());
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compaction() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory)
+            .await
+            .unwrap()
+            .with_compaction_threshold(2);
+        let john = repository.save(&user("john@example.com")).await.unwrap();
+        repository.save(&user("jane@example.com")).await.unwrap();
+        assert_eq!(
+            tokio::fs::metadata(directory.join(LOG_FILE))
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+        repository.delete(john.get_id()).await.unwrap();
+        repository.save(&user("jack@example.com")).await.unwrap();
+        repository.save(&user("jill@example.com")).await.unwrap();
+        drop(repository);
+        truncate(&directory.join(LOG_FILE), 1).await;
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert!(tokio::fs::try_exists(directory.join(SNAPSHOT_FILE))
+            .await
+            .unwrap());
+        assert_eq!(count(&reopened).await, 2);
+        assert!(reopened.find_by_id(john.get_id()).await.is_err());
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_torn_transaction_is_discarded() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        let john = repository.save(&user("john@example.com")).await.unwrap();
+        let mut transaction = repository.begin().await.unwrap();
+        transaction.save(&user("jane@example.com")).await.unwrap();
+        transaction.delete(john.get_id()).await.unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(count(&repository).await, 1);
+        drop(repository);
+        truncate(&directory.join(LOG_FILE), 5).await;
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(count(&reopened).await, 1);
+        assert!(reopened.find_by_id(john.get_id()).await.is_ok());
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_at_rest_and_key_rotation() {
+        let directory = directory();
+        let plain = FileUserRepository::open(&directory).await.unwrap();
+        let john = plain.save(&user("john@example.com")).await.unwrap();
+        drop(plain);
+
+        let repository = FileUserRepository::open_with_encryptor(&directory, encryptor("k1"))
+            .await
+            .unwrap();
+        assert_eq!(repository.rotate_keys().await.unwrap(), 1);
+        let jane = repository
+            .audited(&audit())
+            .save(&user("jane@example.com"))
+            .await
+            .unwrap();
+        assert!(matches!(
+            repository.save(&user("jane@example.com")).await,
+            Err(UserError::EmailAlreadyUsed { email: _ })
+        ));
+        for file in [LOG_FILE, SNAPSHOT_FILE] {
+            let content = tokio::fs::read_to_string(directory.join(file))
+                .await
+                .unwrap();
+            assert!(!content.contains("example.com") && !content.contains("Doe"));
+        }
+        drop(repository);
+
+        let rotated = encryptor("k22").with_key("k1", &[2; 32]).unwrap();
+        let repository = FileUserRepository::open_with_encryptor(&directory, rotated)
+            .await
+            .unwrap();
+        let filter = UserFindRequestFilter {
+            email: Some(String::from("jane@example.com")),
+            ..Default::default()
+        };
+        let found = repository
+            .find_all(&UserFindRequest::new(&filter, "", &10, &1).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(found.get_result().collect::<Vec<_>>(), [jane]);
+        assert_eq!(repository.rotate_keys().await.unwrap(), 2);
+        drop(repository);
+
+        let repository = FileUserRepository::open_with_encryptor(&directory, encryptor("k22"))
+            .await
+            .unwrap();
+        assert_eq!(repository.find_by_id(john.get_id()).await.unwrap(), john);
+        assert_eq!(repository.rotate_keys().await.unwrap(), 0);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_erase_compacts_log() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        let john = repository.save(&user("john@example.com")).await.unwrap();
+        let mut erased = john.clone();
+        erased.anonymize();
+        let mut transaction = repository.begin().await.unwrap();
+        transaction.erase(john.get_id(), &erased).await.unwrap();
+        transaction.commit().await.unwrap();
+
+        for file in [LOG_FILE, SNAPSHOT_FILE] {
+            let content = tokio::fs::read_to_string(directory.join(file))
+                .await
+                .unwrap();
+            assert!(!content.contains("john@example.com") && !content.contains("Doe"));
+        }
+        let pending = repository.pending_events(10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        assert!(pending
+            .iter()
+            .all(|event| !serde_json::to_string(event).unwrap().contains("Doe")));
+        drop(repository);
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(reopened.find_by_id(john.get_id()).await.unwrap(), erased);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_append_is_cut_off() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        repository.save(&user("john@example.com")).await.unwrap();
+        let path = directory.join(LOG_FILE);
+        let mut partial = tokio::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .await
+            .unwrap();
+        partial.write_all(b"{\"op\":\"Put\",\"us").await.unwrap();
+        repository.data.write().await.log = tokio::fs::File::open(&path).await.unwrap();
+
+        assert!(repository.save(&user("jane@example.com")).await.is_err());
+        repository.save(&user("jack@example.com")).await.unwrap();
+        drop(repository);
+
+        let reopened = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(count(&reopened).await, 2);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_outbox_survives_restart() {
+        let directory = directory();
+        let repository = FileUserRepository::open(&directory).await.unwrap();
+        repository.save(&user("john@example.com")).await.unwrap();
+        repository
+            .audited(&audit())
+            .save(&user("jane@example.com"))
+            .await
+            .unwrap();
+        let pending = repository.pending_events(10).await.unwrap();
+        assert!(pending[1].get_audit().is_some());
+        repository.acknowledge_event(&pending[0]).await.unwrap();
+        drop(repository);
+
+        let reopened = FileUserRepository::open(&directory)
+            .await
+            .unwrap()
+            .with_compaction_threshold(1);
+        assert_eq!(reopened.pending_events(10).await.unwrap(), pending[1..]);
+        reopened.save(&user("jack@example.com")).await.unwrap();
+        let pending = reopened.pending_events(10).await.unwrap();
+        assert_eq!(pending.len(), 2);
+        drop(reopened);
+
+        let compacted = FileUserRepository::open(&directory).await.unwrap();
+        assert_eq!(compacted.pending_events(10).await.unwrap(), pending);
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+}
@@ -0,0 +1,256 @@
+use std::{future::Future, path::Path, sync::Arc};
+
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    user::User,
+    webhook::{DeliveryStatus, WebhookDelivery, WebhookDeliveryStoreTrait, WebhookError},
+};
+
+use super::json_file::JsonFile;
+
+const DELIVERIES_FILE: &str = "webhook_deliveries.json";
+const EVENT_FIELD: &str = "event";
+const PAYLOAD_FIELD: &str = "payload";
+
+/// A delivery as written to disk: its response form plus the event and the
+/// payload, so that retries send the same body after a restart.
+#[derive(Debug, Clone)]
+struct StoredDelivery(WebhookDelivery);
+
+impl Serialize for StoredDelivery {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut fields = match serde_json::to_value(&self.0).map_err(S::Error::custom)? {
+            Value::Object(fields) => fields,
+            _ => return Err(S::Error::custom("delivery is not an object")),
+        };
+        fields.insert(
+            EVENT_FIELD.to_string(),
+            serde_json::to_value(self.0.get_event()).map_err(S::Error::custom)?,
+        );
+        fields.insert(
+            PAYLOAD_FIELD.to_string(),
+            Value::String(self.0.get_payload().to_string()),
+        );
+        fields.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StoredDelivery {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The model's value objects borrow their input, which a `Value` cannot lend.
+        let content = Value::deserialize(deserializer)?.to_string();
+        serde_json::from_str(&content)
+            .map(Self)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Webhook deliveries kept next to the users, in `webhook_deliveries.json`,
+/// so that pending retries and dead letters survive a restart.
+#[derive(Debug, Clone)]
+pub struct FileWebhookDeliveryStore {
+    data: Arc<JsonFile<Vec<StoredDelivery>>>,
+}
+
+impl FileWebhookDeliveryStore {
+    /// Opens the deliveries stored in `directory`.
+    pub async fn open(directory: &Path) -> Result<Self, WebhookError> {
+        Ok(Self {
+            data: Arc::new(JsonFile::open(&directory.join(DELIVERIES_FILE)).await?),
+        })
+    }
+
+    async fn find_where<F>(&self, predicate: F) -> Vec<WebhookDelivery>
+    where
+        F: Fn(&WebhookDelivery) -> bool,
+    {
+        self.data
+            .read()
+            .await
+            .iter()
+            .map(|stored| &stored.0)
+            .filter(|delivery| predicate(delivery))
+            .cloned()
+            .collect()
+    }
+}
+
+impl WebhookDeliveryStoreTrait for FileWebhookDeliveryStore {
+    fn save(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| {
+                    match data
+                        .iter_mut()
+                        .find(|stored| stored.0.get_id().eq(delivery.get_id()))
+                    {
+                        Some(stored) => stored.0 = delivery.clone(),
+                        None => data.push(StoredDelivery(delivery.clone())),
+                    }
+                    Ok(())
+                })
+                .await
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookDelivery, WebhookError>> + Send {
+        Box::pin(async move {
+            self.find_where(|delivery| delivery.get_id().eq(id))
+                .await
+                .pop()
+                .ok_or(WebhookError::DeliveryNotExists { id: *id })
+        })
+    }
+
+    fn find_by_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .find_where(|delivery| delivery.get_subscription_id().eq(subscription_id))
+                .await)
+        })
+    }
+
+    fn find_due(
+        &self,
+        now: &u64,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async move {
+            let mut due = self.find_where(|delivery| delivery.is_due(now)).await;
+            due.truncate(limit);
+            Ok(due)
+        })
+    }
+
+    fn find_dead_letters(
+        &self,
+        tenant: &TenantId,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .find_where(|delivery| {
+                    delivery.get_status() == DeliveryStatus::DeadLettered
+                        && delivery.get_tenant().eq(tenant)
+                })
+                .await)
+        })
+    }
+
+    fn delete_for_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| {
+                    data.retain(|stored| stored.0.get_subscription_id().ne(subscription_id));
+                    Ok(())
+                })
+                .await
+        })
+    }
+
+    fn redact_user(
+        &self,
+        erased: &User,
+    ) -> impl Future<Output = Result<usize, WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| {
+                    let deliveries = data
+                        .iter_mut()
+                        .filter(|stored| stored.0.get_user_id().eq(erased.get_id()));
+                    let mut redacted = 0;
+                    for stored in deliveries {
+                        stored.0.redact(erased);
+                        redacted += 1;
+                    }
+                    Ok(redacted)
+                })
+                .await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::TenantId,
+            user::{EmailAddress, Name, User, UserEvent},
+            webhook::{
+                DeliveryStatus, RetryPolicy, WebhookDelivery, WebhookDeliveryStoreTrait,
+                WebhookSubscription, WebhookSubscriptionStoreTrait, WebhookUrl,
+            },
+        },
+        outbound::file_repository_adapter::file_webhook_subscription_store::FileWebhookSubscriptionStore,
+    };
+
+    use super::FileWebhookDeliveryStore;
+
+    #[tokio::test]
+    async fn test_webhooks_survive_restart() {
+        let directory = std::env::temp_dir().join(format!("webhooks-{}", Uuid::new_v4()));
+        let subscriptions = FileWebhookSubscriptionStore::open(&directory)
+            .await
+            .unwrap();
+        let deliveries = FileWebhookDeliveryStore::open(&directory).await.unwrap();
+        let subscription = WebhookSubscription::new(
+            &Uuid::new_v4(),
+            &WebhookUrl::new("https://partner.example.com/hooks").unwrap(),
+            &[],
+            "secret",
+            &0,
+        );
+        subscriptions.save(&subscription).await.unwrap();
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let policy = RetryPolicy::new(2, Duration::from_secs(10), Duration::from_secs(10));
+        let mut pending = WebhookDelivery::new(&subscription, &UserEvent::created(&user), &0);
+        pending.record_failure(&0, Some(500), "Receiver answered with status 500", &policy);
+        deliveries.save(&pending).await.unwrap();
+        let mut dead = WebhookDelivery::new(&subscription, &UserEvent::deleted(&user), &0);
+        dead.record_failure(&0, None, "refused", &policy);
+        dead.record_failure(&0, None, "refused", &policy);
+        deliveries.save(&dead).await.unwrap();
+
+        let reopened = FileWebhookSubscriptionStore::open(&directory)
+            .await
+            .unwrap();
+        let stored = reopened.find_by_id(subscription.get_id()).await.unwrap();
+        assert_eq!(stored.get_secret(), "secret");
+        let reopened = FileWebhookDeliveryStore::open(&directory).await.unwrap();
+        assert_eq!(
+            reopened.find_due(&u64::MAX, 10).await.unwrap(),
+            vec![pending.clone()]
+        );
+        let dead_letters = reopened
+            .find_dead_letters(&TenantId::default())
+            .await
+            .unwrap();
+        assert_eq!(dead_letters[0].get_status(), DeliveryStatus::DeadLettered);
+        assert_eq!(dead_letters[0].get_payload(), dead.get_payload());
+        tokio::fs::remove_dir_all(&directory).await.unwrap();
+    }
+}
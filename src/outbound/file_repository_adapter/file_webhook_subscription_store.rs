@@ -0,0 +1,119 @@
+use std::{collections::HashMap, future::Future, path::Path, sync::Arc};
+
+use serde::{de::Error as _, ser::Error as _, Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    webhook::{WebhookError, WebhookSubscription, WebhookSubscriptionStoreTrait},
+};
+
+use super::json_file::JsonFile;
+
+const SUBSCRIPTIONS_FILE: &str = "webhook_subscriptions.json";
+const SECRET_FIELD: &str = "secret";
+
+/// A subscription as written to disk: its response form plus the secret.
+#[derive(Debug, Clone)]
+struct StoredSubscription(WebhookSubscription);
+
+impl Serialize for StoredSubscription {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut fields = match serde_json::to_value(&self.0).map_err(S::Error::custom)? {
+            Value::Object(fields) => fields,
+            _ => return Err(S::Error::custom("subscription is not an object")),
+        };
+        fields.insert(
+            SECRET_FIELD.to_string(),
+            Value::String(self.0.get_secret().to_string()),
+        );
+        fields.serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for StoredSubscription {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        // The model's value objects borrow their input, which a `Value` cannot lend.
+        let content = Value::deserialize(deserializer)?.to_string();
+        serde_json::from_str(&content)
+            .map(Self)
+            .map_err(D::Error::custom)
+    }
+}
+
+/// Webhook subscriptions kept next to the users, in `webhook_subscriptions.json`.
+#[derive(Debug, Clone)]
+pub struct FileWebhookSubscriptionStore {
+    data: Arc<JsonFile<HashMap<Uuid, StoredSubscription>>>,
+}
+
+impl FileWebhookSubscriptionStore {
+    /// Opens the subscriptions stored in `directory`.
+    pub async fn open(directory: &Path) -> Result<Self, WebhookError> {
+        Ok(Self {
+            data: Arc::new(JsonFile::open(&directory.join(SUBSCRIPTIONS_FILE)).await?),
+        })
+    }
+}
+
+impl WebhookSubscriptionStoreTrait for FileWebhookSubscriptionStore {
+    fn save(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| {
+                    data.insert(
+                        *subscription.get_id(),
+                        StoredSubscription(subscription.clone()),
+                    );
+                    Ok(())
+                })
+                .await
+        })
+    }
+
+    fn delete(&self, id: &Uuid) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .update(|data| match data.remove(id) {
+                    Some(_) => Ok(()),
+                    None => Err(WebhookError::SubscriptionNotExists { id: *id }),
+                })
+                .await
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send {
+        Box::pin(async move {
+            match self.data.read().await.get(id) {
+                Some(stored) => Ok(stored.0.clone()),
+                None => Err(WebhookError::SubscriptionNotExists { id: *id }),
+            }
+        })
+    }
+
+    fn find_all(
+        &self,
+        tenant: &TenantId,
+    ) -> impl Future<Output = Result<Vec<WebhookSubscription>, WebhookError>> + Send {
+        Box::pin(async move {
+            let mut subscriptions: Vec<WebhookSubscription> = self
+                .data
+                .read()
+                .await
+                .values()
+                .map(|stored| &stored.0)
+                .filter(|subscription| subscription.get_tenant().eq(tenant))
+                .cloned()
+                .collect();
+            subscriptions.sort_by_key(|subscription| subscription.get_created_at());
+            Ok(subscriptions)
+        })
+    }
+}
@@ -0,0 +1,63 @@
+use std::path::{Path, PathBuf};
+
+use serde::{de::DeserializeOwned, Serialize};
+use tokio::{
+    fs::File,
+    io::AsyncWriteExt,
+    sync::{RwLock, RwLockReadGuard},
+};
+
+/// Data kept in memory and rewritten whole to a JSON file on every change,
+/// for stores small enough that a log is not worth it.
+#[derive(Debug)]
+pub(crate) struct JsonFile<T> {
+    path: PathBuf,
+    data: RwLock<T>,
+}
+
+impl<T: Serialize + DeserializeOwned + Default + Clone> JsonFile<T> {
+    /// Reads `path`, or starts empty when it does not exist yet.
+    pub(crate) async fn open(path: &Path) -> anyhow::Result<Self> {
+        if let Some(directory) = path.parent() {
+            tokio::fs::create_dir_all(directory).await?;
+        }
+        let data = match tokio::fs::read(path).await {
+            Ok(content) => serde_json::from_slice(&content)?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => T::default(),
+            Err(e) => return Err(e.into()),
+        };
+        Ok(Self {
+            path: path.to_path_buf(),
+            data: RwLock::new(data),
+        })
+    }
+
+    pub(crate) async fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.data.read().await
+    }
+
+    /// Applies `change` to a copy of the data and keeps it once written, so
+    /// memory never holds a change the file lacks.
+    pub(crate) async fn update<R, E, F>(&self, change: F) -> Result<R, E>
+    where
+        F: FnOnce(&mut T) -> Result<R, E>,
+        E: From<anyhow::Error>,
+    {
+        let mut data = self.data.write().await;
+        let mut changed = data.clone();
+        let result = change(&mut changed)?;
+        self.write(&changed).await.map_err(E::from)?;
+        *data = changed;
+        Ok(result)
+    }
+
+    async fn write(&self, data: &T) -> anyhow::Result<()> {
+        let content = serde_json::to_vec(data)?;
+        let temporary = self.path.with_extension("json.tmp");
+        let mut file = File::create(&temporary).await?;
+        file.write_all(&content).await?;
+        file.sync_all().await?;
+        tokio::fs::rename(&temporary, &self.path).await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,5 @@
+pub mod file_credential_repository;
+pub mod file_user_repository;
+pub mod file_webhook_delivery_store;
+pub mod file_webhook_subscription_store;
+mod json_file;
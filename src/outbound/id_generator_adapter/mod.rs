@@ -0,0 +1,2 @@
+pub mod sequential_id_generator;
+pub mod uuid_v7_generator;
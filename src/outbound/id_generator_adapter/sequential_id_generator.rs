@@ -0,0 +1,55 @@
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc,
+};
+
+use uuid::Uuid;
+
+use crate::business::id::IdGeneratorTrait;
+
+/// Deterministic ids for tests: `00000000-0000-0000-0000-000000000001`, then
+/// `…0002` and so on. Clones share the sequence.
+#[derive(Debug, Clone, Default)]
+pub struct SequentialIdGenerator {
+    last: Arc<AtomicU64>,
+}
+
+impl SequentialIdGenerator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The next id generated is `start + 1`.
+    pub fn starting_after(start: u64) -> Self {
+        Self {
+            last: Arc::new(AtomicU64::new(start)),
+        }
+    }
+}
+
+impl IdGeneratorTrait for SequentialIdGenerator {
+    fn generate(&self) -> Uuid {
+        Uuid::from_u128(u128::from(self.last.fetch_add(1, Ordering::SeqCst) + 1))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::id::IdGeneratorTrait;
+
+    use super::SequentialIdGenerator;
+
+    #[test]
+    fn test_sequence() {
+        let generator = SequentialIdGenerator::new();
+        let clone = generator.clone();
+        assert_eq!(generator.generate(), Uuid::from_u128(1));
+        assert_eq!(clone.generate(), Uuid::from_u128(2));
+        assert_eq!(
+            SequentialIdGenerator::starting_after(41).generate(),
+            Uuid::from_u128(42)
+        );
+    }
+}
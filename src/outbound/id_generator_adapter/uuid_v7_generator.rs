@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use crate::business::id::IdGeneratorTrait;
+
+/// Time-ordered UUIDv7 ids, so that new entries land at the end of indexes.
+#[derive(Debug, Clone, Default)]
+pub struct UuidV7Generator;
+
+impl IdGeneratorTrait for UuidV7Generator {
+    fn generate(&self) -> Uuid {
+        Uuid::now_v7()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::business::id::IdGeneratorTrait;
+
+    use super::UuidV7Generator;
+
+    #[test]
+    fn test_time_ordered() {
+        let ids = (0..100)
+            .map(|_| UuidV7Generator.generate())
+            .collect::<Vec<_>>();
+        assert!(ids.iter().all(|id| id.get_version_num() == 7));
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+}
@@ -0,0 +1,81 @@
+use std::{future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::business::audit::{AuditEntry, AuditError, AuditRecord, AuditTrailTrait, GENESIS_HASH};
+
+#[derive(Debug, Clone)]
+pub struct InMemoryAuditTrail {
+    data: Arc<RwLock<Vec<AuditEntry>>>,
+}
+
+impl Default for InMemoryAuditTrail {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryAuditTrail {
+    pub fn new() -> Self {
+        InMemoryAuditTrail {
+            data: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+}
+
+impl AuditTrailTrait for InMemoryAuditTrail {
+    fn append(
+        &self,
+        record: &AuditRecord,
+    ) -> impl Future<Output = Result<AuditEntry, AuditError>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            if let Some(entry) = AuditEntry::find_event(&data, record) {
+                return Ok(entry.clone());
+            }
+            let previous_hash = data.last().map_or(GENESIS_HASH.to_string(), |entry| {
+                entry.get_hash().to_string()
+            });
+            let entry = AuditEntry::seal(record, &(data.len() as u64 + 1), &previous_hash);
+            data.push(entry.clone());
+            Ok(entry)
+        })
+    }
+
+    fn find_by_user(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<AuditEntry>, AuditError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .data
+                .read()
+                .await
+                .iter()
+                .filter(|entry| entry.get_user_id().eq(user_id))
+                .cloned()
+                .collect())
+        })
+    }
+
+    fn redact_user(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<usize, AuditError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .data
+                .write()
+                .await
+                .iter_mut()
+                .filter(|entry| entry.get_user_id().eq(user_id))
+                .filter_map(|entry| entry.redact().then_some(()))
+                .count())
+        })
+    }
+
+    fn verify_chain(&self) -> impl Future<Output = Result<u64, AuditError>> + Send {
+        Box::pin(async move { AuditEntry::verify_chain(self.data.read().await.iter()) })
+    }
+}
@@ -0,0 +1,163 @@
+use std::{collections::HashMap, future::Future, sync::Arc, time::SystemTime};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::{
+    business::credential::{
+        CredentialError, CredentialFindRequest, CredentialFindResponse, CredentialRepositoryTrait,
+        LockoutPolicy, PasswordCredential,
+    },
+    outbound::repository_trait::{FindOptionTrait, RepositoryTrait},
+};
+
+#[derive(Debug, Clone)]
+pub struct InMemoryCredentialRepository {
+    data: Arc<RwLock<HashMap<Uuid, PasswordCredential>>>,
+}
+
+impl Default for InMemoryCredentialRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryCredentialRepository {
+    pub fn new() -> Self {
+        InMemoryCredentialRepository {
+            data: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl RepositoryTrait for InMemoryCredentialRepository {
+    type Id = Uuid;
+    type Entity = PasswordCredential;
+    type Error = CredentialError;
+    type FindOptions = CredentialFindRequest;
+    type FindResult = CredentialFindResponse;
+
+    fn save(
+        &self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            if data.contains_key(entity.get_user_id()) {
+                Err(CredentialError::CredentialAlreadyExists {
+                    id: *entity.get_user_id(),
+                })
+            } else {
+                data.insert(*entity.get_user_id(), entity.clone());
+                Ok(entity.clone())
+            }
+        })
+    }
+
+    fn update(
+        &self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            match data.get_mut(entity_id) {
+                Some(credential) if entity.get_user_id().eq(entity_id) => {
+                    *credential = entity.clone();
+                    Ok(entity.clone())
+                }
+                _ => Err(CredentialError::CredentialNotExists { id: *entity_id }),
+            }
+        })
+    }
+
+    fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            match self.data.write().await.remove(entity_id) {
+                Some(_) => Ok(()),
+                None => Err(CredentialError::CredentialNotExists { id: *entity_id }),
+            }
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async {
+            match self.data.read().await.get(entity_id) {
+                Some(c) => Ok(c.clone()),
+                None => Err(CredentialError::CredentialNotExists { id: *entity_id }),
+            }
+        })
+    }
+
+    fn find_all(
+        &self,
+        options: &Self::FindOptions,
+    ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
+        Box::pin(async { Ok(find_page(self.data.read().await.values(), options)) })
+    }
+}
+
+/// Filters, orders and pages `credentials` as `options` asks.
+pub(crate) fn find_page<'a>(
+    credentials: impl Iterator<Item = &'a PasswordCredential>,
+    options: &CredentialFindRequest,
+) -> CredentialFindResponse {
+    let query = options.get_query();
+    let limit = options.get_limit();
+    let offset = options.get_offset();
+    let now = SystemTime::now();
+    let mut filtered: Vec<PasswordCredential> = credentials
+        .filter(|c| {
+            query.user_id.map_or(true, |id| c.get_user_id().eq(&id))
+                && query
+                    .locked
+                    .map_or(true, |locked| c.is_locked(&now) == locked)
+        })
+        .cloned()
+        .collect();
+    filtered.sort_by(|a, b| a.get_user_id().cmp(b.get_user_id()));
+    let mut limited = filtered.chunks(limit as usize);
+    let num_page = limited.len();
+    let selected = limited
+        .nth(offset.saturating_sub(1) as usize)
+        .map_or(Vec::new(), |chunk| chunk.to_vec());
+    CredentialFindResponse::new(selected, num_page as u64)
+}
+
+impl CredentialRepositoryTrait for InMemoryCredentialRepository {
+    fn record_failure(
+        &self,
+        user_id: &Uuid,
+        policy: &LockoutPolicy,
+        now: &SystemTime,
+    ) -> impl Future<Output = Result<PasswordCredential, CredentialError>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            let credential = data
+                .get_mut(user_id)
+                .ok_or(CredentialError::CredentialNotExists { id: *user_id })?;
+            credential.record_failure(policy, now);
+            Ok(credential.clone())
+        })
+    }
+
+    fn record_success(
+        &self,
+        user_id: &Uuid,
+        now: &SystemTime,
+    ) -> impl Future<Output = Result<PasswordCredential, CredentialError>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            let credential = data
+                .get_mut(user_id)
+                .ok_or(CredentialError::CredentialNotExists { id: *user_id })?;
+            if !credential.is_locked(now) {
+                credential.record_success();
+            }
+            Ok(credential.clone())
+        })
+    }
+}
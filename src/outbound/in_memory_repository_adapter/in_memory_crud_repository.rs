@@ -0,0 +1,133 @@
+use std::{collections::BTreeMap, future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::{
+    business::crud::{CrudEntityTrait, CrudErrorTrait, CrudFindRequestTrait, CrudPage},
+    outbound::repository_trait::{FindOptionTrait, RepositoryTrait},
+};
+
+#[derive(Debug, Clone)]
+pub struct InMemoryCrudRepository<T: CrudEntityTrait> {
+    data: Arc<RwLock<BTreeMap<Uuid, T>>>,
+}
+
+impl<T: CrudEntityTrait> Default for InMemoryCrudRepository<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: CrudEntityTrait> InMemoryCrudRepository<T> {
+    pub fn new() -> Self {
+        InMemoryCrudRepository {
+            data: Arc::new(RwLock::new(BTreeMap::new())),
+        }
+    }
+
+    fn check_conflicts(data: &BTreeMap<Uuid, T>, entity: &T) -> Result<(), T::Error> {
+        data.values()
+            .filter(|other| other.get_id().ne(entity.get_id()))
+            .find_map(|other| entity.conflict_with(other))
+            .map_or(Ok(()), Err)
+    }
+
+    /// Page of the entities matching both `options` and `scope`.
+    pub async fn find_page(
+        &self,
+        options: &T::FindRequest,
+        scope: impl Fn(&T) -> bool,
+    ) -> Result<CrudPage<T>, T::Error> {
+        let limit = options.get_limit().max(1) as usize;
+        let offset = options.get_offset();
+        let data = self.data.read().await;
+        let mut filtered: Vec<&T> = data
+            .values()
+            .filter(|entity| scope(entity) && options.matches(entity))
+            .collect();
+        filtered.sort_by(|a, b| {
+            options
+                .compare(a, b)
+                .then_with(|| a.get_id().cmp(b.get_id()))
+        });
+        let mut limited = filtered.chunks(limit);
+        let num_page = limited.len();
+        let selected = limited
+            .nth(offset.saturating_sub(1) as usize)
+            .map_or(Vec::new(), |chunk| {
+                chunk.iter().map(|entity| (*entity).clone()).collect()
+            });
+        Ok(CrudPage::new(selected, num_page as u64))
+    }
+}
+
+impl<T: CrudEntityTrait> RepositoryTrait for InMemoryCrudRepository<T> {
+    type Id = Uuid;
+    type Entity = T;
+    type Error = T::Error;
+    type FindOptions = T::FindRequest;
+    type FindResult = CrudPage<T>;
+
+    fn save(
+        &self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            let entity = entity.with_id(&Uuid::new_v4());
+            let mut data = self.data.write().await;
+            Self::check_conflicts(&data, &entity)?;
+            data.insert(*entity.get_id(), entity.clone());
+            Ok(entity)
+        })
+    }
+
+    fn update(
+        &self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            if entity_id.ne(entity.get_id()) {
+                return Err(T::Error::mismatch_id(entity_id, entity.get_id()));
+            }
+            let mut data = self.data.write().await;
+            if !data.contains_key(entity_id) {
+                return Err(T::Error::not_exists(entity_id));
+            }
+            Self::check_conflicts(&data, entity)?;
+            data.insert(*entity_id, entity.clone());
+            Ok(entity.clone())
+        })
+    }
+
+    fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            match self.data.write().await.remove(entity_id) {
+                Some(_) => Ok(()),
+                None => Err(T::Error::not_exists(entity_id)),
+            }
+        })
+    }
+
+    fn find_all(
+        &self,
+        options: &Self::FindOptions,
+    ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
+        self.find_page(options, |_| true)
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.data
+                .read()
+                .await
+                .get(entity_id)
+                .cloned()
+                .ok_or_else(|| T::Error::not_exists(entity_id))
+        })
+    }
+}
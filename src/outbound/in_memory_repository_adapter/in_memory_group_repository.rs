@@ -0,0 +1,283 @@
+use std::{collections::BTreeSet, future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::{
+    business::{
+        auth::TenantId,
+        crud::CrudPage,
+        group::{Group, GroupError, GroupFindRequest, GroupRepositoryTrait},
+    },
+    outbound::repository_trait::RepositoryTrait,
+};
+
+use super::in_memory_crud_repository::InMemoryCrudRepository;
+
+/// Memberships are `(group_id, user_id)` pairs; their lock is taken before
+/// touching a group so a deleted group never keeps members. Groups of other
+/// tenants than the handle's are reported as not existing.
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryGroupRepository {
+    groups: InMemoryCrudRepository<Group>,
+    members: Arc<RwLock<BTreeSet<(Uuid, Uuid)>>>,
+    tenant: TenantId,
+}
+
+impl InMemoryGroupRepository {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    async fn find_group(&self, group_id: &Uuid) -> Result<Group, GroupError> {
+        self.groups
+            .find_by_id(group_id)
+            .await
+            .ok()
+            .filter(|group| group.get_tenant().eq(&self.tenant))
+            .ok_or(GroupError::GroupNotExists { id: *group_id })
+    }
+}
+
+impl RepositoryTrait for InMemoryGroupRepository {
+    type Id = Uuid;
+    type Entity = Group;
+    type Error = GroupError;
+    type FindOptions = GroupFindRequest;
+    type FindResult = CrudPage<Group>;
+
+    fn save(&self, entity: &Group) -> impl Future<Output = Result<Group, GroupError>> + Send {
+        let entity = entity.clone().with_tenant(&self.tenant);
+        Box::pin(async move { self.groups.save(&entity).await })
+    }
+
+    fn update(
+        &self,
+        entity_id: &Uuid,
+        entity: &Group,
+    ) -> impl Future<Output = Result<Group, GroupError>> + Send {
+        Box::pin(async move {
+            let _members = self.members.write().await;
+            self.find_group(entity_id).await?;
+            self.groups
+                .update(entity_id, &entity.clone().with_tenant(&self.tenant))
+                .await
+        })
+    }
+
+    fn delete(&self, entity_id: &Uuid) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async move {
+            let mut members = self.members.write().await;
+            self.find_group(entity_id).await?;
+            self.groups.delete(entity_id).await?;
+            members.retain(|(group_id, _)| group_id.ne(entity_id));
+            Ok(())
+        })
+    }
+
+    fn find_all(
+        &self,
+        options: &GroupFindRequest,
+    ) -> impl Future<Output = Result<CrudPage<Group>, GroupError>> + Send {
+        self.groups
+            .find_page(options, |group| group.get_tenant().eq(&self.tenant))
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Uuid,
+    ) -> impl Future<Output = Result<Group, GroupError>> + Send {
+        self.find_group(entity_id)
+    }
+}
+
+impl GroupRepositoryTrait for InMemoryGroupRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+
+    fn add_member(
+        &self,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async move {
+            let mut members = self.members.write().await;
+            self.find_group(group_id).await?;
+            members.insert((*group_id, *user_id));
+            Ok(())
+        })
+    }
+
+    fn remove_member(
+        &self,
+        group_id: &Uuid,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async move {
+            let mut members = self.members.write().await;
+            self.find_group(group_id).await?;
+            members.remove(&(*group_id, *user_id));
+            Ok(())
+        })
+    }
+
+    fn find_members(
+        &self,
+        group_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<Uuid>, GroupError>> + Send {
+        Box::pin(async move {
+            let members = self.members.read().await;
+            self.find_group(group_id).await?;
+            Ok(members
+                .range((*group_id, Uuid::nil())..=(*group_id, Uuid::max()))
+                .map(|(_, user_id)| *user_id)
+                .collect())
+        })
+    }
+
+    fn find_groups_of(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<Group>, GroupError>> + Send {
+        Box::pin(async move {
+            let members = self.members.read().await;
+            let mut groups = Vec::new();
+            for (group_id, _) in members.iter().filter(|(_, member)| member.eq(user_id)) {
+                if let Ok(group) = self.find_group(group_id).await {
+                    groups.push(group);
+                }
+            }
+            Ok(groups)
+        })
+    }
+
+    fn remove_user(&self, user_id: &Uuid) -> impl Future<Output = Result<(), GroupError>> + Send {
+        Box::pin(async move {
+            let mut members = self.members.write().await;
+            let mut own = BTreeSet::new();
+            for (group_id, _) in members.iter().filter(|(_, member)| member.eq(user_id)) {
+                if self.find_group(group_id).await.is_ok() {
+                    own.insert(*group_id);
+                }
+            }
+            members.retain(|(group_id, member)| member.ne(user_id) || !own.contains(group_id));
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::TenantId,
+            group::{Group, GroupError, GroupFindRequest, GroupRepositoryTrait},
+            user::Name,
+        },
+        outbound::repository_trait::RepositoryTrait,
+    };
+
+    use super::InMemoryGroupRepository;
+
+    async fn group(repository: &InMemoryGroupRepository, name: &str) -> Group {
+        repository
+            .save(&Group::new(&Uuid::nil(), &Name::new(name).unwrap()))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_memberships() {
+        let repository = InMemoryGroupRepository::new();
+        let core = group(&repository, "Core").await;
+        let ops = group(&repository, "Ops").await;
+        let (anna, bob) = (Uuid::new_v4(), Uuid::new_v4());
+        repository.add_member(core.get_id(), &anna).await.unwrap();
+        repository.add_member(core.get_id(), &bob).await.unwrap();
+        repository.add_member(ops.get_id(), &anna).await.unwrap();
+
+        let mut members = repository.find_members(core.get_id()).await.unwrap();
+        members.sort();
+        let mut expected = vec![anna, bob];
+        expected.sort();
+        assert_eq!(members, expected);
+        assert_eq!(repository.find_groups_of(&anna).await.unwrap().len(), 2);
+
+        repository.remove_member(core.get_id(), &bob).await.unwrap();
+        assert_eq!(
+            repository.find_members(core.get_id()).await.unwrap(),
+            vec![anna]
+        );
+
+        repository.remove_user(&anna).await.unwrap();
+        assert!(repository.find_groups_of(&anna).await.unwrap().is_empty());
+
+        repository.add_member(ops.get_id(), &bob).await.unwrap();
+        repository.delete(ops.get_id()).await.unwrap();
+        assert!(repository.find_groups_of(&bob).await.unwrap().is_empty());
+        assert!(matches!(
+            repository.add_member(ops.get_id(), &bob).await,
+            Err(GroupError::GroupNotExists { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_unique_name() {
+        let repository = InMemoryGroupRepository::new();
+        group(&repository, "Core").await;
+        let duplicated = repository
+            .save(&Group::new(&Uuid::nil(), &Name::new("Core").unwrap()))
+            .await;
+        assert!(matches!(
+            duplicated,
+            Err(GroupError::NameAlreadyUsed { .. })
+        ));
+        let found = repository
+            .find_all(&GroupFindRequest {
+                name: Some(String::from("Co*")),
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        assert_eq!(found.get_items().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_tenant_isolation() {
+        let acme = InMemoryGroupRepository::new().for_tenant(&TenantId::new("acme").unwrap());
+        let globex = acme.for_tenant(&TenantId::new("globex").unwrap());
+        let core = group(&acme, "Core").await;
+        let anna = Uuid::new_v4();
+        acme.add_member(core.get_id(), &anna).await.unwrap();
+        group(&globex, "Core").await;
+
+        assert!(matches!(
+            globex.find_by_id(core.get_id()).await,
+            Err(GroupError::GroupNotExists { .. })
+        ));
+        assert!(matches!(
+            globex.add_member(core.get_id(), &Uuid::new_v4()).await,
+            Err(GroupError::GroupNotExists { .. })
+        ));
+        assert!(matches!(
+            globex.find_members(core.get_id()).await,
+            Err(GroupError::GroupNotExists { .. })
+        ));
+        assert!(globex.delete(core.get_id()).await.is_err());
+        let listed = globex.find_all(&GroupFindRequest::default()).await.unwrap();
+        assert!(listed
+            .get_items()
+            .iter()
+            .all(|group| group.get_id().ne(core.get_id())));
+        assert!(globex.find_groups_of(&anna).await.unwrap().is_empty());
+
+        globex.remove_user(&anna).await.unwrap();
+        assert_eq!(acme.find_groups_of(&anna).await.unwrap(), vec![core]);
+    }
+}
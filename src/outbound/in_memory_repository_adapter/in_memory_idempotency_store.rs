@@ -0,0 +1,125 @@
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use tokio::sync::Mutex;
+
+use crate::business::idempotency::{
+    IdempotencyError, IdempotencyRecord, IdempotencyStoreTrait, StoredResponse,
+};
+
+/// Number of records above which expired ones are removed.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct InMemoryIdempotencyStore {
+    records: Arc<Mutex<HashMap<String, (IdempotencyRecord, Instant)>>>,
+}
+
+impl Default for InMemoryIdempotencyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryIdempotencyStore {
+    pub fn new() -> Self {
+        InMemoryIdempotencyStore {
+            records: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl IdempotencyStoreTrait for InMemoryIdempotencyStore {
+    fn begin(
+        &self,
+        key: &str,
+        fingerprint: &str,
+        lease: &Duration,
+    ) -> impl Future<Output = Result<Option<IdempotencyRecord>, IdempotencyError>> + Send {
+        Box::pin(async move {
+            let now = Instant::now();
+            let mut records = self.records.lock().await;
+            if records.len() >= SWEEP_THRESHOLD {
+                records.retain(|_key, (_record, expires_at)| *expires_at > now);
+            }
+            match records.get(key) {
+                Some((record, expires_at)) if *expires_at > now => Ok(Some(record.clone())),
+                _ => {
+                    records.insert(
+                        key.to_string(),
+                        (IdempotencyRecord::new(fingerprint), now + *lease),
+                    );
+                    Ok(None)
+                }
+            }
+        })
+    }
+
+    fn complete(
+        &self,
+        key: &str,
+        response: &StoredResponse,
+        ttl: &Duration,
+    ) -> impl Future<Output = Result<(), IdempotencyError>> + Send {
+        Box::pin(async move {
+            if let Some((record, expires_at)) = self.records.lock().await.get_mut(key) {
+                *record = record.clone().with_response(response);
+                *expires_at = Instant::now() + *ttl;
+            }
+            Ok(())
+        })
+    }
+
+    fn release(&self, key: &str) -> impl Future<Output = Result<(), IdempotencyError>> + Send {
+        Box::pin(async move {
+            self.records.lock().await.remove(key);
+            Ok(())
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::business::idempotency::{IdempotencyStoreTrait, StoredResponse};
+
+    use super::InMemoryIdempotencyStore;
+
+    #[tokio::test]
+    async fn test_begin_complete_expire() {
+        let store = InMemoryIdempotencyStore::new();
+        let ttl = Duration::from_secs(60);
+        assert_eq!(store.begin("k", "f", &ttl).await.unwrap(), None);
+        let pending = store.begin("k", "g", &ttl).await.unwrap().unwrap();
+        assert_eq!(pending.get_fingerprint(), "f");
+        assert_eq!(pending.get_response(), None);
+
+        let response = StoredResponse::new(201, &[], b"{}");
+        store.complete("k", &response, &ttl).await.unwrap();
+        let completed = store.begin("k", "f", &ttl).await.unwrap().unwrap();
+        assert_eq!(completed.get_response(), Some(&response));
+
+        store.release("k").await.unwrap();
+        assert_eq!(store.begin("k", "f", &Duration::ZERO).await.unwrap(), None);
+        assert_eq!(store.begin("k", "f", &ttl).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_abandoned_lease_expires() {
+        let store = InMemoryIdempotencyStore::new();
+        assert_eq!(store.begin("k", "f", &Duration::ZERO).await.unwrap(), None);
+        assert_eq!(store.begin("k", "f", &Duration::ZERO).await.unwrap(), None);
+        let response = StoredResponse::new(201, &[], b"{}");
+        store
+            .complete("k", &response, &Duration::from_secs(60))
+            .await
+            .unwrap();
+        let completed = store.begin("k", "f", &Duration::ZERO).await.unwrap();
+        assert_eq!(completed.unwrap().get_response(), Some(&response));
+    }
+}
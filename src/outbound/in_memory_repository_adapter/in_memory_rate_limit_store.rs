@@ -0,0 +1,68 @@
+use std::{collections::HashMap, future::Future, sync::Arc, time::Instant};
+
+use tokio::sync::Mutex;
+
+use crate::business::rate_limit::{
+    RateLimit, RateLimitDecision, RateLimitError, RateLimitStoreTrait, TokenBucket,
+};
+
+/// Number of buckets above which full ones are forgotten.
+const SWEEP_THRESHOLD: usize = 10_000;
+
+#[derive(Debug, Clone)]
+pub struct InMemoryRateLimitStore {
+    buckets: Arc<Mutex<HashMap<String, (TokenBucket, RateLimit)>>>,
+}
+
+impl Default for InMemoryRateLimitStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryRateLimitStore {
+    pub fn new() -> Self {
+        InMemoryRateLimitStore {
+            buckets: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl RateLimitStoreTrait for InMemoryRateLimitStore {
+    fn acquire(
+        &self,
+        key: &str,
+        limit: &RateLimit,
+    ) -> impl Future<Output = Result<RateLimitDecision, RateLimitError>> + Send {
+        Box::pin(async move {
+            let now = Instant::now();
+            let mut buckets = self.buckets.lock().await;
+            if buckets.len() >= SWEEP_THRESHOLD {
+                buckets.retain(|_key, (bucket, limit)| !bucket.is_full(limit, now));
+            }
+            let (bucket, bucket_limit) = buckets
+                .entry(key.to_string())
+                .or_insert_with(|| (TokenBucket::new(limit, now), *limit));
+            *bucket_limit = *limit;
+            Ok(bucket.acquire(limit, now))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::business::rate_limit::{RateLimit, RateLimitStoreTrait};
+
+    use super::InMemoryRateLimitStore;
+
+    #[tokio::test]
+    async fn test_buckets_per_key() {
+        let store = InMemoryRateLimitStore::new();
+        let limit = RateLimit::new(1, Duration::from_secs(60)).unwrap();
+        assert!(store.acquire("a", &limit).await.unwrap().is_allowed());
+        assert!(!store.acquire("a", &limit).await.unwrap().is_allowed());
+        assert!(store.acquire("b", &limit).await.unwrap().is_allowed());
+    }
+}
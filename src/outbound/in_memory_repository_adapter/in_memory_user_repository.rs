@@ -1,20 +1,318 @@
-use std::{collections::HashMap, future::Future, sync::Arc};
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, BTreeSet},
+    future::Future,
+    ops::Bound,
+    sync::Arc,
+};
 
-use tokio::sync::RwLock;
+use tokio::sync::{OwnedRwLockWriteGuard, RwLock};
 use uuid::Uuid;
 
 use crate::{
-    business::user::{
-        dtos::{UserFindRequest, UserFindResponse},
-        model::user::UserError,
-        User, UserRepositoryTrait,
+    business::{
+        audit::AuditContext,
+        auth::TenantId,
+        user::{
+            dtos::{UserFindRequest, UserFindRequestFilter, UserFindResponse},
+            model::user::UserError,
+            CustomAttributes, EmailAddress, User, UserChangeFeedTrait, UserChangeSubscription,
+            UserEvent, UserRepositoryTrait,
+        },
+    },
+    outbound::repository_trait::{
+        FindOptionTrait, OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait,
     },
-    outbound::repository_trait::{FindOptionTrait, RepositoryTrait},
 };
 
+use super::{
+    user_change_log::UserChangeLog,
+    user_change_set::{UserChangeSet, UserLookupTrait},
+    user_outbox::UserOutbox,
+};
+
+type FieldIndex = BTreeSet<(String, Uuid)>;
+
+/// Fields kept in a secondary index, usable both to select and to order users.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum IndexedField {
+    Email,
+    Firstname,
+    Lastname,
+}
+
+impl IndexedField {
+    fn from_order_by(order_by: &str) -> Option<Self> {
+        match order_by.to_lowercase().as_str() {
+            "email" => Some(Self::Email),
+            "firstname" => Some(Self::Firstname),
+            "lastname" => Some(Self::Lastname),
+            _ => None,
+        }
+    }
+
+    fn value(self, user: &User) -> &str {
+        match self {
+            Self::Email => user.get_email(),
+            Self::Firstname => user.get_firstname(),
+            Self::Lastname => user.get_lastname(),
+        }
+    }
+}
+
+/// Users of one tenant with their secondary indexes.
+#[derive(Debug, Default)]
+struct TenantUsers {
+    users: BTreeMap<Uuid, User>,
+    by_email: FieldIndex,
+    by_firstname: FieldIndex,
+    by_lastname: FieldIndex,
+}
+
+static NO_USERS: TenantUsers = TenantUsers {
+    users: BTreeMap::new(),
+    by_email: BTreeSet::new(),
+    by_firstname: BTreeSet::new(),
+    by_lastname: BTreeSet::new(),
+};
+
+/// Users partitioned by tenant, so every lookup and index stays within one.
+#[derive(Debug, Default)]
+struct UserStore {
+    tenants: BTreeMap<TenantId, TenantUsers>,
+    outbox: UserOutbox,
+    changes: UserChangeLog,
+}
+
+impl UserStore {
+    fn record(&mut self, event: UserEvent) {
+        if let Some(erased) = event.get_erased_user() {
+            self.changes.redact(erased);
+        }
+        self.changes.record(&event);
+        self.outbox.push(event);
+    }
+
+    fn tenant(&self, tenant: &TenantId) -> &TenantUsers {
+        self.tenants.get(tenant).unwrap_or(&NO_USERS)
+    }
+
+    fn tenant_mut(&mut self, tenant: &TenantId) -> &mut TenantUsers {
+        self.tenants.entry(tenant.clone()).or_default()
+    }
+}
+
+/// Ids are unique across tenants, so new ones are checked against all of them.
+impl UserLookupTrait for UserStore {
+    fn find_user(&self, user_id: &Uuid) -> Option<User> {
+        self.tenants
+            .values()
+            .find_map(|tenant| tenant.find_user(user_id))
+    }
+
+    fn find_ids_by_email(&self, email: &EmailAddress) -> Vec<Uuid> {
+        self.tenants
+            .values()
+            .flat_map(|tenant| tenant.find_ids_by_email(email))
+            .collect()
+    }
+}
+
+impl UserLookupTrait for TenantUsers {
+    fn find_user(&self, user_id: &Uuid) -> Option<User> {
+        self.users.get(user_id).cloned()
+    }
+
+    fn find_ids_by_email(&self, email: &EmailAddress) -> Vec<Uuid> {
+        self.lookup(IndexedField::Email, email).copied().collect()
+    }
+}
+
+impl TenantUsers {
+    fn index(&self, field: IndexedField) -> &FieldIndex {
+        match field {
+            IndexedField::Email => &self.by_email,
+            IndexedField::Firstname => &self.by_firstname,
+            IndexedField::Lastname => &self.by_lastname,
+        }
+    }
+
+    fn index_mut(&mut self, field: IndexedField) -> &mut FieldIndex {
+        match field {
+            IndexedField::Email => &mut self.by_email,
+            IndexedField::Firstname => &mut self.by_firstname,
+            IndexedField::Lastname => &mut self.by_lastname,
+        }
+    }
+
+    fn insert(&mut self, user: &User) {
+        self.remove(user.get_id());
+        for field in [
+            IndexedField::Email,
+            IndexedField::Firstname,
+            IndexedField::Lastname,
+        ] {
+            self.index_mut(field)
+                .insert((field.value(user).to_string(), *user.get_id()));
+        }
+        self.users.insert(*user.get_id(), user.clone());
+    }
+
+    fn remove(&mut self, user_id: &Uuid) -> Option<User> {
+        let user = self.users.remove(user_id)?;
+        for field in [
+            IndexedField::Email,
+            IndexedField::Firstname,
+            IndexedField::Lastname,
+        ] {
+            self.index_mut(field)
+                .remove(&(field.value(&user).to_string(), *user_id));
+        }
+        Some(user)
+    }
+
+    /// Ids of the indexed values matching `pattern`, in index order. A pattern
+    /// ending with `*` is a prefix.
+    fn lookup<'a>(
+        &'a self,
+        field: IndexedField,
+        pattern: &'a str,
+    ) -> impl Iterator<Item = &'a Uuid> + 'a {
+        let (start, is_prefix) = match pattern.strip_suffix('*') {
+            Some(prefix) => (prefix, true),
+            None => (pattern, false),
+        };
+        self.index(field)
+            .range((
+                Bound::Included((start.to_string(), Uuid::nil())),
+                Bound::Unbounded,
+            ))
+            .take_while(move |(value, _)| match is_prefix {
+                true => value.starts_with(start),
+                false => value.eq(start),
+            })
+            .map(|(_, id)| id)
+    }
+
+    fn users_in(&self, field: Option<IndexedField>) -> Box<dyn Iterator<Item = &User> + '_> {
+        match field {
+            Some(field) => Box::new(
+                self.index(field)
+                    .iter()
+                    .filter_map(|(_, id)| self.users.get(id)),
+            ),
+            None => Box::new(self.users.values()),
+        }
+    }
+
+    /// Narrows the candidates with the most selective indexed filter and
+    /// sorts them, or walks the index of `order_by` when nothing narrows them
+    /// or when both are the same field.
+    fn find_page(&self, options: &UserFindRequest) -> UserFindResponse {
+        let query = options.get_query();
+        let order_by = IndexedField::from_order_by(&options.get_order_by());
+        let by_attribute = CustomAttributes::order_key(&options.get_order_by()).is_some();
+        let selection = [
+            (IndexedField::Email, &query.email),
+            (IndexedField::Lastname, &query.lastname),
+            (IndexedField::Firstname, &query.firstname),
+        ]
+        .into_iter()
+        .find_map(|(field, pattern)| pattern.as_deref().map(|pattern| (field, pattern)));
+        match (query.id, selection) {
+            (Some(id), _) => find_page(self.users.get(&id).into_iter(), options),
+            (None, Some((field, pattern))) if Some(field) == order_by => paginate(
+                self.lookup(field, pattern)
+                    .filter_map(|id| self.users.get(id)),
+                options,
+            ),
+            (None, Some((field, pattern))) => find_page(
+                self.lookup(field, pattern)
+                    .filter_map(|id| self.users.get(id)),
+                options,
+            ),
+            (None, None) if query.members.is_some() => find_page(
+                query
+                    .members
+                    .iter()
+                    .flatten()
+                    .filter_map(|id| self.users.get(id)),
+                options,
+            ),
+            (None, None) if by_attribute => find_page(self.users.values(), options),
+            (None, None) if query.eq(&UserFindRequestFilter::default()) => {
+                let limit = options.get_limit() as usize;
+                let skip = options.get_offset().saturating_sub(1) as usize * limit;
+                UserFindResponse::new(
+                    self.users_in(order_by)
+                        .skip(skip)
+                        .take(limit)
+                        .cloned()
+                        .collect(),
+                    self.users.len().div_ceil(limit) as u64,
+                )
+            }
+            (None, None) => paginate(self.users_in(order_by), options),
+        }
+    }
+}
+
+/// Pages through users already in the requested order.
+fn paginate<'a>(
+    ordered: impl Iterator<Item = &'a User>,
+    options: &UserFindRequest,
+) -> UserFindResponse {
+    let query = options.get_query();
+    let limit = options.get_limit() as usize;
+    let skip = options.get_offset().saturating_sub(1) as usize * limit;
+    let mut count = 0;
+    let mut selected = Vec::new();
+    for user in ordered.filter(|user| query.matches(user)) {
+        if count >= skip && selected.len() < limit {
+            selected.push(user.clone());
+        }
+        count += 1;
+    }
+    UserFindResponse::new(selected, count.div_ceil(limit) as u64)
+}
+
+pub(crate) fn find_page<'a>(
+    users: impl Iterator<Item = &'a User>,
+    options: &UserFindRequest,
+) -> UserFindResponse {
+    let query = options.get_query();
+    let limit = options.get_limit();
+    let order_by = options.get_order_by();
+    let offset = options.get_offset();
+
+    let mut filtered: Vec<User> = users.filter(|user| query.matches(user)).cloned().collect();
+    let attribute = CustomAttributes::order_key(&order_by);
+    filtered.sort_by(|a, b| {
+        match (order_by.to_lowercase().as_str(), attribute) {
+            ("email", _) => a.get_email().cmp(b.get_email()),
+            ("firstname", _) => a.get_firstname().cmp(b.get_firstname()),
+            ("lastname", _) => a.get_lastname().cmp(b.get_lastname()),
+            (_, Some(name)) => a
+                .get_attributes()
+                .get(name)
+                .cmp(&b.get_attributes().get(name)),
+            _ => Ordering::Equal,
+        }
+        .then_with(|| a.get_id().cmp(b.get_id()))
+    });
+    let mut limited = filtered.chunks(limit as usize);
+    let num_page = limited.len();
+    let selected = limited
+        .nth(offset.saturating_sub(1) as usize)
+        .map_or(Vec::new(), |chunk| chunk.to_vec());
+    UserFindResponse::new(selected, num_page as u64)
+}
+
 #[derive(Debug, Clone)]
 pub struct InMemoryUserRepository {
-    data: Arc<RwLock<HashMap<Uuid, User>>>,
+    data: Arc<RwLock<UserStore>>,
+    tenant: TenantId,
+    audit: Option<AuditContext>,
 }
 
 impl Default for InMemoryUserRepository {
@@ -26,7 +324,20 @@ impl Default for InMemoryUserRepository {
 impl InMemoryUserRepository {
     pub fn new() -> Self {
         InMemoryUserRepository {
-            data: Arc::new(RwLock::new(HashMap::new())),
+            data: Arc::new(RwLock::new(UserStore::default())),
+            tenant: TenantId::default(),
+            audit: None,
+        }
+    }
+
+    pub fn with_change_log_capacity(capacity: usize) -> Self {
+        InMemoryUserRepository {
+            data: Arc::new(RwLock::new(UserStore {
+                changes: UserChangeLog::new(capacity),
+                ..Default::default()
+            })),
+            tenant: TenantId::default(),
+            audit: None,
         }
     }
 }
@@ -43,15 +354,9 @@ impl RepositoryTrait for InMemoryUserRepository {
         entity: &Self::Entity,
     ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
         Box::pin(async move {
-            let user_id = Uuid::new_v4();
-            let user = User::new(
-                &user_id,
-                entity.get_firstname(),
-                entity.get_lastname(),
-                entity.get_email(),
-            );
-            let mut data = self.data.write().await;
-            data.insert(user_id, user.clone());
+            let mut transaction = self.begin().await?;
+            let user = transaction.save(entity).await?;
+            transaction.commit().await?;
             Ok(user)
         })
     }
@@ -62,43 +367,18 @@ impl RepositoryTrait for InMemoryUserRepository {
         entity: &Self::Entity,
     ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
         Box::pin(async move {
-            let mut data = self.data.write().await;
-            if entity_id.ne(entity.get_id()) {
-                Err(UserError::MismatchUserId {
-                    id1: *entity_id,
-                    id2: *entity.get_id(),
-                })
-            } else if data.contains_key(entity_id) {
-                if data
-                    .iter()
-                    .filter(|(_k, v)| {
-                        v.get_id().ne(entity.get_id()) && v.get_email().eq(entity.get_email())
-                    })
-                    .count()
-                    > 0
-                {
-                    Err(UserError::EmailAlreadyUsedByOther {
-                        email: entity.get_email().clone(),
-                    })
-                } else {
-                    data.insert(*entity_id, entity.clone());
-                    Ok(entity.clone())
-                }
-            } else {
-                Err(UserError::UserNotExists { id: *entity_id })
-            }
+            let mut transaction = self.begin().await?;
+            let user = transaction.update(entity_id, entity).await?;
+            transaction.commit().await?;
+            Ok(user)
         })
     }
 
     fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
         Box::pin(async move {
-            let mut data = self.data.write().await;
-            if data.contains_key(entity_id) {
-                data.remove(entity_id);
-                Ok(())
-            } else {
-                Err(UserError::UserNotExists { id: *entity_id })
-            }
+            let mut transaction = self.begin().await?;
+            transaction.delete(entity_id).await?;
+            transaction.commit().await
         })
     }
 
@@ -107,52 +387,8 @@ impl RepositoryTrait for InMemoryUserRepository {
         options: &Self::FindOptions,
     ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
         Box::pin(async {
-            let query = options.get_query();
-            let limit = options.get_limit();
-            let order_by = options.get_order_by();
-            let offset = options.get_offset();
             let data = self.data.read().await;
-
-            let mut filtered: Vec<User> = data
-                .iter()
-                .filter(|(k, v)| {
-                    let mut found = true;
-                    if query.id.is_some() {
-                        found &= query.id.unwrap().eq(k);
-                    }
-                    if query.email.is_some() {
-                        found &= query.email.as_ref().unwrap().eq(&v.get_email().to_string());
-                    }
-                    if query.firstname.is_some() {
-                        found &= query
-                            .firstname
-                            .as_ref()
-                            .unwrap()
-                            .eq(&v.get_firstname().to_string());
-                    }
-                    if query.lastname.is_some() {
-                        found &= query
-                            .lastname
-                            .as_ref()
-                            .unwrap()
-                            .eq(&v.get_lastname().to_string());
-                    }
-                    found
-                })
-                .map(|u| u.1.clone())
-                .collect();
-            filtered.sort_by(|a, b| match order_by.to_lowercase().as_str() {
-                "email" => a.get_email().cmp(b.get_email()),
-                "firstname" => a.get_firstname().cmp(b.get_firstname()),
-                "lastname" => a.get_lastname().cmp(b.get_lastname()),
-                _ => a.get_id().cmp(b.get_id()),
-            });
-            let mut limited = filtered.chunks(limit as usize);
-            let num_page = limited.len();
-            let selected = limited
-                .nth(offset as usize)
-                .map_or(Vec::new(), |chunk| chunk.to_vec());
-            Ok(UserFindResponse::new(selected, num_page as u64))
+            Ok(data.tenant(&self.tenant).find_page(options))
         })
     }
 
@@ -161,7 +397,14 @@ impl RepositoryTrait for InMemoryUserRepository {
         entity_id: &Self::Id,
     ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
         Box::pin(async {
-            match self.data.read().await.get(entity_id) {
+            match self
+                .data
+                .read()
+                .await
+                .tenant(&self.tenant)
+                .users
+                .get(entity_id)
+            {
                 Some(u) => Ok(u.clone()),
                 None => Err(UserError::UserNotExists { id: *entity_id }),
             }
@@ -169,4 +412,568 @@ impl RepositoryTrait for InMemoryUserRepository {
     }
 }
 
-impl UserRepositoryTrait for InMemoryUserRepository {}
+impl UnitOfWorkTrait for InMemoryUserRepository {
+    type Transaction = InMemoryUserTransaction;
+
+    fn begin(&self) -> impl Future<Output = Result<Self::Transaction, Self::Error>> + Send {
+        Box::pin(async move {
+            Ok(InMemoryUserTransaction {
+                store: self.data.clone().write_owned().await,
+                tenant: self.tenant.clone(),
+                changes: UserChangeSet::new(&self.tenant, self.audit.as_ref()),
+            })
+        })
+    }
+}
+
+/// Holds the store write lock until committed or rolled back, so staged
+/// changes are checked and applied without interleaving writes.
+#[derive(Debug)]
+pub struct InMemoryUserTransaction {
+    store: OwnedRwLockWriteGuard<UserStore>,
+    tenant: TenantId,
+    changes: UserChangeSet,
+}
+
+impl TransactionTrait for InMemoryUserTransaction {
+    type Id = Uuid;
+    type Entity = User;
+    type Error = UserError;
+
+    fn save(
+        &mut self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move { self.changes.save(&*self.store, entity) })
+    }
+
+    fn update(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.changes
+                .update(self.store.tenant(&self.tenant), entity_id, entity)
+        })
+    }
+
+    fn erase(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.changes
+                .erase(self.store.tenant(&self.tenant), entity_id, entity)
+        })
+    }
+
+    fn delete(
+        &mut self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            self.changes
+                .delete(self.store.tenant(&self.tenant), entity_id)
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        Box::pin(async move {
+            self.changes
+                .find(self.store.tenant(&self.tenant), entity_id)
+                .ok_or(UserError::UserNotExists { id: *entity_id })
+        })
+    }
+
+    fn commit(mut self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            let (staged, events) = self.changes.into_parts();
+            for (user_id, user) in staged {
+                match user {
+                    Some(user) => self.store.tenant_mut(&self.tenant).insert(&user),
+                    None => {
+                        self.store.tenant_mut(&self.tenant).remove(&user_id);
+                    }
+                }
+            }
+            events
+                .into_iter()
+                .for_each(|event| self.store.record(event));
+            Ok(())
+        })
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async { Ok(()) })
+    }
+}
+
+impl OutboxTrait for InMemoryUserRepository {
+    type Event = UserEvent;
+
+    fn pending_events(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<Self::Event>, Self::Error>> + Send {
+        Box::pin(async move { Ok(self.data.read().await.outbox.pending(limit)) })
+    }
+
+    fn acknowledge_event(
+        &self,
+        event: &Self::Event,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        Box::pin(async move {
+            self.data.write().await.outbox.acknowledge(event.get_id());
+            Ok(())
+        })
+    }
+}
+
+impl UserChangeFeedTrait for InMemoryUserRepository {
+    fn subscribe_changes(
+        &self,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .data
+                .read()
+                .await
+                .changes
+                .subscribe(last_sequence)
+                .with_tenant(&self.tenant))
+        })
+    }
+}
+
+impl UserRepositoryTrait for InMemoryUserRepository {
+    fn for_tenant(&self, tenant: &TenantId) -> Self {
+        Self {
+            data: self.data.clone(),
+            tenant: tenant.clone(),
+            audit: self.audit.clone(),
+        }
+    }
+
+    fn audited(&self, audit: &AuditContext) -> Self {
+        Self {
+            data: self.data.clone(),
+            tenant: self.tenant.clone(),
+            audit: Some(audit.clone()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::TenantId,
+            user::{
+                dtos::{AttributeFilter, UserFindRequest, UserFindRequestFilter},
+                model::user::UserError,
+                AttributeValue, CustomAttributes, EmailAddress, Name, User, UserChangeFeedTrait,
+                UserEventKind, UserRepositoryTrait,
+            },
+        },
+        outbound::repository_trait::{
+            FindResultTrait, OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait,
+        },
+    };
+
+    use super::{find_page, InMemoryUserRepository};
+
+    #[tokio::test]
+    async fn test_outbox_records_lifecycle() {
+        let repository = InMemoryUserRepository::new();
+        let user = repository
+            .save(&User::new(
+                &Uuid::new_v4(),
+                &Name::new("John").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new("john@example.com").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let renamed = User::new(
+            user.get_id(),
+            &Name::new("Johnny").unwrap(),
+            user.get_lastname(),
+            user.get_email(),
+        );
+        repository.update(user.get_id(), &renamed).await.unwrap();
+        repository.delete(user.get_id()).await.unwrap();
+
+        let events = repository.pending_events(10).await.unwrap();
+        assert_eq!(events.len(), 3);
+        assert!(
+            matches!(events[0].get_kind(), UserEventKind::UserCreated { after } if **after == user)
+        );
+        assert!(matches!(
+            events[1].get_kind(),
+            UserEventKind::UserUpdated { before, after } if **before == user && **after == renamed
+        ));
+        assert!(
+            matches!(events[2].get_kind(), UserEventKind::UserDeleted { before } if **before == renamed)
+        );
+
+        repository.acknowledge_event(&events[0]).await.unwrap();
+        let remaining = repository.pending_events(10).await.unwrap();
+        assert_eq!(remaining, events[1..].to_vec());
+    }
+
+    #[tokio::test]
+    async fn test_failed_update_records_nothing() {
+        let repository = InMemoryUserRepository::new();
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        assert!(repository.update(user.get_id(), &user).await.is_err());
+        assert!(repository.delete(user.get_id()).await.is_err());
+        assert!(repository.pending_events(10).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_indexes_agree_with_scan() {
+        let repository = InMemoryUserRepository::new();
+        for index in 0..60 {
+            let mut user = User::new(
+                &Uuid::new_v4(),
+                &Name::new(&format!("First{}", index % 7)).unwrap(),
+                &Name::new(&format!("Last{}", index % 5)).unwrap(),
+                &EmailAddress::new(&format!("user{index}@example.com")).unwrap(),
+            );
+            user.set_verified(&(index % 2 == 0));
+            repository.save(&user).await.unwrap();
+        }
+        let filters = [
+            UserFindRequestFilter::default(),
+            UserFindRequestFilter {
+                email: Some(String::from("user1*")),
+                ..Default::default()
+            },
+            UserFindRequestFilter {
+                lastname: Some(String::from("Last3")),
+                verified: Some(true),
+                ..Default::default()
+            },
+            UserFindRequestFilter {
+                firstname: Some(String::from("First*")),
+                lastname: Some(String::from("Last1")),
+                ..Default::default()
+            },
+        ];
+        let data = repository.data.read().await;
+        for filter in filters {
+            for order_by in ["", "email", "firstname", "lastname"] {
+                for offset in [1, 2, 4] {
+                    let options = UserFindRequest::new(&filter, order_by, &7, &offset).unwrap();
+                    assert_eq!(
+                        data.tenant(&repository.tenant).find_page(&options),
+                        find_page(data.tenant(&repository.tenant).users.values(), &options),
+                        "{filter:?} ordered by {order_by:?} page {offset}"
+                    );
+                }
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_indexes_follow_updates() {
+        let repository = InMemoryUserRepository::new();
+        let john = repository
+            .save(&User::new(
+                &Uuid::new_v4(),
+                &Name::new("John").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new("john@example.com").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let jane = repository
+            .save(&User::new(
+                &Uuid::new_v4(),
+                &Name::new("Jane").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new("jane@example.com").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let moved = User::new(
+            john.get_id(),
+            john.get_firstname(),
+            john.get_lastname(),
+            &EmailAddress::new("johnny@example.com").unwrap(),
+        );
+        repository.update(john.get_id(), &moved).await.unwrap();
+        let taken = User::new(
+            jane.get_id(),
+            jane.get_firstname(),
+            jane.get_lastname(),
+            moved.get_email(),
+        );
+        assert!(matches!(
+            repository.update(jane.get_id(), &taken).await,
+            Err(UserError::EmailAlreadyUsedByOther { email: _ })
+        ));
+        let freed = User::new(
+            jane.get_id(),
+            jane.get_firstname(),
+            jane.get_lastname(),
+            john.get_email(),
+        );
+        repository.update(jane.get_id(), &freed).await.unwrap();
+        repository.delete(jane.get_id()).await.unwrap();
+
+        let find = |email: &str| {
+            UserFindRequest::new(
+                &UserFindRequestFilter {
+                    email: Some(email.to_string()),
+                    ..Default::default()
+                },
+                "",
+                &25,
+                &1,
+            )
+            .unwrap()
+        };
+        let found = repository.find_all(&find("john*")).await.unwrap();
+        assert_eq!(found.get_result().collect::<Vec<_>>(), vec![moved]);
+        let found = repository
+            .find_all(&find("jane@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(found.get_result().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_commit_and_rollback() {
+        let repository = InMemoryUserRepository::new();
+        let john = repository
+            .save(&User::new(
+                &Uuid::new_v4(),
+                &Name::new("John").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new("john@example.com").unwrap(),
+            ))
+            .await
+            .unwrap();
+        let jane = User::new(
+            &Uuid::new_v4(),
+            &Name::new("Jane").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("jane@example.com").unwrap(),
+        );
+
+        let mut transaction = repository.begin().await.unwrap();
+        let staged = transaction.save(&jane).await.unwrap();
+        assert_eq!(
+            transaction.find_by_id(staged.get_id()).await.unwrap(),
+            staged
+        );
+        transaction.delete(john.get_id()).await.unwrap();
+        transaction.rollback().await.unwrap();
+        assert!(repository.find_by_id(staged.get_id()).await.is_err());
+        assert!(repository.find_by_id(john.get_id()).await.is_ok());
+        assert_eq!(repository.pending_events(10).await.unwrap().len(), 1);
+
+        let mut transaction = repository.begin().await.unwrap();
+        let saved = transaction.save(&jane).await.unwrap();
+        let taken = User::new(
+            john.get_id(),
+            john.get_firstname(),
+            john.get_lastname(),
+            jane.get_email(),
+        );
+        assert!(matches!(
+            transaction.update(john.get_id(), &taken).await,
+            Err(UserError::EmailAlreadyUsedByOther { email: _ })
+        ));
+        transaction.delete(john.get_id()).await.unwrap();
+        transaction.commit().await.unwrap();
+        assert!(repository.find_by_id(john.get_id()).await.is_err());
+        assert_eq!(repository.find_by_id(saved.get_id()).await.unwrap(), saved);
+        assert_eq!(repository.pending_events(10).await.unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_save_requires_id() {
+        let repository = InMemoryUserRepository::new();
+        let acme = repository.for_tenant(&TenantId::new("acme").unwrap());
+        let john = User::new(
+            &Uuid::nil(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        assert!(matches!(
+            repository.save(&john).await,
+            Err(UserError::MissingUserId)
+        ));
+
+        let jane = User::new(
+            &Uuid::from_u128(42),
+            &Name::new("Jane").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("jane@example.com").unwrap(),
+        );
+        assert_eq!(
+            repository.save(&jane).await.unwrap().get_id(),
+            &Uuid::from_u128(42)
+        );
+        assert!(matches!(
+            acme.save(&jane).await,
+            Err(UserError::IdAlreadyUsed { id: _ })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_tenants_are_isolated() {
+        let repository = InMemoryUserRepository::new();
+        let acme = repository.for_tenant(&TenantId::new("acme").unwrap());
+        let globex = repository.for_tenant(&TenantId::new("globex").unwrap());
+        let mut acme_changes = acme.subscribe_changes(None).await.unwrap();
+        let john = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let saved = acme.save(&john).await.unwrap();
+        assert_eq!(&**saved.get_tenant(), "acme");
+        assert!(matches!(
+            globex.save(&john).await,
+            Err(UserError::IdAlreadyUsed { id: _ })
+        ));
+        let other = globex.save(&john.with_id(&Uuid::new_v4())).await.unwrap();
+        assert!(matches!(
+            acme.save(&john.with_id(&Uuid::new_v4())).await,
+            Err(UserError::EmailAlreadyUsed { email: _ })
+        ));
+
+        assert!(globex.find_by_id(saved.get_id()).await.is_err());
+        assert!(repository.find_by_id(saved.get_id()).await.is_err());
+        assert!(globex.update(saved.get_id(), &john).await.is_err());
+        assert!(globex.delete(saved.get_id()).await.is_err());
+        let found = globex
+            .find_all(
+                &UserFindRequest::new(&UserFindRequestFilter::default(), "", &10, &1).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(
+            found.get_result().map(|u| *u.get_id()).collect::<Vec<_>>(),
+            [*other.get_id()]
+        );
+
+        let change = acme_changes.next().await.unwrap();
+        assert_eq!(&**change.get_event().get_tenant(), "acme");
+        globex.delete(other.get_id()).await.unwrap();
+        acme.delete(saved.get_id()).await.unwrap();
+        let change = acme_changes.next().await.unwrap();
+        assert_eq!(change.get_event().get_user_id(), saved.get_id());
+    }
+
+    #[tokio::test]
+    async fn test_find_by_custom_attributes() {
+        let repository = InMemoryUserRepository::new();
+        for (firstname, department, level) in [
+            ("John", "Sales", 3),
+            ("Jane", "Engineering", 1),
+            ("Jack", "Sales", 2),
+            ("Jill", "Support", 5),
+        ] {
+            let mut user = User::new(
+                &Uuid::new_v4(),
+                &Name::new(firstname).unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new(&format!("{}@example.com", firstname.to_lowercase())).unwrap(),
+            );
+            user.set_attributes(
+                &CustomAttributes::new()
+                    .with("department", AttributeValue::String(department.to_string()))
+                    .with("level", AttributeValue::Integer(level)),
+            );
+            repository.save(&user).await.unwrap();
+        }
+        let firstnames = |filter: &UserFindRequestFilter, order_by: &str| {
+            let options = UserFindRequest::new(filter, order_by, &10, &1).unwrap();
+            let repository = repository.clone();
+            async move {
+                repository
+                    .find_all(&options)
+                    .await
+                    .unwrap()
+                    .get_result()
+                    .map(|user| user.get_firstname().to_string())
+                    .collect::<Vec<_>>()
+            }
+        };
+
+        let sales = UserFindRequestFilter {
+            attributes: Some(AttributeFilter::new("department:Sales").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(
+            firstnames(&sales, "attributes.level").await,
+            ["Jack", "John"]
+        );
+        let senior_sales = UserFindRequestFilter {
+            attributes: Some(AttributeFilter::new("department:S*, level:3").unwrap()),
+            ..Default::default()
+        };
+        assert_eq!(firstnames(&senior_sales, "").await, ["John"]);
+        assert_eq!(
+            firstnames(&UserFindRequestFilter::default(), "attributes.level").await,
+            ["Jane", "Jack", "John", "Jill"]
+        );
+        let by_lastname = UserFindRequestFilter {
+            lastname: Some(String::from("Doe")),
+            ..Default::default()
+        };
+        assert_eq!(
+            firstnames(&by_lastname, "attributes.level").await,
+            ["Jane", "Jack", "John", "Jill"]
+        );
+        assert!(AttributeFilter::new("department").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_find_all_first_page() {
+        let repository = InMemoryUserRepository::new();
+        for index in 0..3 {
+            let user = User::new(
+                &Uuid::new_v4(),
+                &Name::new("Jane").unwrap(),
+                &Name::new("Doe").unwrap(),
+                &EmailAddress::new(&format!("user{index}@example.com")).unwrap(),
+            );
+            repository.save(&user).await.unwrap();
+        }
+        let filter = UserFindRequestFilter::default();
+        for (offset, expected) in [
+            (1, vec!["user0@example.com", "user1@example.com"]),
+            (2, vec!["user2@example.com"]),
+            (3, vec![]),
+        ] {
+            let options = UserFindRequest::new(&filter, "email", &2, &offset).unwrap();
+            let page = repository.find_all(&options).await.unwrap();
+            let emails: Vec<String> = page
+                .get_result()
+                .map(|user| user.get_email().to_string())
+                .collect();
+            assert_eq!(emails, expected, "page {offset}");
+            assert_eq!(page.get_page_count(), 2);
+        }
+    }
+}
@@ -0,0 +1,62 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::business::user::{
+    model::user::UserError, VerificationToken, VerificationTokenStoreTrait,
+};
+
+#[derive(Debug, Clone)]
+pub struct InMemoryVerificationTokenStore {
+    data: Arc<RwLock<HashMap<String, VerificationToken>>>,
+}
+
+impl Default for InMemoryVerificationTokenStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryVerificationTokenStore {
+    pub fn new() -> Self {
+        InMemoryVerificationTokenStore {
+            data: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl VerificationTokenStoreTrait for InMemoryVerificationTokenStore {
+    fn store(
+        &self,
+        token: &VerificationToken,
+    ) -> impl Future<Output = Result<(), UserError>> + Send {
+        Box::pin(async move {
+            self.data
+                .write()
+                .await
+                .insert(token.get_token_hash().to_string(), token.clone());
+            Ok(())
+        })
+    }
+
+    fn take(
+        &self,
+        token_hash: &str,
+    ) -> impl Future<Output = Result<Option<VerificationToken>, UserError>> + Send {
+        Box::pin(async move { Ok(self.data.write().await.remove(token_hash)) })
+    }
+
+    fn revoke_for_user(
+        &self,
+        user_id: &Uuid,
+    ) -> impl Future<Output = Result<(), UserError>> + Send {
+        Box::pin(async move {
+            self.data
+                .write()
+                .await
+                .retain(|_k, v| v.get_user_id().ne(user_id));
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,141 @@
+use std::{future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    user::User,
+    webhook::{DeliveryStatus, WebhookDelivery, WebhookDeliveryStoreTrait, WebhookError},
+};
+
+#[derive(Debug, Clone)]
+pub struct InMemoryWebhookDeliveryStore {
+    data: Arc<RwLock<Vec<WebhookDelivery>>>,
+}
+
+impl Default for InMemoryWebhookDeliveryStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryWebhookDeliveryStore {
+    pub fn new() -> Self {
+        InMemoryWebhookDeliveryStore {
+            data: Arc::new(RwLock::new(Vec::new())),
+        }
+    }
+
+    async fn find_where<F>(&self, predicate: F) -> Vec<WebhookDelivery>
+    where
+        F: Fn(&WebhookDelivery) -> bool,
+    {
+        self.data
+            .read()
+            .await
+            .iter()
+            .filter(|delivery| predicate(delivery))
+            .cloned()
+            .collect()
+    }
+}
+
+impl WebhookDeliveryStoreTrait for InMemoryWebhookDeliveryStore {
+    fn save(
+        &self,
+        delivery: &WebhookDelivery,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            match data
+                .iter_mut()
+                .find(|stored| stored.get_id().eq(delivery.get_id()))
+            {
+                Some(stored) => *stored = delivery.clone(),
+                None => data.push(delivery.clone()),
+            }
+            Ok(())
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookDelivery, WebhookError>> + Send {
+        Box::pin(async move {
+            self.find_where(|delivery| delivery.get_id().eq(id))
+                .await
+                .pop()
+                .ok_or(WebhookError::DeliveryNotExists { id: *id })
+        })
+    }
+
+    fn find_by_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .find_where(|delivery| delivery.get_subscription_id().eq(subscription_id))
+                .await)
+        })
+    }
+
+    fn find_due(
+        &self,
+        now: &u64,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async move {
+            let mut due = self.find_where(|delivery| delivery.is_due(now)).await;
+            due.truncate(limit);
+            Ok(due)
+        })
+    }
+
+    fn find_dead_letters(
+        &self,
+        tenant: &TenantId,
+    ) -> impl Future<Output = Result<Vec<WebhookDelivery>, WebhookError>> + Send {
+        Box::pin(async move {
+            Ok(self
+                .find_where(|delivery| {
+                    delivery.get_status() == DeliveryStatus::DeadLettered
+                        && delivery.get_tenant().eq(tenant)
+                })
+                .await)
+        })
+    }
+
+    fn delete_for_subscription(
+        &self,
+        subscription_id: &Uuid,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .write()
+                .await
+                .retain(|delivery| delivery.get_subscription_id().ne(subscription_id));
+            Ok(())
+        })
+    }
+
+    fn redact_user(
+        &self,
+        erased: &User,
+    ) -> impl Future<Output = Result<usize, WebhookError>> + Send {
+        Box::pin(async move {
+            let mut data = self.data.write().await;
+            let deliveries = data
+                .iter_mut()
+                .filter(|delivery| delivery.get_user_id().eq(erased.get_id()));
+            let mut redacted = 0;
+            for delivery in deliveries {
+                delivery.redact(erased);
+                redacted += 1;
+            }
+            Ok(redacted)
+        })
+    }
+}
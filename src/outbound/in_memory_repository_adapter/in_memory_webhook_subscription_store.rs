@@ -0,0 +1,82 @@
+use std::{collections::HashMap, future::Future, sync::Arc};
+
+use tokio::sync::RwLock;
+use uuid::Uuid;
+
+use crate::business::{
+    auth::TenantId,
+    webhook::{WebhookError, WebhookSubscription, WebhookSubscriptionStoreTrait},
+};
+
+#[derive(Debug, Clone)]
+pub struct InMemoryWebhookSubscriptionStore {
+    data: Arc<RwLock<HashMap<Uuid, WebhookSubscription>>>,
+}
+
+impl Default for InMemoryWebhookSubscriptionStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryWebhookSubscriptionStore {
+    pub fn new() -> Self {
+        InMemoryWebhookSubscriptionStore {
+            data: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+}
+
+impl WebhookSubscriptionStoreTrait for InMemoryWebhookSubscriptionStore {
+    fn save(
+        &self,
+        subscription: &WebhookSubscription,
+    ) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            self.data
+                .write()
+                .await
+                .insert(*subscription.get_id(), subscription.clone());
+            Ok(())
+        })
+    }
+
+    fn delete(&self, id: &Uuid) -> impl Future<Output = Result<(), WebhookError>> + Send {
+        Box::pin(async move {
+            match self.data.write().await.remove(id) {
+                Some(_) => Ok(()),
+                None => Err(WebhookError::SubscriptionNotExists { id: *id }),
+            }
+        })
+    }
+
+    fn find_by_id(
+        &self,
+        id: &Uuid,
+    ) -> impl Future<Output = Result<WebhookSubscription, WebhookError>> + Send {
+        Box::pin(async move {
+            match self.data.read().await.get(id) {
+                Some(subscription) => Ok(subscription.clone()),
+                None => Err(WebhookError::SubscriptionNotExists { id: *id }),
+            }
+        })
+    }
+
+    fn find_all(
+        &self,
+        tenant: &TenantId,
+    ) -> impl Future<Output = Result<Vec<WebhookSubscription>, WebhookError>> + Send {
+        Box::pin(async move {
+            let mut subscriptions: Vec<WebhookSubscription> = self
+                .data
+                .read()
+                .await
+                .values()
+                .filter(|subscription| subscription.get_tenant().eq(tenant))
+                .cloned()
+                .collect();
+            subscriptions.sort_by_key(|subscription| subscription.get_created_at());
+            Ok(subscriptions)
+        })
+    }
+}
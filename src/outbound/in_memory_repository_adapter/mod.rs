@@ -1 +1,13 @@
+pub mod in_memory_audit_trail;
+pub mod in_memory_credential_repository;
+pub mod in_memory_crud_repository;
+pub mod in_memory_group_repository;
+pub mod in_memory_idempotency_store;
+pub mod in_memory_rate_limit_store;
 pub mod in_memory_user_repository;
+pub mod in_memory_verification_token_store;
+pub mod in_memory_webhook_delivery_store;
+pub mod in_memory_webhook_subscription_store;
+pub mod user_change_log;
+pub mod user_change_set;
+pub mod user_outbox;
@@ -0,0 +1,110 @@
+use std::collections::VecDeque;
+
+use tokio::sync::broadcast;
+
+use crate::business::user::{User, UserChange, UserChangeSubscription, UserEvent};
+
+#[derive(Debug)]
+pub struct UserChangeLog {
+    entries: VecDeque<UserChange>,
+    capacity: usize,
+    next_sequence: u64,
+    sender: broadcast::Sender<UserChange>,
+}
+
+impl Default for UserChangeLog {
+    fn default() -> Self {
+        Self::new(1000)
+    }
+}
+
+impl UserChangeLog {
+    pub fn new(capacity: usize) -> Self {
+        let capacity = capacity.max(1);
+        let (sender, _) = broadcast::channel(capacity);
+        Self {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+            next_sequence: 1,
+            sender,
+        }
+    }
+
+    pub fn record(&mut self, event: &UserEvent) {
+        let change = UserChange::new(&self.next_sequence, event);
+        self.next_sequence += 1;
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(change.clone());
+        let _ = self.sender.send(change);
+    }
+
+    /// Rewrites the changes of the user kept for replay with its erased version.
+    pub fn redact(&mut self, erased: &User) {
+        self.entries
+            .iter_mut()
+            .for_each(|change| change.redact(erased));
+    }
+
+    /// Without `last_sequence` only live changes are streamed.
+    pub fn subscribe(&self, last_sequence: Option<u64>) -> UserChangeSubscription {
+        let backlog = match last_sequence {
+            Some(last) => self
+                .entries
+                .iter()
+                .filter(|change| change.get_sequence() > last)
+                .cloned()
+                .collect(),
+            None => Vec::new(),
+        };
+        let last_sequence = last_sequence.or(self.next_sequence.checked_sub(1));
+        UserChangeSubscription::new(backlog, self.sender.subscribe(), last_sequence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::user::{dtos::UserFindRequestFilter, EmailAddress, Name, User, UserEvent};
+
+    use super::UserChangeLog;
+
+    fn user(firstname: &str) -> User {
+        User::new(
+            &Uuid::new_v4(),
+            &Name::new(firstname).unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_resume_from_bounded_log() {
+        let mut log = UserChangeLog::new(2);
+        for firstname in ["Anna", "Bob", "Carl"] {
+            log.record(&UserEvent::created(&user(firstname)));
+        }
+        let mut subscription = log.subscribe(Some(1));
+        assert_eq!(subscription.next().await.unwrap().get_sequence(), 2);
+        assert_eq!(subscription.next().await.unwrap().get_sequence(), 3);
+        log.record(&UserEvent::created(&user("Dana")));
+        assert_eq!(subscription.next().await.unwrap().get_sequence(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_live_subscription_with_filter() {
+        let mut log = UserChangeLog::default();
+        log.record(&UserEvent::created(&user("Anna")));
+        let filter = UserFindRequestFilter {
+            firstname: Some("Carl".to_string()),
+            ..Default::default()
+        };
+        let mut subscription = log.subscribe(None).with_filter(&filter);
+        log.record(&UserEvent::created(&user("Bob")));
+        log.record(&UserEvent::created(&user("Carl")));
+        let change = subscription.next().await.unwrap();
+        assert_eq!(change.get_sequence(), 3);
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::BTreeMap;
+
+use uuid::Uuid;
+
+use crate::business::{
+    audit::AuditContext,
+    auth::TenantId,
+    user::{model::user::UserError, EmailAddress, User, UserEvent},
+};
+
+/// Read access to the committed users of a store, across tenants.
+pub(crate) trait UserLookupTrait {
+    fn find_user(&self, user_id: &Uuid) -> Option<User>;
+    fn find_ids_by_email(&self, email: &EmailAddress) -> Vec<Uuid>;
+}
+
+/// Changes staged by a transaction over a store: the final state of every
+/// touched user (`None` once deleted) and the events to record on commit,
+/// each with its audit record when the transaction is audited. Users of
+/// other tenants are invisible to it.
+#[derive(Debug)]
+pub(crate) struct UserChangeSet {
+    tenant: TenantId,
+    audit: Option<AuditContext>,
+    staged: BTreeMap<Uuid, Option<User>>,
+    events: Vec<UserEvent>,
+}
+
+impl UserChangeSet {
+    pub(crate) fn new(tenant: &TenantId, audit: Option<&AuditContext>) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            audit: audit.cloned(),
+            staged: BTreeMap::new(),
+            events: Vec::new(),
+        }
+    }
+
+    fn record(&mut self, event: UserEvent, before: Option<&User>, after: Option<&User>) {
+        let event = match &self.audit {
+            Some(audit) => {
+                let record = audit.record(event.get_id(), before, after);
+                event.with_audit(&record)
+            }
+            None => event,
+        };
+        self.events.push(event);
+    }
+
+    pub(crate) fn find(&self, store: &impl UserLookupTrait, user_id: &Uuid) -> Option<User> {
+        match self.staged.get(user_id) {
+            Some(staged) => staged.clone(),
+            None => store
+                .find_user(user_id)
+                .filter(|user| user.get_tenant().eq(&self.tenant)),
+        }
+    }
+
+    fn is_email_used_by_other(
+        &self,
+        store: &impl UserLookupTrait,
+        email: &EmailAddress,
+        user_id: &Uuid,
+    ) -> bool {
+        store.find_ids_by_email(email).iter().any(|other| {
+            other.ne(user_id)
+                && !self.staged.contains_key(other)
+                && self.find(store, other).is_some()
+        }) || self.staged.iter().any(|(other, staged)| {
+            other.ne(user_id) && staged.as_ref().is_some_and(|u| u.get_email().eq(email))
+        })
+    }
+
+    /// Ids are assigned by the caller and unique across tenants: an id taken
+    /// in another tenant conflicts like one taken in this tenant.
+    pub(crate) fn save(
+        &mut self,
+        store: &impl UserLookupTrait,
+        entity: &User,
+    ) -> Result<User, UserError> {
+        let user_id = *entity.get_id();
+        if user_id.is_nil() {
+            return Err(UserError::MissingUserId);
+        }
+        let exists = match self.staged.get(&user_id) {
+            Some(staged) => staged.is_some(),
+            None => store.find_user(&user_id).is_some(),
+        };
+        if exists {
+            return Err(UserError::IdAlreadyUsed { id: user_id });
+        }
+        if self.is_email_used_by_other(store, entity.get_email(), &user_id) {
+            return Err(UserError::EmailAlreadyUsed {
+                email: entity.get_email().clone(),
+            });
+        }
+        let mut user = entity.clone();
+        user.set_tenant(&self.tenant);
+        self.staged.insert(user_id, Some(user.clone()));
+        self.record(UserEvent::created(&user), None, Some(&user));
+        Ok(user)
+    }
+
+    /// Stages the new state of an existing user, returning the previous one.
+    fn stage(
+        &mut self,
+        store: &impl UserLookupTrait,
+        user_id: &Uuid,
+        entity: &User,
+    ) -> Result<(User, User), UserError> {
+        if user_id.ne(entity.get_id()) {
+            return Err(UserError::MismatchUserId {
+                id1: *user_id,
+                id2: *entity.get_id(),
+            });
+        }
+        let before = self
+            .find(store, user_id)
+            .ok_or(UserError::UserNotExists { id: *user_id })?;
+        if self.is_email_used_by_other(store, entity.get_email(), user_id) {
+            return Err(UserError::EmailAlreadyUsedByOther {
+                email: entity.get_email().clone(),
+            });
+        }
+        let mut user = entity.clone();
+        user.set_tenant(&self.tenant);
+        self.staged.insert(*user_id, Some(user.clone()));
+        Ok((before, user))
+    }
+
+    pub(crate) fn update(
+        &mut self,
+        store: &impl UserLookupTrait,
+        user_id: &Uuid,
+        entity: &User,
+    ) -> Result<User, UserError> {
+        let (before, user) = self.stage(store, user_id, entity)?;
+        self.record(
+            UserEvent::updated(&before, &user),
+            Some(&before),
+            Some(&user),
+        );
+        Ok(user)
+    }
+
+    /// Stages `entity` like `update`, but as an erasure: the events staged so
+    /// far for the user are rewritten with it, and stores do the same with
+    /// the ones they recorded when the erasure is committed.
+    pub(crate) fn erase(
+        &mut self,
+        store: &impl UserLookupTrait,
+        user_id: &Uuid,
+        entity: &User,
+    ) -> Result<User, UserError> {
+        let (before, user) = self.stage(store, user_id, entity)?;
+        self.events.iter_mut().for_each(|event| event.redact(&user));
+        self.record(UserEvent::erased(&user), Some(&before), Some(&user));
+        Ok(user)
+    }
+
+    pub(crate) fn delete(
+        &mut self,
+        store: &impl UserLookupTrait,
+        user_id: &Uuid,
+    ) -> Result<(), UserError> {
+        let before = self
+            .find(store, user_id)
+            .ok_or(UserError::UserNotExists { id: *user_id })?;
+        self.staged.insert(*user_id, None);
+        self.record(UserEvent::deleted(&before), Some(&before), None);
+        Ok(())
+    }
+
+    pub(crate) fn into_parts(self) -> (BTreeMap<Uuid, Option<User>>, Vec<UserEvent>) {
+        (self.staged, self.events)
+    }
+}
@@ -0,0 +1,89 @@
+use std::collections::VecDeque;
+
+use uuid::Uuid;
+
+use crate::business::user::UserEvent;
+
+/// Events recorded by a store and not yet published, oldest first.
+#[derive(Debug, Default)]
+pub struct UserOutbox {
+    events: VecDeque<UserEvent>,
+}
+
+impl UserOutbox {
+    /// An erasure also redacts the events of the user still waiting.
+    pub fn push(&mut self, event: UserEvent) {
+        if let Some(erased) = event.get_erased_user() {
+            self.events
+                .iter_mut()
+                .for_each(|pending| pending.redact(erased));
+        }
+        self.events.push_back(event);
+    }
+
+    pub fn pending(&self, limit: usize) -> Vec<UserEvent> {
+        self.events.iter().take(limit).cloned().collect()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UserEvent> {
+        self.events.iter()
+    }
+
+    pub fn contains(&self, event_id: &Uuid) -> bool {
+        self.events
+            .iter()
+            .any(|pending| pending.get_id().eq(event_id))
+    }
+
+    /// Events are published in order, so the acknowledged one is normally
+    /// the oldest. Returns whether it was still pending.
+    pub fn acknowledge(&mut self, event_id: &Uuid) -> bool {
+        if self
+            .events
+            .front()
+            .is_some_and(|oldest| oldest.get_id().eq(event_id))
+        {
+            self.events.pop_front();
+            return true;
+        }
+        match self
+            .events
+            .iter()
+            .position(|pending| pending.get_id().eq(event_id))
+        {
+            Some(index) => self.events.remove(index).is_some(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::business::user::{EmailAddress, Name, User, UserEvent};
+
+    use super::UserOutbox;
+
+    #[test]
+    fn test_acknowledge_in_any_order() {
+        let user = User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        );
+        let mut outbox = UserOutbox::default();
+        let events = [
+            UserEvent::created(&user),
+            UserEvent::updated(&user, &user),
+            UserEvent::deleted(&user),
+        ];
+        events.iter().for_each(|event| outbox.push(event.clone()));
+
+        assert!(outbox.acknowledge(events[1].get_id()));
+        assert!(outbox.acknowledge(events[0].get_id()));
+        assert!(!outbox.acknowledge(events[0].get_id()));
+        assert_eq!(outbox.pending(10), [events[2].clone()]);
+    }
+}
@@ -0,0 +1,356 @@
+use std::{future::Future, path::Path, sync::Arc};
+
+use jsonwebtoken::{
+    errors::ErrorKind,
+    jwk::{AlgorithmParameters, JwkSet, KeyAlgorithm},
+    Algorithm, DecodingKey, Validation,
+};
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::business::auth::{
+    AuthError, AuthToken, AuthenticationMethod, AuthenticatorTrait, Principal, Role, TenantId,
+};
+
+use super::jwt_token_issuer::REFRESH_TOKEN_USE;
+
+#[derive(Clone)]
+struct JwtKey {
+    key_id: Option<String>,
+    algorithm: Algorithm,
+    key: DecodingKey,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtClaims {
+    sub: String,
+    #[serde(default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    token_use: Option<String>,
+    #[serde(default)]
+    tenant: Option<TenantId>,
+}
+
+#[derive(Clone, Default)]
+pub struct JwtAuthenticator {
+    keys: Arc<Vec<JwtKey>>,
+    issuer: Option<String>,
+    audience: Option<String>,
+}
+
+impl JwtAuthenticator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_issuer(mut self, issuer: &str) -> Self {
+        self.issuer = Some(issuer.to_string());
+        self
+    }
+
+    pub fn with_audience(mut self, audience: &str) -> Self {
+        self.audience = Some(audience.to_string());
+        self
+    }
+
+    pub fn with_hs256_secret(self, secret: &[u8]) -> Self {
+        self.with_key(None, Algorithm::HS256, DecodingKey::from_secret(secret))
+    }
+
+    pub fn with_hs256_secret_file(self, path: &Path) -> Result<Self, AuthError> {
+        let secret = Self::read_key_file(path)?;
+        Ok(self.with_hs256_secret(String::from_utf8_lossy(&secret).trim().as_bytes()))
+    }
+
+    pub fn with_rs256_public_key_pem(self, pem: &[u8]) -> Result<Self, AuthError> {
+        let key = DecodingKey::from_rsa_pem(pem).map_err(|e| AuthError::InvalidKey {
+            source_name: String::from("RS256 public key"),
+            reason: e.to_string(),
+        })?;
+        Ok(self.with_key(None, Algorithm::RS256, key))
+    }
+
+    pub fn with_rs256_public_key_file(self, path: &Path) -> Result<Self, AuthError> {
+        let pem = Self::read_key_file(path)?;
+        self.with_rs256_public_key_pem(&pem)
+            .map_err(|e| Self::rename_key_error(e, path))
+    }
+
+    pub fn with_jwks(mut self, jwks: &JwkSet) -> Result<Self, AuthError> {
+        for jwk in &jwks.keys {
+            let algorithm = match (&jwk.algorithm, jwk.common.key_algorithm) {
+                (AlgorithmParameters::RSA(_), None | Some(KeyAlgorithm::RS256)) => Algorithm::RS256,
+                (AlgorithmParameters::OctetKey(_), None | Some(KeyAlgorithm::HS256)) => {
+                    Algorithm::HS256
+                }
+                _ => continue,
+            };
+            let key = DecodingKey::from_jwk(jwk).map_err(|e| AuthError::InvalidKey {
+                source_name: jwk
+                    .common
+                    .key_id
+                    .clone()
+                    .unwrap_or_else(|| String::from("JWKS entry")),
+                reason: e.to_string(),
+            })?;
+            self = self.with_key(jwk.common.key_id.as_deref(), algorithm, key);
+        }
+        Ok(self)
+    }
+
+    pub fn with_jwks_file(self, path: &Path) -> Result<Self, AuthError> {
+        let content = Self::read_key_file(path)?;
+        let jwks: JwkSet = serde_json::from_slice(&content).map_err(|e| AuthError::InvalidKey {
+            source_name: path.display().to_string(),
+            reason: e.to_string(),
+        })?;
+        self.with_jwks(&jwks)
+            .map_err(|e| Self::rename_key_error(e, path))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+
+    fn with_key(mut self, key_id: Option<&str>, algorithm: Algorithm, key: DecodingKey) -> Self {
+        let mut keys = self.keys.as_ref().clone();
+        keys.push(JwtKey {
+            key_id: key_id.map(str::to_string),
+            algorithm,
+            key,
+        });
+        self.keys = Arc::new(keys);
+        self
+    }
+
+    fn read_key_file(path: &Path) -> Result<Vec<u8>, AuthError> {
+        std::fs::read(path).map_err(|e| AuthError::InvalidKey {
+            source_name: path.display().to_string(),
+            reason: e.to_string(),
+        })
+    }
+
+    fn rename_key_error(error: AuthError, path: &Path) -> AuthError {
+        match error {
+            AuthError::InvalidKey { reason, .. } => AuthError::InvalidKey {
+                source_name: path.display().to_string(),
+                reason,
+            },
+            e => e,
+        }
+    }
+
+    fn validation(&self, algorithm: Algorithm) -> Validation {
+        let mut validation = Validation::new(algorithm);
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        validation
+    }
+
+    fn decode(&self, token: &str) -> Result<Principal, AuthError> {
+        let header = jsonwebtoken::decode_header(token).map_err(|e| AuthError::InvalidToken {
+            reason: e.to_string(),
+        })?;
+        let candidates = self.keys.iter().filter(|k| {
+            k.algorithm == header.alg
+                && (header.kid.is_none() || k.key_id.is_none() || k.key_id == header.kid)
+        });
+        let mut last_error = AuthError::InvalidToken {
+            reason: format!("no key available for algorithm {:?}", header.alg),
+        };
+        for candidate in candidates {
+            match jsonwebtoken::decode::<JwtClaims>(
+                token,
+                &candidate.key,
+                &self.validation(candidate.algorithm),
+            ) {
+                Ok(data) if data.claims.token_use.as_deref() == Some(REFRESH_TOKEN_USE) => {
+                    return Err(AuthError::InvalidToken {
+                        reason: String::from("refresh token cannot be used as access token"),
+                    })
+                }
+                Ok(data) => {
+                    let claims = data.claims;
+                    let user_id = Uuid::parse_str(&claims.sub).ok();
+                    let principal = Principal::new(
+                        &claims.sub,
+                        user_id.as_ref(),
+                        &claims.roles,
+                        AuthenticationMethod::Jwt,
+                    );
+                    return Ok(match &claims.tenant {
+                        Some(tenant) => principal.with_tenant(tenant),
+                        None => principal,
+                    });
+                }
+                Err(e) => {
+                    last_error = match e.kind() {
+                        ErrorKind::ExpiredSignature => return Err(AuthError::ExpiredToken),
+                        _ => AuthError::InvalidToken {
+                            reason: e.to_string(),
+                        },
+                    }
+                }
+            }
+        }
+        Err(last_error)
+    }
+}
+
+impl AuthenticatorTrait for JwtAuthenticator {
+    fn authenticate(
+        &self,
+        token: &AuthToken,
+    ) -> impl Future<Output = Result<Principal, AuthError>> + Send {
+        Box::pin(async move {
+            match token {
+                AuthToken::Bearer(token) => self.decode(token),
+                _ => Err(AuthError::UnsupportedCredentials),
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use jsonwebtoken::{EncodingKey, Header};
+    use serde::Serialize;
+    use uuid::Uuid;
+
+    use crate::business::auth::{AuthError, AuthToken, AuthenticatorTrait, Role};
+
+    use super::JwtAuthenticator;
+
+    const FIXTURES: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/jwt");
+
+    #[derive(Serialize)]
+    struct Claims {
+        sub: String,
+        exp: u64,
+        roles: Vec<String>,
+    }
+
+    fn fixture(name: &str) -> PathBuf {
+        PathBuf::from(FIXTURES).join(name)
+    }
+
+    fn claims(sub: &str, expires_in: i64) -> Claims {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        Claims {
+            sub: sub.to_string(),
+            exp: (now + expires_in) as u64,
+            roles: vec![String::from("Admin")],
+        }
+    }
+
+    fn rs256_token(kid: Option<&str>, claims: &Claims) -> String {
+        let pem = std::fs::read(fixture("rs256_private.pem")).unwrap();
+        let mut header = Header::new(jsonwebtoken::Algorithm::RS256);
+        header.kid = kid.map(str::to_string);
+        jsonwebtoken::encode(&header, claims, &EncodingKey::from_rsa_pem(&pem).unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_hs256_ok() {
+        let user_id = Uuid::new_v4();
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims(&user_id.to_string(), 60),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        let principal = authenticator
+            .authenticate(&AuthToken::Bearer(token))
+            .await
+            .unwrap();
+        assert_eq!(principal.get_user_id(), Some(&user_id));
+        assert!(principal.has_role(&Role::new("admin")));
+    }
+
+    #[tokio::test]
+    async fn test_hs256_wrong_secret_ko() {
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims("john", 60),
+            &EncodingKey::from_secret(b"other"),
+        )
+        .unwrap();
+        let result = authenticator.authenticate(&AuthToken::Bearer(token)).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_hs256_expired_ko() {
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let token = jsonwebtoken::encode(
+            &Header::default(),
+            &claims("john", -3600),
+            &EncodingKey::from_secret(b"secret"),
+        )
+        .unwrap();
+        let result = authenticator.authenticate(&AuthToken::Bearer(token)).await;
+        assert!(matches!(result, Err(AuthError::ExpiredToken)));
+    }
+
+    #[tokio::test]
+    async fn test_rs256_public_key_file_ok() {
+        let authenticator = JwtAuthenticator::new()
+            .with_rs256_public_key_file(&fixture("rs256_public.pem"))
+            .unwrap();
+        let token = rs256_token(None, &claims("john", 60));
+        let principal = authenticator
+            .authenticate(&AuthToken::Bearer(token))
+            .await
+            .unwrap();
+        assert_eq!(principal.get_subject(), "john");
+        assert_eq!(principal.get_user_id(), None);
+    }
+
+    #[tokio::test]
+    async fn test_jwks_file_ok() {
+        let authenticator = JwtAuthenticator::new()
+            .with_jwks_file(&fixture("jwks.json"))
+            .unwrap();
+        let token = rs256_token(Some("test-rs256"), &claims("john", 60));
+        let result = authenticator.authenticate(&AuthToken::Bearer(token)).await;
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_jwks_unknown_kid_ko() {
+        let authenticator = JwtAuthenticator::new()
+            .with_jwks_file(&fixture("jwks.json"))
+            .unwrap();
+        let token = rs256_token(Some("unknown"), &claims("john", 60));
+        let result = authenticator.authenticate(&AuthToken::Bearer(token)).await;
+        assert!(matches!(result, Err(AuthError::InvalidToken { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_api_key_unsupported() {
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let result = authenticator
+            .authenticate(&AuthToken::ApiKey(String::from("key")))
+            .await;
+        assert!(matches!(result, Err(AuthError::UnsupportedCredentials)));
+    }
+
+    #[test]
+    fn test_missing_key_file_ko() {
+        let result = JwtAuthenticator::new().with_hs256_secret_file(&fixture("missing"));
+        assert!(matches!(result, Err(AuthError::InvalidKey { .. })));
+    }
+}
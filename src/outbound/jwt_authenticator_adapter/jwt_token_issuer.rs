@@ -0,0 +1,263 @@
+use std::{
+    path::Path,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::business::{
+    auth::{Role, TenantId},
+    credential::{AuthTokens, CredentialError, TokenIssuerTrait},
+};
+
+pub const ACCESS_TOKEN_USE: &str = "access";
+pub const REFRESH_TOKEN_USE: &str = "refresh";
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IssuedClaims {
+    sub: String,
+    iat: u64,
+    exp: u64,
+    #[serde(skip_serializing_if = "Vec::is_empty", default)]
+    roles: Vec<Role>,
+    #[serde(default)]
+    tenant: TenantId,
+    /// Token version of the credential, bumped when the password changes.
+    #[serde(default)]
+    ver: u32,
+    token_use: String,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    iss: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    aud: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct JwtTokenIssuer {
+    algorithm: Algorithm,
+    encoding_key: Arc<EncodingKey>,
+    decoding_key: Arc<DecodingKey>,
+    issuer: Option<String>,
+    audience: Option<String>,
+    access_token_ttl: Duration,
+    refresh_token_ttl: Duration,
+}
+
+impl JwtTokenIssuer {
+    pub fn hs256(secret: &[u8]) -> Self {
+        Self::new(
+            Algorithm::HS256,
+            EncodingKey::from_secret(secret),
+            DecodingKey::from_secret(secret),
+        )
+    }
+
+    pub fn hs256_from_file(path: &Path) -> Result<Self, CredentialError> {
+        let secret = Self::read_key_file(path)?;
+        Ok(Self::hs256(
+            String::from_utf8_lossy(&secret).trim().as_bytes(),
+        ))
+    }
+
+    pub fn rs256(private_key_pem: &[u8], public_key_pem: &[u8]) -> Result<Self, CredentialError> {
+        let encoding_key = EncodingKey::from_rsa_pem(private_key_pem)
+            .map_err(|e| CredentialError::Unknown(e.into()))?;
+        let decoding_key = DecodingKey::from_rsa_pem(public_key_pem)
+            .map_err(|e| CredentialError::Unknown(e.into()))?;
+        Ok(Self::new(Algorithm::RS256, encoding_key, decoding_key))
+    }
+
+    pub fn rs256_from_files(
+        private_key_path: &Path,
+        public_key_path: &Path,
+    ) -> Result<Self, CredentialError> {
+        Self::rs256(
+            &Self::read_key_file(private_key_path)?,
+            &Self::read_key_file(public_key_path)?,
+        )
+    }
+
+    pub fn with_issuer(mut self, issuer: &str) -> Self {
+        self.issuer = Some(issuer.to_string());
+        self
+    }
+
+    pub fn with_audience(mut self, audience: &str) -> Self {
+        self.audience = Some(audience.to_string());
+        self
+    }
+
+    pub fn with_ttl(mut self, access_token_ttl: Duration, refresh_token_ttl: Duration) -> Self {
+        self.access_token_ttl = access_token_ttl;
+        self.refresh_token_ttl = refresh_token_ttl;
+        self
+    }
+
+    fn new(algorithm: Algorithm, encoding_key: EncodingKey, decoding_key: DecodingKey) -> Self {
+        Self {
+            algorithm,
+            encoding_key: Arc::new(encoding_key),
+            decoding_key: Arc::new(decoding_key),
+            issuer: None,
+            audience: None,
+            access_token_ttl: Duration::from_secs(15 * 60),
+            refresh_token_ttl: Duration::from_secs(7 * 24 * 60 * 60),
+        }
+    }
+
+    fn read_key_file(path: &Path) -> Result<Vec<u8>, CredentialError> {
+        std::fs::read(path).map_err(|e| {
+            CredentialError::Unknown(anyhow::anyhow!("cannot read {}: {}", path.display(), e))
+        })
+    }
+
+    fn encode(
+        &self,
+        user_id: &Uuid,
+        tenant: &TenantId,
+        roles: &[Role],
+        token_version: &u32,
+        token_use: &str,
+        ttl: &Duration,
+    ) -> Result<String, CredentialError> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| CredentialError::Unknown(e.into()))?;
+        let claims = IssuedClaims {
+            sub: user_id.to_string(),
+            iat: now.as_secs(),
+            exp: (now + *ttl).as_secs(),
+            roles: roles.to_vec(),
+            tenant: tenant.clone(),
+            ver: *token_version,
+            token_use: token_use.to_string(),
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+        };
+        jsonwebtoken::encode(&Header::new(self.algorithm), &claims, &self.encoding_key)
+            .map_err(|e| CredentialError::Unknown(e.into()))
+    }
+}
+
+impl TokenIssuerTrait for JwtTokenIssuer {
+    fn issue(
+        &self,
+        user_id: &Uuid,
+        tenant: &TenantId,
+        roles: &[Role],
+        token_version: &u32,
+    ) -> Result<AuthTokens, CredentialError> {
+        let access_token = self.encode(
+            user_id,
+            tenant,
+            roles,
+            token_version,
+            ACCESS_TOKEN_USE,
+            &self.access_token_ttl,
+        )?;
+        let refresh_token = self.encode(
+            user_id,
+            tenant,
+            &[],
+            token_version,
+            REFRESH_TOKEN_USE,
+            &self.refresh_token_ttl,
+        )?;
+        Ok(AuthTokens::new(
+            &access_token,
+            &refresh_token,
+            self.access_token_ttl.as_secs(),
+        ))
+    }
+
+    fn verify_refresh_token(
+        &self,
+        refresh_token: &str,
+    ) -> Result<(Uuid, TenantId, u32), CredentialError> {
+        let mut validation = Validation::new(self.algorithm);
+        match &self.audience {
+            Some(audience) => validation.set_audience(&[audience]),
+            None => validation.validate_aud = false,
+        }
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+        }
+        let claims =
+            jsonwebtoken::decode::<IssuedClaims>(refresh_token, &self.decoding_key, &validation)
+                .map_err(|_| CredentialError::InvalidRefreshToken)?
+                .claims;
+        if claims.token_use != REFRESH_TOKEN_USE {
+            return Err(CredentialError::InvalidRefreshToken);
+        }
+        let user_id =
+            Uuid::parse_str(&claims.sub).map_err(|_| CredentialError::InvalidRefreshToken)?;
+        Ok((user_id, claims.tenant, claims.ver))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::{AuthError, AuthToken, AuthenticatorTrait, Role, TenantId},
+            credential::{CredentialError, TokenIssuerTrait},
+        },
+        outbound::jwt_authenticator_adapter::jwt_authenticator::JwtAuthenticator,
+    };
+
+    use super::JwtTokenIssuer;
+
+    #[tokio::test]
+    async fn test_access_token_accepted_by_authenticator() {
+        let issuer = JwtTokenIssuer::hs256(b"secret");
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let user_id = Uuid::new_v4();
+        let tenant = TenantId::new("acme").unwrap();
+        let tokens = issuer
+            .issue(&user_id, &tenant, &[Role::new("admin")], &0)
+            .unwrap();
+        let principal = authenticator
+            .authenticate(&AuthToken::Bearer(tokens.get_access_token().to_string()))
+            .await
+            .unwrap();
+        assert_eq!(principal.get_user_id(), Some(&user_id));
+        assert!(principal.has_role(&Role::new("admin")));
+        assert_eq!(principal.get_tenant(), Some(&tenant));
+    }
+
+    #[tokio::test]
+    async fn test_refresh_token_rejected_by_authenticator() {
+        let issuer = JwtTokenIssuer::hs256(b"secret");
+        let authenticator = JwtAuthenticator::new().with_hs256_secret(b"secret");
+        let tokens = issuer
+            .issue(&Uuid::new_v4(), &TenantId::default(), &[], &0)
+            .unwrap();
+        let result = authenticator
+            .authenticate(&AuthToken::Bearer(tokens.get_refresh_token().to_string()))
+            .await;
+        assert!(matches!(result, Err(AuthError::InvalidToken { .. })));
+    }
+
+    #[test]
+    fn test_verify_refresh_token() {
+        let issuer = JwtTokenIssuer::hs256(b"secret");
+        let user_id = Uuid::new_v4();
+        let tenant = TenantId::new("acme").unwrap();
+        let tokens = issuer.issue(&user_id, &tenant, &[], &3).unwrap();
+        assert_eq!(
+            issuer
+                .verify_refresh_token(tokens.get_refresh_token())
+                .unwrap(),
+            (user_id, tenant, 3)
+        );
+        assert!(matches!(
+            issuer.verify_refresh_token(tokens.get_access_token()),
+            Err(CredentialError::InvalidRefreshToken)
+        ));
+    }
+}
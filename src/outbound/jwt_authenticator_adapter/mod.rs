@@ -0,0 +1,2 @@
+pub mod jwt_authenticator;
+pub mod jwt_token_issuer;
@@ -0,0 +1,105 @@
+use std::{future::Future, path::PathBuf, sync::Arc};
+
+use tokio::{io::AsyncWriteExt, sync::Mutex};
+
+use crate::business::{
+    mail::{EmailMessage, MailerError, MailerTrait},
+    user::EmailAddress,
+};
+
+use super::render_message;
+
+#[derive(Debug, Clone)]
+enum FileMailerTarget {
+    Stdout,
+    File(PathBuf),
+}
+
+#[derive(Debug, Clone)]
+pub struct FileMailer {
+    from: EmailAddress,
+    target: FileMailerTarget,
+    lock: Arc<Mutex<()>>,
+}
+
+impl FileMailer {
+    pub fn stdout(from: &EmailAddress) -> Self {
+        Self {
+            from: from.clone(),
+            target: FileMailerTarget::Stdout,
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    pub fn file(from: &EmailAddress, path: &std::path::Path) -> Self {
+        Self {
+            from: from.clone(),
+            target: FileMailerTarget::File(path.to_path_buf()),
+            lock: Arc::new(Mutex::new(())),
+        }
+    }
+
+    async fn write(&self, content: &[u8]) -> std::io::Result<()> {
+        let _guard = self.lock.lock().await;
+        match &self.target {
+            FileMailerTarget::Stdout => {
+                let mut stdout = tokio::io::stdout();
+                stdout.write_all(content).await?;
+                stdout.flush().await
+            }
+            FileMailerTarget::File(path) => {
+                let mut file = tokio::fs::OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(path)
+                    .await?;
+                file.write_all(content).await?;
+                file.flush().await
+            }
+        }
+    }
+}
+
+impl MailerTrait for FileMailer {
+    fn send(&self, message: &EmailMessage) -> impl Future<Output = Result<(), MailerError>> + Send {
+        Box::pin(async move {
+            let content = format!("{}.\r\n", render_message(&self.from, message));
+            self.write(content.as_bytes())
+                .await
+                .map_err(|e| MailerError::Transport {
+                    reason: e.to_string(),
+                })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::business::{
+        mail::{EmailMessage, MailerTrait},
+        user::EmailAddress,
+    };
+
+    use super::FileMailer;
+
+    #[tokio::test]
+    async fn test_file_mailer_appends_messages() {
+        let path = std::env::temp_dir().join(format!("mailbox-{}.txt", uuid::Uuid::new_v4()));
+        let from = EmailAddress::new("no-reply@example.com").unwrap();
+        let to = EmailAddress::new("john@example.com").unwrap();
+        let mailer = FileMailer::file(&from, &path);
+        mailer
+            .send(&EmailMessage::new(&to, "First", "Hello"))
+            .await
+            .unwrap();
+        mailer
+            .send(&EmailMessage::new(&to, "Second", "World"))
+            .await
+            .unwrap();
+        let content = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert!(content.contains("To: john@example.com"));
+        assert!(content.contains("Subject: First"));
+        assert!(content.contains("Subject: Second"));
+    }
+}
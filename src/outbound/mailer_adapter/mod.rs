@@ -0,0 +1,19 @@
+pub mod file_mailer;
+pub mod smtp_mailer;
+
+use crate::business::{mail::EmailMessage, user::EmailAddress};
+
+fn render_message(from: &EmailAddress, message: &EmailMessage) -> String {
+    let body = message
+        .get_body()
+        .lines()
+        .collect::<Vec<&str>>()
+        .join("\r\n");
+    format!(
+        "From: {}\r\nTo: {}\r\nSubject: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}\r\n",
+        from,
+        message.get_to(),
+        message.get_subject().replace(['\r', '\n'], " "),
+        body
+    )
+}
@@ -0,0 +1,249 @@
+use std::{future::Future, time::Duration};
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{
+        tcp::{OwnedReadHalf, OwnedWriteHalf},
+        TcpStream,
+    },
+};
+
+use crate::business::{
+    mail::{EmailMessage, MailerError, MailerTrait},
+    user::EmailAddress,
+};
+
+use super::render_message;
+
+#[derive(Debug, Clone)]
+pub struct SmtpMailer {
+    host: String,
+    port: u16,
+    from: EmailAddress,
+    hello_name: String,
+    timeout: Duration,
+}
+
+struct SmtpConnection {
+    reader: BufReader<OwnedReadHalf>,
+    writer: OwnedWriteHalf,
+}
+
+impl SmtpConnection {
+    async fn read_reply(&mut self) -> Result<(u16, String), MailerError> {
+        let mut reply = String::new();
+        loop {
+            let mut line = String::new();
+            if self
+                .reader
+                .read_line(&mut line)
+                .await
+                .map_err(transport_error)?
+                == 0
+            {
+                return Err(MailerError::Transport {
+                    reason: String::from("connection closed by server"),
+                });
+            }
+            reply.push_str(&line);
+            if line.len() < 4 || line.as_bytes()[3] != b'-' {
+                let code = line
+                    .get(..3)
+                    .and_then(|c| c.parse::<u16>().ok())
+                    .ok_or_else(|| MailerError::Transport {
+                        reason: format!("malformed reply {}", line.trim_end()),
+                    })?;
+                return Ok((code, reply.trim_end().to_string()));
+            }
+        }
+    }
+
+    async fn expect(&mut self, expected: &[u16]) -> Result<(), MailerError> {
+        let (code, reply) = self.read_reply().await?;
+        if expected.contains(&code) {
+            Ok(())
+        } else {
+            Err(MailerError::Rejected { code, reply })
+        }
+    }
+
+    async fn command(&mut self, command: &str, expected: &[u16]) -> Result<(), MailerError> {
+        self.writer
+            .write_all(format!("{}\r\n", command).as_bytes())
+            .await
+            .map_err(transport_error)?;
+        self.expect(expected).await
+    }
+}
+
+fn transport_error(error: std::io::Error) -> MailerError {
+    MailerError::Transport {
+        reason: error.to_string(),
+    }
+}
+
+impl SmtpMailer {
+    pub fn new(host: &str, port: u16, from: &EmailAddress) -> Self {
+        Self {
+            host: host.to_string(),
+            port,
+            from: from.clone(),
+            hello_name: String::from("localhost"),
+            timeout: Duration::from_secs(30),
+        }
+    }
+
+    pub fn with_hello_name(mut self, hello_name: &str) -> Self {
+        self.hello_name = hello_name.to_string();
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    async fn deliver(&self, message: &EmailMessage) -> Result<(), MailerError> {
+        let stream = TcpStream::connect((self.host.as_str(), self.port))
+            .await
+            .map_err(transport_error)?;
+        let (reader, writer) = stream.into_split();
+        let mut connection = SmtpConnection {
+            reader: BufReader::new(reader),
+            writer,
+        };
+        connection.expect(&[220]).await?;
+        connection
+            .command(&format!("EHLO {}", self.hello_name), &[250])
+            .await?;
+        connection
+            .command(&format!("MAIL FROM:<{}>", self.from), &[250])
+            .await?;
+        connection
+            .command(&format!("RCPT TO:<{}>", message.get_to()), &[250, 251])
+            .await?;
+        connection.command("DATA", &[354]).await?;
+        let data = render_message(&self.from, message)
+            .split("\r\n")
+            .map(|line| match line.starts_with('.') {
+                true => format!(".{}", line),
+                false => line.to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join("\r\n");
+        connection
+            .writer
+            .write_all(data.as_bytes())
+            .await
+            .map_err(transport_error)?;
+        connection.command(".", &[250]).await?;
+        connection.command("QUIT", &[221]).await
+    }
+}
+
+impl MailerTrait for SmtpMailer {
+    fn send(&self, message: &EmailMessage) -> impl Future<Output = Result<(), MailerError>> + Send {
+        Box::pin(async move {
+            tokio::time::timeout(self.timeout, self.deliver(message))
+                .await
+                .map_err(|_| MailerError::Transport {
+                    reason: String::from("timed out"),
+                })?
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tokio::{
+        io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+        net::TcpListener,
+        task::JoinHandle,
+    };
+
+    use crate::business::{
+        mail::{EmailMessage, MailerError, MailerTrait},
+        user::EmailAddress,
+    };
+
+    use super::SmtpMailer;
+
+    async fn smtp_sink(reject_recipient: bool) -> (u16, JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let handle = tokio::spawn(async move {
+            let (stream, _) = listener.accept().await.unwrap();
+            let (reader, mut writer) = stream.into_split();
+            let mut reader = BufReader::new(reader);
+            let mut data = String::new();
+            let mut in_data = false;
+            writer.write_all(b"220 sink ready\r\n").await.unwrap();
+            loop {
+                let mut line = String::new();
+                if reader.read_line(&mut line).await.unwrap() == 0 {
+                    break;
+                }
+                if in_data {
+                    if line == ".\r\n" {
+                        in_data = false;
+                        writer.write_all(b"250 queued\r\n").await.unwrap();
+                    } else {
+                        data.push_str(&line);
+                    }
+                    continue;
+                }
+                let reply: &[u8] = match line.get(..4).unwrap_or_default() {
+                    "EHLO" => b"250-sink\r\n250 8BITMIME\r\n",
+                    "RCPT" if reject_recipient => b"550 no such user\r\n",
+                    "DATA" => {
+                        in_data = true;
+                        b"354 go ahead\r\n"
+                    }
+                    "QUIT" => {
+                        writer.write_all(b"221 bye\r\n").await.unwrap();
+                        break;
+                    }
+                    _ => b"250 ok\r\n",
+                };
+                writer.write_all(reply).await.unwrap();
+            }
+            data
+        });
+        (port, handle)
+    }
+
+    fn message() -> EmailMessage {
+        EmailMessage::new(
+            &EmailAddress::new("john@example.com").unwrap(),
+            "Verify",
+            "Hello\n.hidden",
+        )
+    }
+
+    #[tokio::test]
+    async fn test_smtp_delivery_ok() {
+        let (port, sink) = smtp_sink(false).await;
+        let from = EmailAddress::new("no-reply@example.com").unwrap();
+        SmtpMailer::new("127.0.0.1", port, &from)
+            .send(&message())
+            .await
+            .unwrap();
+        let data = sink.await.unwrap();
+        assert!(data.contains("To: john@example.com"));
+        assert!(data.contains("Subject: Verify"));
+        assert!(data.contains("\r\n..hidden\r\n"));
+    }
+
+    #[tokio::test]
+    async fn test_smtp_recipient_rejected() {
+        let (port, _sink) = smtp_sink(true).await;
+        let from = EmailAddress::new("no-reply@example.com").unwrap();
+        let result = SmtpMailer::new("127.0.0.1", port, &from)
+            .send(&message())
+            .await;
+        assert!(matches!(
+            result,
+            Err(MailerError::Rejected { code: 550, .. })
+        ));
+    }
+}
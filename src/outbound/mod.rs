@@ -1,2 +1,14 @@
+pub mod api_key_authenticator_adapter;
+pub mod argon2_password_hasher_adapter;
+pub mod audit_trail_adapter;
+pub mod encryption_adapter;
+pub mod event_publisher_adapter;
+pub mod event_sourced_repository_adapter;
+pub mod file_repository_adapter;
+pub mod id_generator_adapter;
 pub mod in_memory_repository_adapter;
+pub mod jwt_authenticator_adapter;
+pub mod mailer_adapter;
 pub mod repository_trait;
+pub mod tracing_adapter;
+pub mod webhook_sender_adapter;
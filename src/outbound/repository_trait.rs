@@ -33,6 +33,65 @@ pub trait RepositoryTrait: Clone + Sync + Send + 'static {
     ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send;
 }
 
+pub trait OutboxTrait: RepositoryTrait {
+    type Event: Clone + Sync + Send + 'static;
+    fn pending_events(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<Self::Event>, Self::Error>> + Send;
+
+    fn acknowledge_event(
+        &self,
+        event: &Self::Event,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
+/// Groups several changes so they are applied together on commit, or not at
+/// all on rollback. Dropping an uncommitted transaction rolls it back.
+pub trait UnitOfWorkTrait: RepositoryTrait {
+    type Transaction: TransactionTrait<Id = Self::Id, Entity = Self::Entity, Error = Self::Error>;
+    fn begin(&self) -> impl Future<Output = Result<Self::Transaction, Self::Error>> + Send;
+}
+
+/// Staged changes of a unit of work; reads see the changes staged so far.
+pub trait TransactionTrait: Sync + Send + 'static {
+    type Id: Clone + Sync + Send + 'static;
+    type Entity: Clone + Sync + Send + 'static;
+    type Error: Debug + Display;
+    fn save(
+        &mut self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send;
+
+    fn update(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send;
+
+    /// Replaces the entity like `update`, and the values it held in every
+    /// change recorded before, so that none of them is kept.
+    fn erase(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send;
+
+    fn delete(
+        &mut self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send;
+
+    fn commit(self) -> impl Future<Output = Result<(), Self::Error>> + Send;
+
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>> + Send;
+}
+
 pub trait FindOptionTrait: Clone + Sync + Send + 'static {
     type QueryFilter: Clone + Sync + Send + 'static;
     fn get_query(&self) -> Self::QueryFilter;
@@ -0,0 +1,190 @@
+use std::str::FromStr;
+
+use opentelemetry::{trace::TracerProvider as _, KeyValue};
+use opentelemetry_otlp::{SpanExporter, WithExportConfig};
+use opentelemetry_sdk::{
+    propagation::TraceContextPropagator, runtime, trace::TracerProvider, Resource,
+};
+use thiserror::Error;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt, EnvFilter, Layer};
+
+const DEFAULT_FILTER: &str = "info";
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = TelemetryError;
+
+    fn from_str(raw: &str) -> Result<Self, Self::Err> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            _ => Err(TelemetryError::InvalidLogFormat {
+                format: raw.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum TelemetryError {
+    #[error("Log format {format} is invalid, expected text or json")]
+    InvalidLogFormat { format: String },
+    #[error("Log filter {filter} is invalid: {reason}")]
+    InvalidFilter { filter: String, reason: String },
+    #[error("OTLP exporter could not be built: {reason}")]
+    InvalidExporter { reason: String },
+    #[error("Telemetry is already initialized: {reason}")]
+    AlreadyInitialized { reason: String },
+}
+
+/// Process-wide log output and trace export. Logs go to stdout, as text or
+/// one JSON object per line with the enclosing spans; when an OTLP endpoint
+/// is set, spans are also exported over HTTP to a collector such as
+/// `http://localhost:4318`.
+#[derive(Debug, Clone)]
+pub struct Telemetry {
+    service_name: String,
+    format: LogFormat,
+    filter: String,
+    otlp_endpoint: Option<String>,
+}
+
+/// Flushes the spans still buffered by the exporter when dropped.
+#[derive(Debug)]
+pub struct TelemetryGuard {
+    provider: Option<TracerProvider>,
+}
+
+impl Drop for TelemetryGuard {
+    fn drop(&mut self) {
+        if let Some(provider) = self.provider.take() {
+            provider.shutdown().ok();
+        }
+    }
+}
+
+impl Telemetry {
+    pub fn new(service_name: &str) -> Self {
+        Self {
+            service_name: service_name.to_string(),
+            format: LogFormat::default(),
+            filter: String::from(DEFAULT_FILTER),
+            otlp_endpoint: None,
+        }
+    }
+
+    pub fn with_format(mut self, format: LogFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// `RUST_LOG` syntax, e.g. `info,i_tantana=debug`.
+    pub fn with_filter(mut self, filter: &str) -> Self {
+        self.filter = filter.to_string();
+        self
+    }
+
+    /// Base URL of the collector; the traces path is appended when missing.
+    pub fn with_otlp_endpoint(mut self, endpoint: &str) -> Self {
+        let endpoint = endpoint.trim_end_matches('/');
+        self.otlp_endpoint = Some(match endpoint.ends_with(OTLP_TRACES_PATH) {
+            true => endpoint.to_string(),
+            false => format!("{endpoint}{OTLP_TRACES_PATH}"),
+        });
+        self
+    }
+
+    pub fn get_format(&self) -> LogFormat {
+        self.format
+    }
+
+    pub fn get_otlp_endpoint(&self) -> Option<&str> {
+        self.otlp_endpoint.as_deref()
+    }
+
+    /// Installs the global subscriber and W3C trace context propagator. Must
+    /// be called from within a Tokio runtime when an OTLP endpoint is set.
+    pub fn init(self) -> Result<TelemetryGuard, TelemetryError> {
+        let filter =
+            EnvFilter::try_new(&self.filter).map_err(|e| TelemetryError::InvalidFilter {
+                filter: self.filter.clone(),
+                reason: e.to_string(),
+            })?;
+        opentelemetry::global::set_text_map_propagator(TraceContextPropagator::new());
+        let fmt = match self.format {
+            LogFormat::Text => tracing_subscriber::fmt::layer().boxed(),
+            LogFormat::Json => tracing_subscriber::fmt::layer()
+                .json()
+                .with_current_span(true)
+                .with_span_list(true)
+                .boxed(),
+        };
+        let provider = self
+            .otlp_endpoint
+            .as_deref()
+            .map(|endpoint| self.provider(endpoint));
+        let provider = provider.transpose()?;
+        let otel = provider.as_ref().map(|provider| {
+            tracing_opentelemetry::layer().with_tracer(provider.tracer(self.service_name.clone()))
+        });
+        tracing_subscriber::registry()
+            .with(filter)
+            .with(fmt)
+            .with(otel)
+            .try_init()
+            .map_err(|e| TelemetryError::AlreadyInitialized {
+                reason: e.to_string(),
+            })?;
+        Ok(TelemetryGuard { provider })
+    }
+
+    fn provider(&self, endpoint: &str) -> Result<TracerProvider, TelemetryError> {
+        let exporter = SpanExporter::builder()
+            .with_http()
+            .with_endpoint(endpoint)
+            .build()
+            .map_err(|e| TelemetryError::InvalidExporter {
+                reason: e.to_string(),
+            })?;
+        Ok(TracerProvider::builder()
+            .with_batch_exporter(exporter, runtime::Tokio)
+            .with_resource(Resource::new([KeyValue::new(
+                "service.name",
+                self.service_name.clone(),
+            )]))
+            .build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{LogFormat, Telemetry};
+
+    #[test]
+    fn test_configuration() {
+        assert_eq!("JSON".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("text".parse::<LogFormat>().unwrap(), LogFormat::Text);
+        assert!("xml".parse::<LogFormat>().is_err());
+        let telemetry = Telemetry::new("users").with_otlp_endpoint("http://localhost:4318/");
+        assert_eq!(
+            telemetry.get_otlp_endpoint(),
+            Some("http://localhost:4318/v1/traces")
+        );
+        let telemetry = telemetry.with_otlp_endpoint("http://collector/v1/traces");
+        assert_eq!(
+            telemetry.get_otlp_endpoint(),
+            Some("http://collector/v1/traces")
+        );
+        assert!(Telemetry::new("users")
+            .with_filter("i_tantana=loud")
+            .init()
+            .is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::{fmt::Display, future::Future};
+
+use tracing::{debug, debug_span, Instrument};
+
+use crate::{
+    business::{
+        audit::AuditContext,
+        auth::TenantId,
+        user::{
+            model::user::UserError, UserChangeFeedTrait, UserChangeSubscription,
+            UserRepositoryTrait,
+        },
+    },
+    outbound::repository_trait::{OutboxTrait, RepositoryTrait, TransactionTrait, UnitOfWorkTrait},
+};
+
+/// Wraps a repository so every call runs in its own debug span, nested under
+/// the span of the service operation that made it.
+#[derive(Debug, Clone)]
+pub struct TracedRepository<R> {
+    inner: R,
+}
+
+impl<R> TracedRepository<R> {
+    pub fn new(inner: R) -> Self {
+        Self { inner }
+    }
+}
+
+#[derive(Debug)]
+pub struct TracedTransaction<T> {
+    inner: T,
+}
+
+fn traced<T, E: Display>(
+    operation: &'static str,
+    future: impl Future<Output = Result<T, E>> + Send,
+) -> impl Future<Output = Result<T, E>> + Send {
+    async move {
+        let result = future.await;
+        if let Err(error) = &result {
+            debug!(%error, "repository call failed");
+        }
+        result
+    }
+    .instrument(debug_span!("repository", operation))
+}
+
+impl<R: RepositoryTrait> RepositoryTrait for TracedRepository<R> {
+    type Id = R::Id;
+    type Entity = R::Entity;
+    type Error = R::Error;
+    type FindOptions = R::FindOptions;
+    type FindResult = R::FindResult;
+
+    fn save(
+        &self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("save", self.inner.save(entity))
+    }
+
+    fn update(
+        &self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("update", self.inner.update(entity_id, entity))
+    }
+
+    fn delete(&self, entity_id: &Self::Id) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        traced("delete", self.inner.delete(entity_id))
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("find_by_id", self.inner.find_by_id(entity_id))
+    }
+
+    fn find_all(
+        &self,
+        options: &Self::FindOptions,
+    ) -> impl Future<Output = Result<Self::FindResult, Self::Error>> + Send {
+        traced("find_all", self.inner.find_all(options))
+    }
+}
+
+impl<R: OutboxTrait> OutboxTrait for TracedRepository<R> {
+    type Event = R::Event;
+
+    fn pending_events(
+        &self,
+        limit: usize,
+    ) -> impl Future<Output = Result<Vec<Self::Event>, Self::Error>> + Send {
+        traced("pending_events", self.inner.pending_events(limit))
+    }
+
+    fn acknowledge_event(
+        &self,
+        event: &Self::Event,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        traced("acknowledge_event", self.inner.acknowledge_event(event))
+    }
+}
+
+impl<R: UnitOfWorkTrait> UnitOfWorkTrait for TracedRepository<R> {
+    type Transaction = TracedTransaction<R::Transaction>;
+
+    fn begin(&self) -> impl Future<Output = Result<Self::Transaction, Self::Error>> + Send {
+        let begin = traced("begin", self.inner.begin());
+        async move {
+            Ok(TracedTransaction {
+                inner: begin.await?,
+            })
+        }
+    }
+}
+
+impl<T: TransactionTrait> TransactionTrait for TracedTransaction<T> {
+    type Id = T::Id;
+    type Entity = T::Entity;
+    type Error = T::Error;
+
+    fn save(
+        &mut self,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("transaction.save", self.inner.save(entity))
+    }
+
+    fn update(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("transaction.update", self.inner.update(entity_id, entity))
+    }
+
+    fn erase(
+        &mut self,
+        entity_id: &Self::Id,
+        entity: &Self::Entity,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("transaction.erase", self.inner.erase(entity_id, entity))
+    }
+
+    fn delete(
+        &mut self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        traced("transaction.delete", self.inner.delete(entity_id))
+    }
+
+    fn find_by_id(
+        &self,
+        entity_id: &Self::Id,
+    ) -> impl Future<Output = Result<Self::Entity, Self::Error>> + Send {
+        traced("transaction.find_by_id", self.inner.find_by_id(entity_id))
+    }
+
+    fn commit(self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        traced("transaction.commit", self.inner.commit())
+    }
+
+    fn rollback(self) -> impl Future<Output = Result<(), Self::Error>> + Send {
+        traced("transaction.rollback", self.inner.rollback())
+    }
+}
+
+impl<R: UserChangeFeedTrait> UserChangeFeedTrait for TracedRepository<R> {
+    fn subscribe_changes(
+        &self,
+        last_sequence: Option<u64>,
+    ) -> impl Future<Output = Result<UserChangeSubscription, UserError>> + Send {
+        traced(
+            "subscribe_changes",
+            self.inner.subscribe_changes(last_sequence),
+        )
+    }
+}
+
+impl<R: UserRepositoryTrait> UserRepositoryTrait for TracedRepository<R> {
+    fn for_tenant(&self, tenant: &TenantId) -> Self {
+        Self::new(self.inner.for_tenant(tenant))
+    }
+
+    fn audited(&self, audit: &AuditContext) -> Self {
+        Self::new(self.inner.audited(audit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use uuid::Uuid;
+
+    use crate::{
+        business::{
+            auth::TenantId,
+            user::{model::user::UserError, EmailAddress, Name, User, UserRepositoryTrait},
+        },
+        outbound::{
+            in_memory_repository_adapter::in_memory_user_repository::InMemoryUserRepository,
+            repository_trait::{RepositoryTrait, TransactionTrait, UnitOfWorkTrait},
+        },
+    };
+
+    use super::TracedRepository;
+
+    #[tokio::test]
+    async fn test_delegates_to_inner_repository() {
+        let inner = InMemoryUserRepository::new();
+        let repository = TracedRepository::new(inner.clone());
+        let user = User::new(
+            &Uuid::from_u128(42),
+            &Name::new("Jane").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("jane@example.com").unwrap(),
+        );
+        let mut transaction = repository.begin().await.unwrap();
+        let saved = transaction.save(&user).await.unwrap();
+        transaction.commit().await.unwrap();
+        assert_eq!(inner.find_by_id(saved.get_id()).await.unwrap(), saved);
+        let other = repository.for_tenant(&TenantId::new("acme").unwrap());
+        assert!(matches!(
+            other.find_by_id(saved.get_id()).await,
+            Err(UserError::UserNotExists { .. })
+        ));
+        assert!(repository.find_by_id(&Uuid::nil()).await.is_err());
+    }
+}
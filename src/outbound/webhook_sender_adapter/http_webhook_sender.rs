@@ -0,0 +1,239 @@
+use std::{collections::HashMap, future::Future, io, sync::Arc, time::Duration};
+
+use opentelemetry::propagation::TextMapPropagator;
+use opentelemetry_sdk::propagation::TraceContextPropagator;
+use reqwest::{
+    dns::{Addrs, Name, Resolve, Resolving},
+    header, Client,
+};
+use tracing::{info_span, Instrument};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::business::webhook::{
+    WebhookAllowList, WebhookError, WebhookRequest, WebhookSenderTrait,
+};
+
+pub const WEBHOOK_ID_HEADER: &str = "webhook-id";
+pub const WEBHOOK_EVENT_HEADER: &str = "webhook-event";
+pub const WEBHOOK_TIMESTAMP_HEADER: &str = "webhook-timestamp";
+pub const WEBHOOK_SIGNATURE_HEADER: &str = "webhook-signature";
+
+/// Resolves host names, dropping the internal addresses the allow list
+/// refuses, so that a public name cannot point deliveries inward.
+struct GuardedResolver {
+    allow_list: WebhookAllowList,
+}
+
+impl Resolve for GuardedResolver {
+    fn resolve(&self, name: Name) -> Resolving {
+        let allow_list = self.allow_list.clone();
+        Box::pin(async move {
+            let host = name.as_str().to_string();
+            let addresses: Vec<_> = tokio::net::lookup_host((host.as_str(), 0))
+                .await?
+                .filter(|address| !allow_list.refuses_address(&host, &address.ip()))
+                .collect();
+            if addresses.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::PermissionDenied,
+                    format!("{} resolves to internal addresses only", host),
+                )
+                .into());
+            }
+            Ok(Box::new(addresses.into_iter()) as Addrs)
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct HttpWebhookSender {
+    client: Client,
+    allow_list: WebhookAllowList,
+}
+
+impl Default for HttpWebhookSender {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10), WebhookAllowList::default())
+            .expect("default HTTP client")
+    }
+}
+
+impl HttpWebhookSender {
+    pub fn new(timeout: Duration, allow_list: WebhookAllowList) -> Result<Self, WebhookError> {
+        let client = Client::builder()
+            .timeout(timeout)
+            .redirect(reqwest::redirect::Policy::none())
+            .dns_resolver(Arc::new(GuardedResolver {
+                allow_list: allow_list.clone(),
+            }))
+            .build()
+            .map_err(|e| WebhookError::Unknown(e.into()))?;
+        Ok(Self { client, allow_list })
+    }
+}
+
+impl WebhookSenderTrait for HttpWebhookSender {
+    fn send(
+        &self,
+        request: &WebhookRequest,
+    ) -> impl Future<Output = Result<u16, WebhookError>> + Send {
+        let span = info_span!(
+            "webhook_send",
+            delivery_id = %request.get_delivery_id(),
+            event = %request.get_event_type(),
+        );
+        let mut trace_context: HashMap<String, String> = HashMap::new();
+        TraceContextPropagator::new().inject_context(&span.context(), &mut trace_context);
+        trace_context.retain(|_, value| !value.is_empty());
+        Box::pin(
+            async move {
+                // Addresses in the url are not resolved, so they are checked here.
+                if self.allow_list.refuses(request.get_url()) {
+                    return Err(WebhookError::InternalTarget {
+                        url: request.get_url().clone(),
+                    });
+                }
+                trace_context
+                    .into_iter()
+                    .fold(
+                        self.client.post(request.get_url().to_string()),
+                        |builder, (name, value)| builder.header(name, value),
+                    )
+                    .header(header::CONTENT_TYPE, "application/json")
+                    .header(WEBHOOK_ID_HEADER, request.get_delivery_id().to_string())
+                    .header(WEBHOOK_EVENT_HEADER, request.get_event_type().to_string())
+                    .header(
+                        WEBHOOK_TIMESTAMP_HEADER,
+                        request.get_timestamp().to_string(),
+                    )
+                    .header(
+                        WEBHOOK_SIGNATURE_HEADER,
+                        format!("sha256={}", request.get_signature()),
+                    )
+                    .body(request.get_body().to_string())
+                    .send()
+                    .await
+                    .map(|response| response.status().as_u16())
+                    .map_err(|e| WebhookError::Transport {
+                        url: request.get_url().clone(),
+                        reason: e.to_string(),
+                    })
+            }
+            .instrument(span),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{
+        sync::{Arc, Mutex},
+        time::Duration,
+    };
+
+    use axum::{
+        extract::State,
+        http::{HeaderMap, StatusCode},
+        routing::post,
+        Router,
+    };
+    use uuid::Uuid;
+
+    use crate::business::{
+        user::{EmailAddress, Name, User, UserEvent},
+        webhook::{
+            WebhookAllowList, WebhookDelivery, WebhookError, WebhookRequest, WebhookSenderTrait,
+            WebhookSubscription, WebhookUrl,
+        },
+    };
+
+    use super::{HttpWebhookSender, WEBHOOK_SIGNATURE_HEADER, WEBHOOK_TIMESTAMP_HEADER};
+
+    type Received = Arc<Mutex<Vec<(HeaderMap, String)>>>;
+
+    fn local_sender() -> HttpWebhookSender {
+        HttpWebhookSender::new(
+            Duration::from_secs(10),
+            WebhookAllowList::parse("127.0.0.1"),
+        )
+        .unwrap()
+    }
+
+    async fn receive(
+        State(received): State<Received>,
+        headers: HeaderMap,
+        body: String,
+    ) -> StatusCode {
+        received.lock().unwrap().push((headers, body));
+        StatusCode::NO_CONTENT
+    }
+
+    #[tokio::test]
+    async fn test_send_signed_payload() {
+        let received = Received::default();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        let router = Router::new()
+            .route("/hooks", post(receive))
+            .with_state(received.clone());
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let subscription = WebhookSubscription::new(
+            &Uuid::new_v4(),
+            &WebhookUrl::new(&format!("http://{}/hooks", address)).unwrap(),
+            &[],
+            "secret",
+            &0,
+        );
+        let event = UserEvent::created(&User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        ));
+        let delivery = WebhookDelivery::new(&subscription, &event, &0);
+        let status = local_sender()
+            .send(&WebhookRequest::new(&subscription, &delivery, &42))
+            .await
+            .unwrap();
+        assert_eq!(status, 204);
+
+        let received = received.lock().unwrap();
+        let (headers, body) = &received[0];
+        assert_eq!(body, delivery.get_payload());
+        assert_eq!(headers[WEBHOOK_TIMESTAMP_HEADER], "42");
+        assert_eq!(
+            headers[WEBHOOK_SIGNATURE_HEADER].to_str().unwrap(),
+            format!("sha256={}", subscription.sign(&42, body))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_receiver() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let address = listener.local_addr().unwrap();
+        drop(listener);
+        let subscription = WebhookSubscription::new(
+            &Uuid::new_v4(),
+            &WebhookUrl::new(&format!("http://{}/hooks", address)).unwrap(),
+            &[],
+            "secret",
+            &0,
+        );
+        let event = UserEvent::deleted(&User::new(
+            &Uuid::new_v4(),
+            &Name::new("John").unwrap(),
+            &Name::new("Doe").unwrap(),
+            &EmailAddress::new("john@example.com").unwrap(),
+        ));
+        let delivery = WebhookDelivery::new(&subscription, &event, &0);
+        let result = local_sender()
+            .send(&WebhookRequest::new(&subscription, &delivery, &0))
+            .await;
+        assert!(matches!(result, Err(WebhookError::Transport { .. })));
+        let result = HttpWebhookSender::default()
+            .send(&WebhookRequest::new(&subscription, &delivery, &0))
+            .await;
+        assert!(matches!(result, Err(WebhookError::InternalTarget { .. })));
+    }
+}